@@ -0,0 +1,66 @@
+//! Used by implementations whose `Dna` and `Rna` are generic over their capacity, and compare equal
+//! (and convert) across capacities.
+
+use utils::{DnaTrait, NucleotideError, RnaToDnaTrait, RnaTrait};
+
+pub trait Tests {
+    type Dna<'a>: DnaTrait<'a, Self::Rna<'a>> + PartialEq<Self::SmallDna<'a>> + 'a;
+    type Rna<'a>: RnaToDnaTrait<'a, Self::Dna<'a>>
+        + PartialEq<Self::SmallRna<'a>>
+        + for<'r> TryFrom<&'r Self::SmallRna<'a>, Error = NucleotideError>
+        + 'a;
+    /// The same `Dna`, with a capacity of 4 nucleotides.
+    type SmallDna<'a>: DnaTrait<'a, Self::SmallRna<'a>> + PartialEq<Self::Dna<'a>> + 'a;
+    /// The same `Rna`, with a capacity of 4 nucleotides.
+    type SmallRna<'a>: RnaToDnaTrait<'a, Self::SmallDna<'a>>
+        + PartialEq<Self::Rna<'a>>
+        + for<'r> TryFrom<&'r Self::Rna<'a>, Error = NucleotideError>
+        + 'a;
+
+    /// Different capacities compare equal, as long as the nucleotides are the same.
+    fn test_eq_across_capacities() {
+        let small = Self::SmallRna::new("CGAU").expect("RNA");
+        let rna = Self::Rna::new("CGAU").expect("RNA");
+        assert!(small == rna);
+        assert!(rna == small);
+
+        let longer = Self::Rna::new("CGAUA").expect("RNA");
+        assert!(small != longer);
+        assert!(longer != small);
+
+        let small_dna = Self::SmallDna::new("GCTA").expect("DNA");
+        let dna = Self::Dna::new("GCTA").expect("DNA");
+        assert!(small_dna == dna);
+        assert!(dna == small_dna);
+        assert!(small.into_dna() == rna.into_dna());
+    }
+
+    /// [`TryFrom`] copies to the other capacity, if the nucleotides fit there.
+    fn test_try_from_other_capacity() {
+        let rna = Self::Rna::new("CGAU").expect("RNA");
+        let small = Self::SmallRna::try_from(&rna).expect("fits");
+        assert!(small == rna);
+        assert!(Self::Rna::try_from(&small).expect("fits") == rna);
+
+        let longer = Self::Rna::new("CGAUA").expect("RNA");
+        assert_eq!(
+            Self::SmallRna::try_from(&longer).err(),
+            Some(NucleotideError::CapacityExceeded {
+                capacity: 4,
+                required: 5
+            })
+        );
+    }
+}
+
+/// The tests of [`Tests`] that `api_tests!(cross_capacity: T)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! cross_capacity_list {
+    ([$($args:tt)*]) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_eq_across_capacities()
+            test_try_from_other_capacity()
+        ] }
+    };
+}
@@ -0,0 +1,93 @@
+//! Used by implementations whose `Rna` has a constant-time `ct_eq` (which their [`PartialEq`] uses
+//! with their opt-in `constant_time_eq` feature).
+
+use core::cell::Cell;
+use utils::{RnaTrait, RnaTraitMut};
+
+pub trait Tests {
+    type Rna<'a>: RnaTraitMut<'a> + 'a;
+    /// The same `Rna`, with a capacity of 4 bytes.
+    type SmallRna<'a>: RnaTrait<'a> + 'a;
+
+    /// `left.ct_eq(right)`.
+    fn ct_eq(left: &Self::Rna<'_>, right: &Self::Rna<'_>) -> bool;
+
+    /// `left.ct_eq(right)`, from the smaller capacity.
+    fn small_ct_eq(left: &Self::SmallRna<'_>, right: &Self::Rna<'_>) -> bool;
+
+    /// `left.ct_eq(right)`, to the smaller capacity.
+    fn ct_eq_small(left: &Self::Rna<'_>, right: &Self::SmallRna<'_>) -> bool;
+
+    /// `left.ct_eq_probed(right, probe)`: what `ct_eq` does, calling `probe` once per operation
+    /// whose count must not depend on the nucleotides (every byte read, and the comparison of the
+    /// lengths).
+    fn ct_eq_probed(left: &Self::Rna<'_>, right: &Self::Rna<'_>, probe: &dyn Fn()) -> bool;
+
+    fn test_ct_eq() {
+        let rna = Self::Rna::new("CGAU").expect("RNA");
+        assert!(Self::ct_eq(&rna, &Self::Rna::new("CGAU").expect("RNA")));
+        for other in ["GGAU", "CGAA", "CGA", ""] {
+            let other_rna = Self::Rna::new(other).expect("RNA");
+            assert!(!Self::ct_eq(&rna, &other_rna), "{other}");
+        }
+    }
+
+    /// Different capacities compare equal, as long as the nucleotides are the same.
+    fn test_ct_eq_across_capacities() {
+        let small = Self::SmallRna::new("CGAU").expect("RNA");
+        let rna = Self::Rna::new("CGAU").expect("RNA");
+        assert!(Self::small_ct_eq(&small, &rna));
+        assert!(Self::ct_eq_small(&rna, &small));
+
+        let longer = Self::Rna::new("CGAUA").expect("RNA");
+        assert!(!Self::small_ct_eq(&small, &longer));
+        assert!(!Self::ct_eq_small(&longer, &small));
+    }
+
+    /// [`RnaTraitMut::set_from_iter`] zeroes leftover bytes, so a shortened instance still compares
+    /// equal over the whole storage.
+    fn test_ct_eq_after_mutation() {
+        let mut rna = Self::Rna::new("CGAUUAGC").expect("RNA");
+        rna.set_from_iter(&mut "UA".chars()).expect("success");
+        assert!(Self::ct_eq(&rna, &Self::Rna::new("UA").expect("RNA")));
+    }
+
+    /// `ct_eq` (and hence [`PartialEq`] with `constant_time_eq` feature) does as many operations
+    /// whether the nucleotides are equal or not, wherever the first mismatch is, and whether the
+    /// lengths differ. Its result agrees with [`PartialEq`] (with or without the feature).
+    fn test_ct_eq_operations_dont_depend_on_mismatch() {
+        let rna = Self::Rna::new("CGAUCGAU").expect("RNA");
+        let count_operations = |other: &'static str| {
+            let other_rna = Self::Rna::new(other).expect("RNA");
+            let operations = Cell::new(0usize);
+            let equal = Self::ct_eq_probed(&rna, &other_rna, &|| {
+                operations.set(operations.get() + 1);
+            });
+            assert_eq!(equal, rna == other_rna, "{other}");
+            assert_eq!(equal, Self::ct_eq(&rna, &other_rna), "{other}");
+            operations.get()
+        };
+
+        let operations_if_equal = count_operations("CGAUCGAU");
+        assert!(operations_if_equal > 0);
+        for other in [
+            "GGAUCGAU", "CGAACGAU", "CGAUCGAA", "CGAUCGA", "", "UUUUUUUU",
+        ] {
+            assert_eq!(count_operations(other), operations_if_equal, "{other}");
+        }
+    }
+}
+
+/// The tests of [`Tests`] that `api_tests!(ct_eq: T)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! ct_eq_list {
+    ([$($args:tt)*]) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_ct_eq()
+            test_ct_eq_across_capacities()
+            test_ct_eq_after_mutation()
+            test_ct_eq_operations_dont_depend_on_mismatch()
+        ] }
+    };
+}
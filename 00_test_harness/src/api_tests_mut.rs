@@ -1,9 +1,12 @@
 extern crate alloc;
 
+use alloc::vec::Vec;
 use utils::api_tests_mut::RnaTraitMutLeakStorage;
 use utils::{DnaTrait, OurResult, RnaTrait, RnaTraitMut};
-use alloc::vec::Vec;
 
+pub mod complement_in_place;
+pub mod wipe_on_clone;
+pub mod wipe_on_drop;
 pub mod wipe_on_mut;
 
 /// Type (signature) of a call back function that [`Tests`] trait passes to the user-provided
@@ -16,9 +19,15 @@ pub type WithStorageLeakedCallBack<'a, RES: 'a> = &'a dyn Fn(&mut dyn Iterator<I
 /// `test_` functions from [`Tests`] trait, so that `test_` functions here can detect leakage.
 ///
 /// This exposes `self`'s whole storage (including any unused data; mapped to bytes if needed).
+/// It takes `&mut`, so that a heap-based implementation can reach its unused capacity soundly
+/// (through [`Vec::spare_capacity_mut`](alloc::vec::Vec::spare_capacity_mut)).
 #[allow(type_alias_bounds)]
 pub type WithStorageLeaked<'a, RNA: RnaTraitMutLeakStorage<'a>, RES> =
-    &'a dyn Fn(&RNA, WithStorageLeakedCallBack<'a, RES>) -> RES;
+    &'a dyn Fn(&mut RNA, WithStorageLeakedCallBack<'a, RES>) -> RES;
+
+/// Like [`WithStorageLeaked`], for `Dna` that owns its storage. See [`wipe_on_drop::DnaTests`].
+pub type WithDnaStorageLeaked<'a, DNA, RES> =
+    &'a dyn Fn(&mut DNA, WithStorageLeakedCallBack<'a, RES>) -> RES;
 
 /// A helper.
 #[allow(dead_code)] //@TODO
@@ -28,19 +37,29 @@ fn cga_modified_to_u<'a, R: RnaTraitMut<'a>>() -> R {
     rna
 }
 
-/// A helper.
+/// A helper. Storage shorter than `CGA` (for example, of a clone) can't leak.
 #[allow(dead_code)] //@TODO
 fn leaks_g_or_a<'a, R: RnaTraitMutLeakStorage<'a>>(
-    rna: &R,
+    rna: &mut R,
     with_storage_leaked: WithStorageLeaked<'a, R, bool>,
 ) -> bool {
     #[allow(clippy::char_lit_as_u8)]
     with_storage_leaked(rna, &|bytes_iter| {
         let bytes = bytes_iter.collect::<Vec<_>>();
-        bytes[1] == 'G' as u8 || bytes[2] == 'A' as u8
+        bytes.get(1) == Some(&('G' as u8)) || bytes.get(2) == Some(&('A' as u8))
     })
 }
 
+/// A helper: whether `bytes_iter` yields zeros only.
+pub fn all_zero(bytes_iter: &mut dyn Iterator<Item = u8>) -> bool {
+    for byte in bytes_iter {
+        if byte != 0 {
+            return false;
+        }
+    }
+    true
+}
+
 pub trait Tests {
     type Dna<'a>: DnaTrait<'a, Self::Rna<'a>>;
     type Rna<'a>: RnaTraitMut<'a> + 'a;
@@ -57,8 +76,31 @@ pub trait Tests {
         Ok(())
     }
 
-    fn all_tests() -> OurResult<()> {
-        Self::test_modify_string_based_rna()?;
+    /// A failed [`RnaTraitMut::set_from_iter`] leaves the previous nucleotides intact.
+    fn test_failed_mutation_keeps_previous_value() -> OurResult<()> {
+        let mut rna = Self::Rna::new("CGAU")?;
+        let err = rna.set_from_iter(&mut "UAXG".chars()).unwrap_err();
+        assert_eq!(err.index(), 2);
+        assert_eq!(rna, Self::Rna::new("CGAU")?);
+
+        // Valid nucleotide, but invalid in context
+        assert!(rna.set_from_iter(&mut "GT".chars()).is_err());
+        assert_eq!(rna, Self::Rna::new("CGAU")?);
+
+        rna.set_from_iter(&mut "UAG".chars())?;
+        assert_eq!(rna, Self::Rna::new("UAG")?);
         Ok(())
     }
 }
+
+/// The tests of [`Tests`] that `api_tests!(mutation: T)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! mutation_list {
+    ([$($args:tt)*]) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_modify_string_based_rna()
+            test_failed_mutation_keeps_previous_value()
+        ] }
+    };
+}
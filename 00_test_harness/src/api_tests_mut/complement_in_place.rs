@@ -0,0 +1,63 @@
+//! Used by owned implementations, which complement their storage in place.
+
+use utils::{ComplementMutTrait, ComplementTrait, DnaTrait, OurResult, RnaToDnaTrait, RnaTrait};
+
+pub trait Tests {
+    type Dna<'a>: DnaTrait<'a, Self::Rna<'a>> + ComplementMutTrait;
+    type Rna<'a>: RnaToDnaTrait<'a, Self::Dna<'a>> + ComplementMutTrait + 'a;
+
+    fn test_complement_in_place() -> OurResult<()> {
+        let mut dna = Self::Dna::new("ACGTTA")?;
+        dna.complement_in_place();
+        assert_eq!(dna, Self::Dna::new("TGCAAT")?);
+
+        let mut rna = Self::Rna::new("ACGUUA")?;
+        rna.complement_in_place();
+        assert_eq!(rna, Self::Rna::new("UGCAAU")?);
+        Ok(())
+    }
+
+    fn test_reverse_complement_in_place() -> OurResult<()> {
+        let mut dna = Self::Dna::new("AACGTTTG")?;
+        dna.reverse_complement_in_place();
+        assert_eq!(dna, Self::Dna::new("CAAACGTT")?);
+
+        let mut rna = Self::Rna::new("AACGUUUG")?;
+        rna.reverse_complement_in_place();
+        assert_eq!(rna, Self::Rna::new("CAAACGUU")?);
+        Ok(())
+    }
+
+    /// In-place operations give the same result as their copying counterparts, also for
+    /// transcribed (materialized) sequences.
+    fn test_in_place_equals_copying() -> OurResult<()> {
+        let dna = Self::Dna::new("ACGTGGTCTTAG")?;
+        let mut rna = dna.into_rna();
+        rna.reverse_complement_in_place();
+        assert_eq!(rna, dna.into_rna().reverse_complement());
+
+        let mut dna_from_rna = rna.into_dna();
+        dna_from_rna.complement_in_place();
+        assert_eq!(dna_from_rna, rna.into_dna().complement());
+
+        dna_from_rna.reverse_complement_in_place();
+        assert_eq!(
+            dna_from_rna,
+            rna.into_dna().complement().reverse_complement()
+        );
+        Ok(())
+    }
+}
+
+/// The tests of [`Tests`] that `api_tests!(complement_in_place: T)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! complement_in_place_list {
+    ([$($args:tt)*]) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_complement_in_place()
+            test_reverse_complement_in_place()
+            test_in_place_equals_copying()
+        ] }
+    };
+}
@@ -0,0 +1,88 @@
+//! Used by implementations *_wipe_on_clone.
+
+use crate::api_tests_mut::{self, WithStorageLeaked};
+use utils::api_tests_mut::RnaTraitMutLeakStorage;
+use utils::{ComplementMutTrait, DnaTrait, RnaToDnaTrait, RnaTrait, RnaTraitMut};
+
+pub trait Tests {
+    type Rna<'a>: RnaTraitMutLeakStorage<'a> + Clone + 'a;
+
+    /// A clone doesn't carry any data from the unused part of the original's storage.
+    fn test_clone_does_not_leak<'a>(
+        with_storage_leaked: WithStorageLeaked<'a, Self::Rna<'a>, bool>,
+    ) {
+        let rna = api_tests_mut::cga_modified_to_u::<Self::Rna<'_>>();
+        let mut clone = rna.clone();
+        assert_eq!(clone, rna);
+
+        assert!(!api_tests_mut::leaks_g_or_a(
+            &mut clone,
+            with_storage_leaked
+        ));
+        // "U" is one byte. Anything after it must be zero.
+        let unused_zeroed = with_storage_leaked(&mut clone, &|bytes_iter| {
+            bytes_iter.skip(1).all(|byte| byte == 0)
+        });
+        assert!(unused_zeroed);
+    }
+
+    /// A clone has its own storage: modifying (or dropping, hence wiping) it leaves the original
+    /// intact, and the other way.
+    fn test_clone_is_independent() {
+        let mut rna = Self::Rna::new("CGAU").expect("RNA");
+        let mut clone = rna.clone();
+        clone.set_from_iter(&mut "UA".chars()).expect("success");
+        assert_eq!(rna, Self::Rna::new("CGAU").expect("RNA"));
+        drop(clone);
+        assert_eq!(rna, Self::Rna::new("CGAU").expect("RNA"));
+
+        let clone = rna.clone();
+        rna.set_from_iter(&mut "G".chars()).expect("success");
+        assert_eq!(clone, Self::Rna::new("CGAU").expect("RNA"));
+    }
+}
+
+/// The tests of [`Tests`] that `api_tests!(wipe_on_clone: T, with_storage_leaked)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! wipe_on_clone_list {
+    ([$($args:tt)*] $leaked:expr) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_clone_does_not_leak(&$leaked)
+            test_clone_is_independent()
+        ] }
+    };
+}
+
+/// Like [`Tests`], for implementations whose `Dna` owns nucleotides that it materializes in
+/// [`RnaToDnaTrait::into_dna`].
+pub trait DnaTests {
+    type Dna<'a>: DnaTrait<'a, Self::Rna<'a>> + ComplementMutTrait + Clone + 'a;
+    type Rna<'a>: RnaToDnaTrait<'a, Self::Dna<'a>> + 'a;
+
+    /// Like [`Tests::test_clone_is_independent`], for `Dna` materialized from `Rna`.
+    fn test_materialized_dna_clone_is_independent() {
+        let rna = Self::Rna::new("CGAU").expect("RNA");
+        let mut dna = rna.into_dna();
+        let mut clone = dna.clone();
+        clone.complement_in_place();
+        assert_eq!(dna, rna.into_dna());
+        drop(clone);
+        assert_eq!(dna, rna.into_dna());
+
+        let clone = dna.clone();
+        dna.reverse_complement_in_place();
+        assert_eq!(clone, rna.into_dna());
+    }
+}
+
+/// The tests of [`DnaTests`] that `api_tests!(wipe_on_clone_dna: T)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! wipe_on_clone_dna_list {
+    ([$($args:tt)*]) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_materialized_dna_clone_is_independent()
+        ] }
+    };
+}
@@ -0,0 +1,81 @@
+//! Used by implementations *_wipe_on_drop (with their opt-in `security` feature).
+
+use crate::api_tests_mut::{self, WithDnaStorageLeaked, WithStorageLeaked};
+use core::mem::ManuallyDrop;
+use utils::api_tests_mut::RnaTraitMutLeakStorage;
+use utils::{DnaTrait, RnaToDnaTrait, RnaTrait};
+
+pub trait Tests {
+    type Rna<'a>: RnaTraitMutLeakStorage<'a> + 'a;
+
+    /// Drop an instance in place, and then inspect the storage it occupied. For an inline storage
+    /// (an array) `with_storage_leaked` reads it directly. A heap-based storage is deallocated on
+    /// drop, and reading it afterwards would be undefined behavior. So for a dropped instance its
+    /// `with_storage_leaked` must inspect a copy of the deallocated block instead (for example, one
+    /// kept by a test global allocator).
+    fn test_drop_wipes_storage<'a>(
+        with_storage_leaked: WithStorageLeaked<'a, Self::Rna<'a>, bool>,
+    ) {
+        let mut rna = ManuallyDrop::new(Self::Rna::new("CGAU").expect("RNA"));
+        assert!(!with_storage_leaked(&mut rna, &api_tests_mut::all_zero));
+
+        // SAFETY: We don't use `rna` afterwards, other than passing it to `with_storage_leaked`,
+        // which doesn't read deallocated storage (see above).
+        unsafe { ManuallyDrop::drop(&mut rna) };
+
+        let wiped = with_storage_leaked(&mut rna, &api_tests_mut::all_zero);
+        assert!(wiped);
+    }
+}
+
+/// The tests of [`Tests`] that `api_tests!(wipe_on_drop: T, with_storage_leaked)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! wipe_on_drop_list {
+    ([$($args:tt)*] $leaked:expr) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_drop_wipes_storage(&$leaked)
+        ] }
+    };
+}
+
+/// Like [`Tests`], for implementations whose `Dna` owns nucleotides that it materializes in
+/// [`RnaToDnaTrait::into_dna`].
+pub trait DnaTests {
+    type Dna<'a>: DnaTrait<'a, Self::Rna<'a>> + 'a;
+    type Rna<'a>: RnaToDnaTrait<'a, Self::Dna<'a>> + 'a;
+
+    /// Like [`Tests::test_drop_wipes_storage`], for `Dna` materialized from `Rna`.
+    fn test_drop_wipes_materialized_dna<'a>(
+        with_storage_leaked: WithDnaStorageLeaked<'a, Self::Dna<'a>, bool>,
+    ) {
+        let rna = Self::Rna::new("CGAU").expect("RNA");
+        let mut dna = ManuallyDrop::new(rna.into_dna());
+        assert!(!with_storage_leaked(&mut dna, &api_tests_mut::all_zero));
+
+        // SAFETY: As in `Tests::test_drop_wipes_storage`.
+        unsafe { ManuallyDrop::drop(&mut dna) };
+
+        let wiped = with_storage_leaked(&mut dna, &api_tests_mut::all_zero);
+        assert!(wiped);
+    }
+
+    /// The opt-in security mode that wipes `Dna` on drop also hides its nucleotides from
+    /// [`Debug`](core::fmt::Debug).
+    fn test_materialized_dna_debug_redacted() {
+        let rna = Self::Rna::new("CGAU").expect("RNA");
+        assert_eq!(format!("{:?}", rna.into_dna()), "Dna(<redacted>)");
+    }
+}
+
+/// The tests of [`DnaTests`] that `api_tests!(wipe_on_drop_dna: T, with_storage_leaked)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! wipe_on_drop_dna_list {
+    ([$($args:tt)*] $leaked:expr) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_drop_wipes_materialized_dna(&$leaked)
+            test_materialized_dna_debug_redacted()
+        ] }
+    };
+}
@@ -2,21 +2,98 @@
 
 extern crate alloc;
 
-use utils::api_tests_mut::RnaTraitMutLeakStorage;
 use crate::api_tests_mut::{self, WithStorageLeaked};
-use utils::DnaTrait;
+use utils::api_tests_mut::RnaTraitMutLeakStorage;
+use utils::{DnaTrait, RnaTrait, RnaTraitMut};
 
+/// Like other `Tests` traits, for implementations whose `Dna` transcribes into their `Rna`. The
+/// tests themselves are in [`RnaTests`], which this implements.
 pub trait Tests {
     type Dna<'a>: DnaTrait<'a, Self::Rna<'a>>;
     type Rna<'a>: RnaTraitMutLeakStorage<'a> + 'a;
+}
+
+impl<T: Tests> RnaTests for T {
+    type Rna<'a> = T::Rna<'a>;
+}
+
+/// The tests. They need no `Dna`. So implementations that don't implement [`utils::DnaTrait`]
+/// implement this trait directly, rather than [`Tests`].
+pub trait RnaTests {
+    type Rna<'a>: RnaTraitMutLeakStorage<'a> + 'a;
 
     fn test_modify_string_based_rna_mutation_does_not_leak<'a>(
         with_storage_leaked: WithStorageLeaked<'a, Self::Rna<'a>, bool>,
     ) {
-        let rna = api_tests_mut::cga_modified_to_u::<Self::Rna<'_>>();
+        let mut rna = api_tests_mut::cga_modified_to_u::<Self::Rna<'_>>();
+
+        let leaks = api_tests_mut::leaks_g_or_a(&mut rna, with_storage_leaked);
+
+        assert!(!leaks);
+    }
+
+    /// Like [`RnaTests::test_modify_string_based_rna_mutation_does_not_leak`], but for every
+    /// nucleotide (not only `G` and `A`) at every position past the new end: the storage must be
+    /// zero there. A nucleotide that's encoded the same as a wiped slot (`C` in a 2-bit packed
+    /// storage) passes, since those bits don't tell it apart from no nucleotide.
+    fn test_shortening_does_not_leak_any_nucleotide<'a>(
+        with_storage_leaked: WithStorageLeaked<'a, Self::Rna<'a>, bool>,
+    ) {
+        for nucleotides in ["CCC", "GGG", "AAA", "UUU"] {
+            let mut rna = Self::Rna::new(nucleotides).expect("RNA");
+            rna.set_from_iter(&mut "U".chars()).expect("success");
+
+            let wiped = with_storage_leaked(&mut rna, &|bytes_iter| {
+                api_tests_mut::all_zero(&mut bytes_iter.skip(1))
+            });
 
-        let leaks = api_tests_mut::leaks_g_or_a(&rna, with_storage_leaked);
+            assert!(wiped, "{nucleotides} modified to U");
+        }
+    }
+}
+
+/// Like [`RnaTests`], for implementations whose [`utils::RnaTrait::new`] has no storage to modify.
+pub trait RnaInStorageTests: RnaTests {
+    /// An instance holding `CGA` in `storage`, which it can modify.
+    fn cga_in(storage: &mut [u8; 3]) -> Self::Rna<'_>;
+
+    /// Like [`RnaTests::test_modify_string_based_rna_mutation_does_not_leak`], but for `CGA` in
+    /// `storage` (see [`RnaInStorageTests::cga_in`]).
+    fn test_modify_rna_in_storage_mutation_does_not_leak<'a>(
+        storage: &'a mut [u8; 3],
+        with_storage_leaked: WithStorageLeaked<'a, Self::Rna<'a>, bool>,
+    ) {
+        let mut cga = Self::cga_in(storage);
+        assert_eq!(cga, Self::Rna::new("CGA").expect("RNA"));
+        cga.set_from_iter(&mut "U".chars()).expect("success");
+        assert_eq!(cga, Self::Rna::new("U").expect("RNA"));
+
+        let leaks = api_tests_mut::leaks_g_or_a(&mut cga, with_storage_leaked);
 
         assert!(!leaks);
     }
 }
+
+/// The tests of [`RnaTests`] that `api_tests!(wipe_on_mut: T, with_storage_leaked)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! wipe_on_mut_list {
+    ([$($args:tt)*] $leaked:expr) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_modify_string_based_rna_mutation_does_not_leak(&$leaked)
+            test_shortening_does_not_leak_any_nucleotide(&$leaked)
+        ] }
+    };
+}
+
+/// The tests of [`RnaInStorageTests`] that
+/// `api_tests!(wipe_on_mut_in_storage: T, with_storage_leaked)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! wipe_on_mut_in_storage_list {
+    ([$($args:tt)*] $leaked:expr) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_modify_rna_in_storage_mutation_does_not_leak(&mut [0; 3], &$leaked)
+        ] }
+    };
+}
@@ -1,10 +1,33 @@
-use utils::{DnaTrait, OurResult, RnaTrait};
+use core::fmt::Display;
+use utils::{
+    checks, ComplementTrait, DnaTrait, NucleotideError, OurResult, RnaToDnaTrait, RnaTrait,
+};
+
+pub mod with_storage;
 
 /// Most test_* functions are a verbatim copy from original Exercism's tests from
 /// https://github.com/exercism/rust/blob/main/exercises/practice/rna-transcription/tests/rna-transcription.rs
 pub trait Tests {
-    type Dna<'a>: DnaTrait<'a, Self::Rna<'a>>;
-    type Rna<'a>: RnaTrait<'a> + 'a;
+    type Dna<'a>: DnaTrait<'a, Self::Rna<'a>> + ComplementTrait;
+    type Rna<'a>: RnaToDnaTrait<'a, Self::Dna<'a>> + ComplementTrait + Display + 'a;
+
+    /// Whether [`Debug`](core::fmt::Debug) of `Rna` hides the nucleotides (as in an opt-in
+    /// security mode). Then it shows `Rna(<redacted>)`.
+    const REDACTED_DEBUG: bool = false;
+
+    /// Whether `Dna` and `Rna` store IUPAC ambiguity codes. If not, their `new_iupac` still reports
+    /// invalid IUPAC input against the IUPAC alphabet, but then it rejects ambiguity codes against
+    /// the strict alphabet.
+    const AMBIGUITY_CODES: bool = true;
+
+    /// Expected [`Debug`](core::fmt::Debug) output of `Rna` holding `nucleotides`.
+    fn rna_debug(nucleotides: &str) -> String {
+        if Self::REDACTED_DEBUG {
+            "Rna(<redacted>)".to_owned()
+        } else {
+            format!("Rna(\"{nucleotides}\")")
+        }
+    }
 
     // ------ Start end functions from Exercism
     fn test_valid_self_input() {
@@ -17,20 +40,23 @@ pub trait Tests {
 
     fn test_invalid_self_input() {
         // Invalid character
-        assert_eq!(Self::Dna::new("X").err(), Some(0));
+        assert_eq!(Self::Dna::new("X").err().map(|e| e.index()), Some(0));
         // Valid nucleotide, but invalid in context
-        assert_eq!(Self::Dna::new("U").err(), Some(0));
+        assert_eq!(Self::Dna::new("U").err().map(|e| e.index()), Some(0));
         // Longer string with contained errors
-        assert_eq!(Self::Dna::new("ACGTUXXCTTAA").err(), Some(4));
+        assert_eq!(
+            Self::Dna::new("ACGTUXXCTTAA").err().map(|e| e.index()),
+            Some(4)
+        );
     }
 
     fn test_invalid_rna_input() {
         // Invalid character
-        assert_eq!(Self::Rna::new("X").unwrap_err(), 0);
+        assert_eq!(Self::Rna::new("X").unwrap_err().index(), 0);
         // Valid nucleotide, but invalid in context
-        assert_eq!(Self::Rna::new("T").unwrap_err(), 0);
+        assert_eq!(Self::Rna::new("T").unwrap_err().index(), 0);
         // Longer string with contained errors
-        assert_eq!(Self::Rna::new("ACGUTTXCUUAA").unwrap_err(), 4);
+        assert_eq!(Self::Rna::new("ACGUTTXCUUAA").unwrap_err().index(), 4);
     }
 
     fn test_acid_equals_acid() {
@@ -90,12 +116,64 @@ pub trait Tests {
 
     // ------- Tests on top of Exercism's tests:
 
+    fn test_invalid_nucleotide_error_details() {
+        assert_eq!(
+            Self::Dna::new("GCU").unwrap_err(),
+            NucleotideError::InvalidNucleotide {
+                char_index: 2,
+                byte_offset: 2,
+                found: 'U',
+                expected_alphabet: "GCTA",
+            }
+        );
+        assert_eq!(
+            Self::Rna::new("CT").unwrap_err(),
+            NucleotideError::InvalidNucleotide {
+                char_index: 1,
+                byte_offset: 1,
+                found: 'T',
+                expected_alphabet: "CGAU",
+            }
+        );
+    }
+
+    /// Bytes (not proven UTF-8) locate errors at the same index as the equivalent `&str`.
+    fn test_new_from_bytes() -> OurResult<()> {
+        assert_eq!(Self::Dna::new_from_bytes(b"GCTA")?, Self::Dna::new("GCTA")?);
+        assert_eq!(Self::Rna::new_from_bytes(b"CGAU")?, Self::Rna::new("CGAU")?);
+        assert_eq!(
+            Self::Dna::new_from_bytes("GCéX".as_bytes()).unwrap_err(),
+            Self::Dna::new("GCéX").unwrap_err()
+        );
+        assert_eq!(
+            Self::Rna::new_from_bytes(b"CGT").unwrap_err(),
+            Self::Rna::new("CGT").unwrap_err()
+        );
+        assert_eq!(
+            Self::Dna::new_from_bytes(b"AC\xffG").unwrap_err().index(),
+            2
+        );
+        Ok(())
+    }
+
+    /// `Dna`/`Rna` created from bytes transcribe and complement like those created from `&str`.
+    fn test_from_bytes_transcribe_and_complement() -> OurResult<()> {
+        let dna = Self::Dna::new_from_bytes(b"GCTA")?;
+        assert_eq!(dna.into_rna(), Self::Rna::new("CGAU")?);
+        assert_eq!(dna.into_rna().into_dna(), dna);
+        assert_eq!(dna.reverse_complement(), Self::Dna::new("TAGC")?);
+        let rna = Self::Rna::new_from_bytes(b"CGAU")?;
+        assert_eq!(rna.into_dna(), Self::Dna::new("GCTA")?);
+        assert_eq!(rna.complement(), Self::Rna::new("GCUA")?);
+        Ok(())
+    }
+
     /// Honoring default derived format of a newtype-based implementation. Any other implementations
-    /// to conform.
+    /// to conform (unless [`Tests::REDACTED_DEBUG`]).
     fn test_rna_given_nucleotides_debug() -> OurResult<()> {
         let rna = Self::Rna::new("CGAU")?;
         let rna_dbg = format!("{:?}", rna);
-        assert_eq!("Rna(\"CGAU\")", rna_dbg);
+        assert_eq!(Self::rna_debug("CGAU"), rna_dbg);
         Ok(())
     }
 
@@ -104,22 +182,204 @@ pub trait Tests {
         let dna = Self::Dna::new("GCTA")?;
         let rna = dna.into_rna();
         let rna_dbg = format!("{:?}", rna);
-        assert_eq!("Rna(\"CGAU\")", rna_dbg);
+        assert_eq!(Self::rna_debug("CGAU"), rna_dbg);
         Ok(())
     }
 
-    fn all_tests() {
-        Self::test_valid_self_input();
-        Self::test_valid_rna_input();
-        Self::test_invalid_self_input();
-        Self::test_invalid_rna_input();
-        Self::test_acid_equals_acid();
-        Self::test_transcribes_cytosine_guanine();
-        Self::test_transcribes_guanine_cytosine();
-        Self::test_transcribes_adenine_uracil();
-        Self::test_transcribes_thymine_to_adenine();
-        Self::test_transcribes_all_self_to_rna();
-        assert!(Self::test_rna_given_nucleotides_debug().is_ok());
-        assert!(Self::test_rna_from_dna_debug().is_ok());
+    fn test_try_into_rna() -> OurResult<()> {
+        let dna = Self::Dna::new("ACGTGGTCTTAA")?;
+        assert_eq!(dna.try_into_rna()?, Self::Rna::new("UGCACCAGAAUU")?);
+        assert_eq!(dna.try_into_rna()?, dna.into_rna());
+        Ok(())
     }
+
+    fn test_reverse_transcribes_all_rna_to_dna() -> OurResult<()> {
+        assert_eq!(
+            Self::Rna::new("UGCACCAGAAUU")?.into_dna(),
+            Self::Dna::new("ACGTGGTCTTAA")?
+        );
+        Ok(())
+    }
+
+    /// DNA -> RNA -> DNA, and RNA -> DNA -> RNA, give back the original sequence.
+    fn test_round_trip() -> OurResult<()> {
+        let dna = Self::Dna::new("ACGTGGTCTTAA")?;
+        assert_eq!(dna.into_rna().into_dna(), dna);
+        assert_eq!(dna.into_rna().into_dna().into_rna(), dna.into_rna());
+
+        let rna = Self::Rna::new("UGCACCAGAAUU")?;
+        assert_eq!(rna.into_dna().into_rna(), rna);
+        Ok(())
+    }
+
+    fn test_complement() -> OurResult<()> {
+        assert_eq!(
+            Self::Dna::new("ACGTTA")?.complement(),
+            Self::Dna::new("TGCAAT")?
+        );
+        assert_eq!(
+            Self::Rna::new("ACGUUA")?.complement(),
+            Self::Rna::new("UGCAAU")?
+        );
+        // Complement of a transcribed sequence has the original nucleotides (in the other alphabet).
+        assert_eq!(
+            Self::Dna::new("ACGTTA")?.into_rna().complement(),
+            Self::Rna::new("ACGUUA")?
+        );
+        assert_eq!(
+            Self::Rna::new("ACGUUA")?.into_dna().complement(),
+            Self::Dna::new("ACGTTA")?
+        );
+        Ok(())
+    }
+
+    fn test_reverse_complement() -> OurResult<()> {
+        assert_eq!(
+            Self::Dna::new("AACGTTTG")?.reverse_complement(),
+            Self::Dna::new("CAAACGTT")?
+        );
+        assert_eq!(
+            Self::Rna::new("AACGUUUG")?.reverse_complement(),
+            Self::Rna::new("CAAACGUU")?
+        );
+        assert_eq!(
+            Self::Dna::new("AACGTTTG")?.into_rna().reverse_complement(),
+            Self::Rna::new("GUUUGCAA")?
+        );
+        assert_eq!(
+            Self::Dna::new("")?.reverse_complement(),
+            Self::Dna::new("")?
+        );
+        Ok(())
+    }
+
+    /// Complementing twice gives back the original. And complementing commutes with (reverse)
+    /// transcription.
+    fn test_complement_involution() -> OurResult<()> {
+        let dna = Self::Dna::new("ACGTGGTCTTAA")?;
+        assert_eq!(dna.complement().complement(), dna);
+        assert_eq!(dna.reverse_complement().reverse_complement(), dna);
+        assert_eq!(
+            dna.reverse_complement().complement().complement(),
+            dna.reverse_complement()
+        );
+        assert_eq!(dna.complement().into_rna(), dna.into_rna().complement());
+        assert_eq!(
+            dna.reverse_complement().into_rna(),
+            dna.into_rna().reverse_complement()
+        );
+
+        let rna = Self::Rna::new("UGCACCAGAAUU")?;
+        assert_eq!(rna.complement().complement(), rna);
+        assert_eq!(rna.reverse_complement().reverse_complement(), rna);
+        assert_eq!(
+            rna.reverse_complement().into_dna(),
+            rna.into_dna().reverse_complement()
+        );
+        Ok(())
+    }
+
+    fn test_reverse_complement_debug() -> OurResult<()> {
+        let rna = Self::Rna::new("AACG")?.reverse_complement();
+        assert_eq!(Self::rna_debug("CGUU"), format!("{:?}", rna));
+        let rna = Self::Dna::new("AACG")?.into_rna().reverse_complement();
+        assert_eq!(Self::rna_debug("GCAA"), format!("{:?}", rna));
+        Ok(())
+    }
+
+    /// [`Display`] shows bare nucleotides (as FASTA does).
+    fn test_rna_display() -> OurResult<()> {
+        assert_eq!("CGAU", format!("{}", Self::Rna::new("CGAU")?));
+        assert_eq!("CGAU", format!("{}", Self::Dna::new("GCTA")?.into_rna()));
+        let rna = Self::Rna::new("AACG")?.reverse_complement();
+        assert_eq!("CGUU", format!("{}", rna));
+        Ok(())
+    }
+
+    fn test_iupac_input() -> OurResult<()> {
+        if Self::AMBIGUITY_CODES {
+            assert!(Self::Dna::new_iupac("ACGTRYN").is_ok());
+            assert!(Self::Rna::new_iupac("ACGURYN").is_ok());
+        } else {
+            assert_eq!(
+                Self::Dna::new_iupac("ACGTRYN").unwrap_err(),
+                NucleotideError::InvalidNucleotide {
+                    char_index: 4,
+                    byte_offset: 4,
+                    found: 'R',
+                    expected_alphabet: checks::DNA_NUCLEOTIDES,
+                }
+            );
+            assert_eq!(Self::Rna::new_iupac("ACGURYN").unwrap_err().index(), 4);
+        }
+        // `new` stays strict.
+        assert_eq!(Self::Dna::new("ACGTRYN").unwrap_err().index(), 4);
+        assert_eq!(Self::Rna::new("ACGURYN").unwrap_err().index(), 4);
+        assert_eq!(
+            Self::Dna::new_iupac("ACGTX").unwrap_err(),
+            NucleotideError::InvalidNucleotide {
+                char_index: 4,
+                byte_offset: 4,
+                found: 'X',
+                expected_alphabet: "GCTARYSWKMBDHVN",
+            }
+        );
+        assert_eq!(Self::Rna::new_iupac("NACGT").unwrap_err().index(), 4);
+        assert_eq!(Self::Dna::new_iupac("ACGT")?, Self::Dna::new("ACGT")?);
+        Ok(())
+    }
+
+    /// Ambiguity codes get transcribed (and complemented) to their complementary codes. Without
+    /// [`Tests::AMBIGUITY_CODES`] only the strict nucleotides get through `new_iupac`.
+    fn test_iupac_transcription() -> OurResult<()> {
+        if !Self::AMBIGUITY_CODES {
+            assert_eq!(Self::Dna::new_iupac("ARYKMN").unwrap_err().index(), 1);
+            let dna = Self::Dna::new_iupac("ACGT")?;
+            assert_eq!(dna.into_rna(), Self::Rna::new_iupac("UGCA")?);
+            assert_eq!(dna.into_rna().into_dna(), dna);
+            assert_eq!(dna.reverse_complement(), Self::Dna::new_iupac("ACGT")?);
+            return Ok(());
+        }
+        let dna = Self::Dna::new_iupac("ARYKMN")?;
+        assert_eq!(dna.into_rna(), Self::Rna::new_iupac("UYRMKN")?);
+        assert_eq!(dna.into_rna().into_dna(), dna);
+        assert_eq!(dna.reverse_complement(), Self::Dna::new_iupac("NKMRYT")?);
+        assert_eq!(dna.into_rna().complement(), Self::Rna::new_iupac("ARYKMN")?);
+        Ok(())
+    }
+}
+
+/// The tests of [`Tests`] that `api_tests!(read_only: T)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! read_only_list {
+    ([$($args:tt)*]) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_valid_self_input()
+            test_valid_rna_input()
+            test_invalid_self_input()
+            test_invalid_rna_input()
+            test_acid_equals_acid()
+            test_transcribes_cytosine_guanine()
+            test_transcribes_guanine_cytosine()
+            test_transcribes_adenine_uracil()
+            test_transcribes_thymine_to_adenine()
+            test_transcribes_all_self_to_rna()
+            test_invalid_nucleotide_error_details()
+            test_new_from_bytes()
+            test_from_bytes_transcribe_and_complement()
+            test_rna_given_nucleotides_debug()
+            test_rna_from_dna_debug()
+            test_try_into_rna()
+            test_reverse_transcribes_all_rna_to_dna()
+            test_round_trip()
+            test_complement()
+            test_reverse_complement()
+            test_complement_involution()
+            test_reverse_complement_debug()
+            test_rna_display()
+            test_iupac_input()
+            test_iupac_transcription()
+        ] }
+    };
 }
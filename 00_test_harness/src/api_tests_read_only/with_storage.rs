@@ -0,0 +1,240 @@
+//! The same tests as [`super::Tests`], for implementations of [`DnaTraitWithStorage`]: their
+//! transcription needs a storage slice from the caller. Each test declares its storage before any
+//! `Dna` or `Rna`, because they all share one lifetime.
+//!
+//! No complement tests: these implementations don't implement [`utils::ComplementTrait`] (a
+//! complement would need another storage).
+
+use core::fmt::Display;
+use utils::{DnaTraitWithStorage, NucleotideError, OurResult, RnaToDnaTraitWithStorage, RnaTrait};
+
+pub trait Tests {
+    type Dna<'a>: DnaTraitWithStorage<'a, Self::Rna<'a>>;
+    type Rna<'a>: RnaToDnaTraitWithStorage<'a, Self::Dna<'a>> + Display + 'a;
+
+    // ------ Start end functions from Exercism
+    fn test_valid_self_input() {
+        assert!(Self::Dna::new("GCTA").is_ok());
+    }
+
+    fn test_valid_rna_input() {
+        assert!(Self::Rna::new("CGAU").is_ok());
+    }
+
+    fn test_invalid_self_input() {
+        // Invalid character
+        assert_eq!(Self::Dna::new("X").err().map(|e| e.index()), Some(0));
+        // Valid nucleotide, but invalid in context
+        assert_eq!(Self::Dna::new("U").err().map(|e| e.index()), Some(0));
+        // Longer string with contained errors
+        assert_eq!(
+            Self::Dna::new("ACGTUXXCTTAA").err().map(|e| e.index()),
+            Some(4)
+        );
+    }
+
+    fn test_invalid_rna_input() {
+        // Invalid character
+        assert_eq!(Self::Rna::new("X").unwrap_err().index(), 0);
+        // Valid nucleotide, but invalid in context
+        assert_eq!(Self::Rna::new("T").unwrap_err().index(), 0);
+        // Longer string with contained errors
+        assert_eq!(Self::Rna::new("ACGUTTXCUUAA").unwrap_err().index(), 4);
+    }
+
+    fn test_acid_equals_acid() {
+        assert_eq!(
+            Self::Dna::new("CGA").unwrap(),
+            Self::Dna::new("CGA").unwrap()
+        );
+        assert_ne!(
+            Self::Dna::new("CGA").unwrap(),
+            Self::Dna::new("AGC").unwrap()
+        );
+        assert_eq!(
+            Self::Rna::new("CGA").unwrap(),
+            Self::Rna::new("CGA").unwrap()
+        );
+        assert_ne!(
+            Self::Rna::new("CGA").unwrap(),
+            Self::Rna::new("AGC").unwrap()
+        );
+    }
+
+    fn test_transcribes_cytosine_guanine() {
+        let mut storage = [0u8; 1];
+        assert_eq!(
+            Self::Rna::new("G").unwrap(),
+            Self::Dna::new("C").unwrap().into_rna(&mut storage)
+        );
+    }
+
+    /// Storage longer than needed.
+    fn test_transcribes_guanine_cytosine() {
+        let mut storage = [0u8; 2];
+        assert_eq!(
+            Self::Rna::new("C").unwrap(),
+            Self::Dna::new("G").unwrap().into_rna(&mut storage)
+        );
+    }
+
+    fn test_transcribes_adenine_uracil() {
+        let mut storage = [0u8; 4];
+        assert_eq!(
+            Self::Rna::new("U").unwrap(),
+            Self::Dna::new("A").unwrap().into_rna(&mut storage)
+        );
+    }
+
+    fn test_transcribes_thymine_to_adenine() {
+        let mut storage = [0u8; 1];
+        assert_eq!(
+            Self::Rna::new("A").unwrap(),
+            Self::Dna::new("T").unwrap().into_rna(&mut storage)
+        );
+    }
+
+    fn test_transcribes_all_self_to_rna() {
+        let mut storage = [0u8; 12];
+        assert_eq!(
+            Self::Rna::new("UGCACCAGAAUU").unwrap(),
+            Self::Dna::new("ACGTGGTCTTAA")
+                .unwrap()
+                .into_rna(&mut storage)
+        )
+    }
+    // ------ End test functions from Exercism
+
+    // ------- Tests on top of Exercism's tests:
+
+    fn test_invalid_nucleotide_error_details() {
+        assert_eq!(
+            Self::Dna::new("GCU").unwrap_err(),
+            NucleotideError::InvalidNucleotide {
+                char_index: 2,
+                byte_offset: 2,
+                found: 'U',
+                expected_alphabet: "GCTA",
+            }
+        );
+        assert_eq!(
+            Self::Rna::new("CT").unwrap_err(),
+            NucleotideError::InvalidNucleotide {
+                char_index: 1,
+                byte_offset: 1,
+                found: 'T',
+                expected_alphabet: "CGAU",
+            }
+        );
+    }
+
+    /// Bytes (not proven UTF-8) locate errors at the same index as the equivalent `&str`.
+    fn test_new_from_bytes() -> OurResult<()> {
+        assert_eq!(Self::Dna::new_from_bytes(b"GCTA")?, Self::Dna::new("GCTA")?);
+        assert_eq!(Self::Rna::new_from_bytes(b"CGAU")?, Self::Rna::new("CGAU")?);
+        assert_eq!(
+            Self::Dna::new_from_bytes("GCéX".as_bytes()).unwrap_err(),
+            Self::Dna::new("GCéX").unwrap_err()
+        );
+        assert_eq!(
+            Self::Rna::new_from_bytes(b"CGT").unwrap_err(),
+            Self::Rna::new("CGT").unwrap_err()
+        );
+        assert_eq!(
+            Self::Dna::new_from_bytes(b"AC\xffG").unwrap_err().index(),
+            2
+        );
+        Ok(())
+    }
+
+    /// Given nucleotides, and transcribed ones, show the same.
+    fn test_rna_debug() -> OurResult<()> {
+        let mut storage = [0u8; 4];
+        let rna = Self::Rna::new("CGAU")?;
+        assert_eq!(format!("{:?}", rna), "Rna(\"CGAU\")");
+        let rna = Self::Dna::new("GCTA")?.into_rna(&mut storage);
+        assert_eq!(format!("{:?}", rna), "Rna(\"CGAU\")");
+        Ok(())
+    }
+
+    fn test_reverse_transcribes_all_rna_to_dna() -> OurResult<()> {
+        let mut storage = [0u8; 12];
+        assert_eq!(
+            Self::Rna::new("UGCACCAGAAUU")?.into_dna(&mut storage),
+            Self::Dna::new("ACGTGGTCTTAA")?
+        );
+        Ok(())
+    }
+
+    /// DNA -> RNA -> DNA, and RNA -> DNA -> RNA, give back the original sequence.
+    fn test_round_trip() -> OurResult<()> {
+        let (mut rna_storage, mut dna_storage) = ([0u8; 12], [0u8; 12]);
+        let dna = Self::Dna::new("ACGTGGTCTTAA")?;
+        let rna = dna.into_rna(&mut rna_storage);
+        assert_eq!(rna.into_dna(&mut dna_storage), dna);
+
+        let (mut dna_storage, mut rna_storage) = ([0u8; 12], [0u8; 12]);
+        let rna = Self::Rna::new("UGCACCAGAAUU")?;
+        let dna = rna.into_dna(&mut dna_storage);
+        assert_eq!(dna.into_rna(&mut rna_storage), rna);
+        Ok(())
+    }
+
+    /// [`Display`] shows bare nucleotides (as FASTA does).
+    fn test_rna_display() -> OurResult<()> {
+        let mut storage = [0u8; 4];
+        assert_eq!("CGAU", format!("{}", Self::Rna::new("CGAU")?));
+        let rna = Self::Dna::new("GCTA")?.into_rna(&mut storage);
+        assert_eq!("CGAU", format!("{}", rna));
+        Ok(())
+    }
+
+    fn test_iupac_input() -> OurResult<()> {
+        assert!(Self::Dna::new_iupac("ACGTRYN").is_ok());
+        assert!(Self::Rna::new_iupac("ACGURYN").is_ok());
+        // `new` stays strict.
+        assert_eq!(Self::Dna::new("ACGTRYN").unwrap_err().index(), 4);
+        assert_eq!(Self::Rna::new("ACGURYN").unwrap_err().index(), 4);
+        assert_eq!(Self::Rna::new_iupac("X").unwrap_err().index(), 0);
+        assert_eq!(Self::Dna::new_iupac("ACGT")?, Self::Dna::new("ACGT")?);
+        Ok(())
+    }
+
+    /// Ambiguity codes get transcribed to their complementary codes.
+    fn test_iupac_transcription() -> OurResult<()> {
+        let (mut rna_storage, mut dna_storage) = ([0u8; 6], [0u8; 6]);
+        let dna = Self::Dna::new_iupac("ARYKMN")?;
+        let rna = dna.into_rna(&mut rna_storage);
+        assert_eq!(rna, Self::Rna::new_iupac("UYRMKN")?);
+        assert_eq!(rna.into_dna(&mut dna_storage), dna);
+        Ok(())
+    }
+}
+
+/// The tests of [`Tests`] that `api_tests!(read_only_with_storage: T)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! read_only_with_storage_list {
+    ([$($args:tt)*]) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_valid_self_input()
+            test_valid_rna_input()
+            test_invalid_self_input()
+            test_invalid_rna_input()
+            test_acid_equals_acid()
+            test_transcribes_cytosine_guanine()
+            test_transcribes_guanine_cytosine()
+            test_transcribes_adenine_uracil()
+            test_transcribes_thymine_to_adenine()
+            test_transcribes_all_self_to_rna()
+            test_invalid_nucleotide_error_details()
+            test_new_from_bytes()
+            test_rna_debug()
+            test_reverse_transcribes_all_rna_to_dna()
+            test_round_trip()
+            test_rna_display()
+            test_iupac_input()
+            test_iupac_transcription()
+        ] }
+    };
+}
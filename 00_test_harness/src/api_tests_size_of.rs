@@ -0,0 +1,74 @@
+use core::mem::{align_of, size_of};
+use utils::LenType;
+
+/// [`size_of`] of owned, array-based `Rna` for common combinations of capacity and length type.
+/// A smaller length type shrinks `Rna`, unless the alignment of its nucleotides pads it back (as
+/// with `char`).
+pub trait Tests {
+    /// Type of one stored nucleotide: `u8` or `char`.
+    type Nucleotide;
+    type Rna<const M: usize, L: LenType>;
+
+    fn test_u8_len() {
+        assert_eq!(
+            size_of::<Self::Rna<40, u8>>(),
+            expected_size::<Self::Nucleotide, u8>(40)
+        );
+        assert_eq!(
+            size_of::<Self::Rna<255, u8>>(),
+            expected_size::<Self::Nucleotide, u8>(255)
+        );
+    }
+
+    fn test_u16_len() {
+        assert_eq!(
+            size_of::<Self::Rna<40, u16>>(),
+            expected_size::<Self::Nucleotide, u16>(40)
+        );
+        assert_eq!(
+            size_of::<Self::Rna<1000, u16>>(),
+            expected_size::<Self::Nucleotide, u16>(1000)
+        );
+    }
+
+    fn test_usize_len() {
+        assert_eq!(
+            size_of::<Self::Rna<40, usize>>(),
+            expected_size::<Self::Nucleotide, usize>(40)
+        );
+    }
+
+    /// With byte-sized nucleotides, 40 of them and a `u8` length take 41 bytes.
+    fn test_smaller_len_type_is_not_larger() {
+        let (with_u8, with_u16, with_usize) = (
+            size_of::<Self::Rna<40, u8>>(),
+            size_of::<Self::Rna<40, u16>>(),
+            size_of::<Self::Rna<40, usize>>(),
+        );
+        assert!(with_u8 <= with_u16 && with_u16 <= with_usize);
+        if size_of::<Self::Nucleotide>() == 1 {
+            assert_eq!(with_u8, 41);
+        }
+    }
+}
+
+/// The tests of [`Tests`] that `api_tests!(size_of: T)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! size_of_list {
+    ([$($args:tt)*]) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_u8_len()
+            test_u16_len()
+            test_usize_len()
+            test_smaller_len_type_is_not_larger()
+        ] }
+    };
+}
+
+/// Size of a struct of `capacity` nucleotides of type `N` and a length of type `L`, padded to the
+/// larger alignment of the two. (That's how rustc lays out such a struct.)
+fn expected_size<N, L>(capacity: usize) -> usize {
+    let align = align_of::<N>().max(align_of::<L>());
+    (capacity * size_of::<N>() + size_of::<L>()).next_multiple_of(align)
+}
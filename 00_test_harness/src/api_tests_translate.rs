@@ -0,0 +1,98 @@
+use utils::protein::{CodonTable, StopCodons, Translation};
+use utils::{ComplementTrait, DnaTrait, NucleotideError, OurResult, RnaTrait, TranslateTrait};
+
+/// Translation of RNA into proteins. All RNA here fits in 12 nucleotides (the default capacity of
+/// the const generic implementation).
+pub trait Tests {
+    type Dna<'a>: DnaTrait<'a, Self::Rna<'a>>;
+    type Rna<'a>: RnaTrait<'a> + ComplementTrait + TranslateTrait + 'a;
+
+    fn test_translate() -> OurResult<()> {
+        let rna = Self::Rna::new("AUGUUUUGG")?;
+        assert_eq!(rna.translate::<3>()?, "MFW");
+        assert_eq!(rna.translate::<5>()?, "MFW");
+        assert!(Self::Rna::new("")?.translate::<0>()?.is_empty());
+        Ok(())
+    }
+
+    /// Translating RNA based on DNA (transcribed on the fly).
+    fn test_translate_dna_based() -> OurResult<()> {
+        let rna = Self::Dna::new("TACAAAACC")?.into_rna();
+        assert_eq!(rna.translate::<3>()?, "MFW");
+        Ok(())
+    }
+
+    fn test_translate_reverse_complement() -> OurResult<()> {
+        let rna = Self::Rna::new("CCAAAACAU")?.reverse_complement();
+        assert_eq!(rna.translate::<3>()?, "MFW");
+        Ok(())
+    }
+
+    fn test_stop_codons() -> OurResult<()> {
+        let rna = Self::Rna::new("AUGUAAUUUUGA")?;
+        assert_eq!(rna.translate::<4>()?, "M");
+        let emit = Translation {
+            stop_codons: StopCodons::Emit,
+            ..Translation::default()
+        };
+        assert_eq!(rna.translate_into([0u8; 4], emit)?, "M*F*");
+        Ok(())
+    }
+
+    fn test_incomplete_codon_ignored() -> OurResult<()> {
+        assert_eq!(Self::Rna::new("AUGUU")?.translate::<2>()?, "M");
+        Ok(())
+    }
+
+    fn test_alternative_codon_table() -> OurResult<()> {
+        let rna = Self::Rna::new("AUGUGAAUA")?;
+        assert_eq!(rna.translate::<3>()?, "M");
+        let vertebrate_mitochondrial = Translation {
+            table: CodonTable::VertebrateMitochondrial,
+            ..Translation::default()
+        };
+        assert_eq!(
+            rna.translate_into([0u8; 3], vertebrate_mitochondrial)?,
+            "MWM"
+        );
+        Ok(())
+    }
+
+    fn test_caller_provided_buffer() -> OurResult<()> {
+        let mut buffer = [0u8; 4];
+        let protein =
+            Self::Rna::new("AUGUUU")?.translate_into(&mut buffer[..], Translation::default())?;
+        assert_eq!(protein, "MF");
+        assert_eq!(&buffer[..2], b"MF");
+        Ok(())
+    }
+
+    fn test_capacity_exceeded() -> OurResult<()> {
+        assert_eq!(
+            Self::Rna::new("AUGUUUUGG")?.translate::<2>(),
+            Err(NucleotideError::CapacityExceeded {
+                capacity: 2,
+                required: 3
+            })
+        );
+        Ok(())
+    }
+}
+
+/// The tests of [`Tests`] that `api_tests!(translate: T)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! translate_list {
+    ([$($args:tt)*]) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_translate()
+            test_translate_dna_based()
+            test_translate_reverse_complement()
+            test_stop_codons()
+            test_incomplete_codon_ignored()
+            test_alternative_codon_table()
+            test_caller_provided_buffer()
+            test_capacity_exceeded()
+        ] }
+    };
+}
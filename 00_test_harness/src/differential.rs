@@ -0,0 +1,337 @@
+//! Differential tests. Generate random DNA/RNA strings (valid and invalid), and check each
+//! implementation against a simple reference model here. Since all implementations have to agree
+//! with the model, they agree with each other, too.
+
+use crate::api_tests_read_only;
+use utils::{DnaTrait, NucleotideError, RnaToDnaTrait, RnaTrait};
+
+/// A pseudo-random number generator (SplitMix64). It's self-contained and `no_std`-friendly (it
+/// uses `core` only), so that the harness doesn't depend on any external crates. It's seeded, hence
+/// the tests are reproducible. Not for cryptography.
+#[derive(Clone, Debug)]
+pub struct Prng {
+    state: u64,
+}
+
+impl Prng {
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`. (Its modulo bias is negligible for the small bounds we use.) Panic if
+    /// `bound` is zero.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    pub fn choose<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+}
+
+pub mod with_storage;
+
+pub const DNA_ALPHABET: &str = "GCTA";
+pub const RNA_ALPHABET: &str = "CGAU";
+/// Invalid in both (strict) alphabets, or in one of them. Multi-byte characters make the character
+/// index and the byte offset of an error differ.
+const INVALID: &[char] = &['U', 'T', 'X', 'N', 'a', 'g', ' ', 'é', '🧬'];
+/// Length limit of generated strings. Longer than 40, the most common capacity.
+const MAX_GENERATED_LEN: usize = 48;
+/// Default of `SEED` of the `Tests` traits.
+#[allow(clippy::unusual_byte_groupings)]
+const DEFAULT_SEED: u64 = 0x5EED_0F_DA7A;
+
+/// A random string of up to `max_len` characters from `alphabet`. About half of them are invalid:
+/// they have one or two characters from [`INVALID`].
+pub fn random_nucleotides(prng: &mut Prng, alphabet: &str, max_len: usize) -> String {
+    let len = prng.below(max_len + 1);
+    random_nucleotides_of_len(prng, alphabet, len)
+}
+
+/// Like [`random_nucleotides`], but of exactly `len` characters (for implementations with an exact
+/// length).
+pub fn random_nucleotides_of_len(prng: &mut Prng, alphabet: &str, len: usize) -> String {
+    let alphabet = alphabet.chars().collect::<Vec<_>>();
+    let mut chars = (0..len).map(|_| prng.choose(&alphabet)).collect::<Vec<_>>();
+    if len > 0 && prng.below(2) == 0 {
+        for _ in 0..1 + prng.below(2) {
+            let index = prng.below(len);
+            chars[index] = prng.choose(INVALID);
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// A random variation of `nucleotides` (which are valid): the same, with one nucleotide changed,
+/// shortened, or longer by one nucleotide. So that random pairs are often equal or nearly equal.
+pub fn random_variation(prng: &mut Prng, nucleotides: &str, alphabet: &str) -> String {
+    let alphabet = alphabet.chars().collect::<Vec<_>>();
+    let mut chars = nucleotides.chars().collect::<Vec<_>>();
+    match prng.below(4) {
+        1 if !chars.is_empty() => {
+            let index = prng.below(chars.len());
+            chars[index] = prng.choose(&alphabet);
+        }
+        2 => chars.truncate(prng.below(chars.len() + 1)),
+        3 => chars.push(prng.choose(&alphabet)),
+        _ => {}
+    }
+    chars.into_iter().collect()
+}
+
+/// Reference validation: the first character not in `expected_alphabet`, if any.
+pub fn reference_check(
+    nucleotides: &str,
+    expected_alphabet: &'static str,
+) -> Result<(), NucleotideError> {
+    match nucleotides
+        .char_indices()
+        .enumerate()
+        .find(|(_, (_, c))| !expected_alphabet.contains(*c))
+    {
+        Some((char_index, (byte_offset, found))) => Err(NucleotideError::InvalidNucleotide {
+            char_index,
+            byte_offset,
+            found,
+            expected_alphabet,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Reference transcription of (valid) DNA nucleotides.
+pub fn reference_transcription(dna: &str) -> String {
+    dna.chars()
+        .map(|nucl| match nucl {
+            'G' => 'C',
+            'C' => 'G',
+            'T' => 'A',
+            'A' => 'U',
+            _ => panic!("Unexpected nucleotide {nucl}."),
+        })
+        .collect()
+}
+
+/// Run `check` on `num_cases` cases, generated from `seed`.
+fn run_cases(seed: u64, num_cases: usize, check: impl Fn(&mut Prng)) {
+    let mut prng = Prng::new(seed);
+    for _ in 0..num_cases {
+        check(&mut prng);
+    }
+}
+
+pub trait Tests: api_tests_read_only::Tests {
+    /// Capacity of `Rna` (in nucleotides). Longer (valid) DNA must fail
+    /// [`DnaTrait::try_into_rna`].
+    const MAX_LEN: usize = usize::MAX;
+    const NUM_CASES: usize = 2_000;
+    const SEED: u64 = DEFAULT_SEED;
+
+    /// Validation errors, [`Debug`](core::fmt::Debug) and [`core::fmt::Display`] of `Dna`, and of
+    /// its transcription.
+    fn test_dna_agrees_with_reference() {
+        run_cases(
+            Self::SEED,
+            Self::NUM_CASES,
+            Self::check_dna_agrees_with_reference,
+        );
+    }
+
+    /// Validation errors, [`Debug`](core::fmt::Debug) and [`core::fmt::Display`] of `Rna`.
+    fn test_rna_agrees_with_reference() {
+        run_cases(
+            Self::SEED,
+            Self::NUM_CASES,
+            Self::check_rna_agrees_with_reference,
+        );
+    }
+
+    /// Equality of `Dna`, of `Rna`, and of `Dna` transcribed to `Rna`, agrees with equality of the
+    /// nucleotides.
+    fn test_equality_agrees_with_reference() {
+        run_cases(
+            Self::SEED,
+            Self::NUM_CASES,
+            Self::check_equality_agrees_with_reference,
+        );
+    }
+
+    /// One case of [`Tests::test_dna_agrees_with_reference`].
+    fn check_dna_agrees_with_reference(prng: &mut Prng) {
+        let dna = random_nucleotides(prng, DNA_ALPHABET, MAX_GENERATED_LEN);
+        let expected = reference_check(&dna, DNA_ALPHABET);
+        let actual = Self::Dna::new(&dna);
+        assert_eq!(
+            actual.as_ref().err(),
+            expected.as_ref().err(),
+            "DNA {dna:?}"
+        );
+        let Ok(actual) = actual else {
+            return;
+        };
+        assert_eq!(format!("{actual:?}"), format!("Dna(\"{dna}\")"));
+
+        let rna = actual.try_into_rna();
+        if dna.len() > Self::MAX_LEN {
+            assert_eq!(
+                rna.err(),
+                Some(NucleotideError::CapacityExceeded {
+                    capacity: Self::MAX_LEN,
+                    required: dna.len()
+                }),
+                "DNA {dna:?}"
+            );
+        } else {
+            let rna = rna.expect("transcribed RNA");
+            let expected = reference_transcription(&dna);
+            assert_eq!(
+                format!("{rna:?}"),
+                Self::rna_debug(&expected),
+                "DNA {dna:?}"
+            );
+            assert_eq!(rna.to_string(), expected, "DNA {dna:?}");
+        }
+    }
+
+    /// One case of [`Tests::test_rna_agrees_with_reference`].
+    fn check_rna_agrees_with_reference(prng: &mut Prng) {
+        let rna = random_nucleotides(prng, RNA_ALPHABET, MAX_GENERATED_LEN.min(Self::MAX_LEN));
+        // Capacity is in UTF-8 bytes. Whether an invalid multi-byte character that doesn't fit
+        // fails as CapacityExceeded or as InvalidNucleotide differs between implementations.
+        if rna.len() > Self::MAX_LEN {
+            return;
+        }
+        let expected = reference_check(&rna, RNA_ALPHABET);
+        let actual = Self::Rna::new(&rna);
+        assert_eq!(
+            actual.as_ref().err(),
+            expected.as_ref().err(),
+            "RNA {rna:?}"
+        );
+        if let Ok(actual) = actual {
+            assert_eq!(format!("{actual:?}"), Self::rna_debug(&rna), "RNA {rna:?}");
+            assert_eq!(actual.to_string(), rna, "RNA {rna:?}");
+        }
+    }
+
+    /// One case of [`Tests::test_equality_agrees_with_reference`].
+    fn check_equality_agrees_with_reference(prng: &mut Prng) {
+        let max_len = MAX_GENERATED_LEN.min(Self::MAX_LEN);
+        let left = random_nucleotides(prng, DNA_ALPHABET, max_len);
+        if reference_check(&left, DNA_ALPHABET).is_err() {
+            return;
+        }
+        // The variation may be one nucleotide longer.
+        let left = &left[..left.len().min(max_len.saturating_sub(1))];
+        let right = random_variation(prng, left, DNA_ALPHABET);
+        // Before any `Dna`, because `Rna` (borrowing these) and transcribed `Dna` share lifetimes.
+        let (left_rna, right_rna) = (
+            reference_transcription(left),
+            reference_transcription(&right),
+        );
+
+        let (left_dna, right_dna) = (Self::Dna::new(left), Self::Dna::new(&right));
+        let (left_dna, right_dna) = (left_dna.expect("DNA"), right_dna.expect("DNA"));
+        assert_eq!(
+            left_dna == right_dna,
+            left == right,
+            "{left:?} vs {right:?}"
+        );
+        assert_eq!(
+            left_dna.into_rna() == right_dna.into_rna(),
+            left == right,
+            "{left:?} vs {right:?}"
+        );
+
+        let left_rna_given = Self::Rna::new(&left_rna).expect("RNA");
+        let right_rna_given = Self::Rna::new(&right_rna).expect("RNA");
+        assert_eq!(
+            left_rna_given == right_rna_given,
+            left == right,
+            "{left:?} vs {right:?}"
+        );
+        assert!(left_dna.into_rna() == left_rna_given, "{left:?}");
+        assert_eq!(
+            right_dna.into_rna() == left_rna_given,
+            left == right,
+            "{left:?} vs {right:?}"
+        );
+    }
+
+    /// DNA of [`Tests::MAX_LEN`] nucleotides transcribes, but one nucleotide more fails
+    /// [`DnaTrait::try_into_rna`]. Only for implementations whose `Rna` has a limited capacity, so
+    /// it's not a part of `api_tests!(differential: T)` - see `api_tests!(capacity: T)`.
+    fn test_try_into_rna_capacity_exceeded() {
+        let long_dna = "GATTACA"
+            .chars()
+            .cycle()
+            .take(Self::MAX_LEN + 1)
+            .collect::<String>();
+        let dna = Self::Dna::new(&long_dna).expect("DNA");
+        assert_eq!(
+            dna.try_into_rna().err(),
+            Some(NucleotideError::CapacityExceeded {
+                capacity: Self::MAX_LEN,
+                required: Self::MAX_LEN + 1
+            })
+        );
+
+        let fitting_dna = Self::Dna::new(&long_dna[..Self::MAX_LEN]).expect("DNA");
+        assert!(fitting_dna.try_into_rna().is_ok());
+    }
+
+    /// RNA of [`Tests::MAX_LEN`] nucleotides survives reverse transcription and back, but one
+    /// nucleotide more fails [`RnaTrait::new`]. Like
+    /// [`Tests::test_try_into_rna_capacity_exceeded`], for `api_tests!(capacity: T)` only.
+    fn test_new_rna_capacity_exceeded() {
+        let long_rna = "CGAU"
+            .chars()
+            .cycle()
+            .take(Self::MAX_LEN + 1)
+            .collect::<String>();
+        assert_eq!(
+            Self::Rna::new(&long_rna).err(),
+            Some(NucleotideError::CapacityExceeded {
+                capacity: Self::MAX_LEN,
+                required: Self::MAX_LEN + 1
+            })
+        );
+
+        let rna = Self::Rna::new(&long_rna[..Self::MAX_LEN]).expect("RNA");
+        assert_eq!(rna.into_dna().into_rna(), rna);
+    }
+}
+
+/// The tests of [`Tests`] that `api_tests!(differential: T)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! differential_list {
+    ([$($args:tt)*]) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_dna_agrees_with_reference()
+            test_rna_agrees_with_reference()
+            test_equality_agrees_with_reference()
+        ] }
+    };
+}
+
+/// The tests of [`Tests`] that `api_tests!(capacity: T)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! capacity_list {
+    ([$($args:tt)*]) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_try_into_rna_capacity_exceeded()
+            test_new_rna_capacity_exceeded()
+        ] }
+    };
+}
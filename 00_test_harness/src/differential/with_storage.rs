@@ -0,0 +1,153 @@
+//! The same differential tests as [`super::Tests`], for implementations of
+//! [`DnaTraitWithStorage`]. Each test declares its storage before any `Dna` or `Rna`, because they
+//! share one lifetime. The storage is as long as the nucleotides, so transcription never fails.
+
+use super::{
+    random_nucleotides, random_variation, reference_check, reference_transcription, run_cases,
+    Prng, DEFAULT_SEED, DNA_ALPHABET, MAX_GENERATED_LEN, RNA_ALPHABET,
+};
+use crate::api_tests_read_only::with_storage;
+use utils::{DnaTraitWithStorage, RnaToDnaTraitWithStorage, RnaTrait};
+
+pub trait Tests: with_storage::Tests {
+    const NUM_CASES: usize = 2_000;
+    const SEED: u64 = DEFAULT_SEED;
+
+    /// Validation errors and [`Debug`](core::fmt::Debug) of `Dna`, and
+    /// [`Debug`](core::fmt::Debug) and [`core::fmt::Display`] of its transcription.
+    fn test_dna_agrees_with_reference() {
+        run_cases(
+            Self::SEED,
+            Self::NUM_CASES,
+            Self::check_dna_agrees_with_reference,
+        );
+    }
+
+    /// Validation errors, [`Debug`](core::fmt::Debug) and [`core::fmt::Display`] of `Rna`.
+    fn test_rna_agrees_with_reference() {
+        run_cases(
+            Self::SEED,
+            Self::NUM_CASES,
+            Self::check_rna_agrees_with_reference,
+        );
+    }
+
+    /// Equality of `Dna`, of `Rna`, and of `Dna` transcribed to `Rna` (and back), agrees with
+    /// equality of the nucleotides.
+    fn test_equality_agrees_with_reference() {
+        run_cases(
+            Self::SEED,
+            Self::NUM_CASES,
+            Self::check_equality_agrees_with_reference,
+        );
+    }
+
+    /// One case of [`Tests::test_dna_agrees_with_reference`].
+    fn check_dna_agrees_with_reference(prng: &mut Prng) {
+        let dna = random_nucleotides(prng, DNA_ALPHABET, MAX_GENERATED_LEN);
+        let mut storage = vec![0u8; dna.len()];
+        let expected = reference_check(&dna, DNA_ALPHABET);
+        let actual = Self::Dna::new(&dna);
+        assert_eq!(
+            actual.as_ref().err(),
+            expected.as_ref().err(),
+            "DNA {dna:?}"
+        );
+        let Ok(actual) = actual else {
+            return;
+        };
+        assert_eq!(format!("{actual:?}"), format!("Dna(\"{dna}\")"));
+
+        let rna = actual.into_rna(&mut storage);
+        let expected = reference_transcription(&dna);
+        assert_eq!(
+            format!("{rna:?}"),
+            format!("Rna(\"{expected}\")"),
+            "DNA {dna:?}"
+        );
+        assert_eq!(rna.to_string(), expected, "DNA {dna:?}");
+    }
+
+    /// One case of [`Tests::test_rna_agrees_with_reference`].
+    fn check_rna_agrees_with_reference(prng: &mut Prng) {
+        let rna = random_nucleotides(prng, RNA_ALPHABET, MAX_GENERATED_LEN);
+        let expected = reference_check(&rna, RNA_ALPHABET);
+        let actual = Self::Rna::new(&rna);
+        assert_eq!(
+            actual.as_ref().err(),
+            expected.as_ref().err(),
+            "RNA {rna:?}"
+        );
+        if let Ok(actual) = actual {
+            assert_eq!(
+                format!("{actual:?}"),
+                format!("Rna(\"{rna}\")"),
+                "RNA {rna:?}"
+            );
+            assert_eq!(actual.to_string(), rna, "RNA {rna:?}");
+        }
+    }
+
+    /// One case of [`Tests::test_equality_agrees_with_reference`].
+    fn check_equality_agrees_with_reference(prng: &mut Prng) {
+        let left = random_nucleotides(prng, DNA_ALPHABET, MAX_GENERATED_LEN);
+        if reference_check(&left, DNA_ALPHABET).is_err() {
+            return;
+        }
+        let right = random_variation(prng, &left, DNA_ALPHABET);
+        let (left_rna, right_rna) = (
+            reference_transcription(&left),
+            reference_transcription(&right),
+        );
+        let mut left_storage = vec![0u8; left.len()];
+        let mut right_storage = vec![0u8; right.len()];
+        let mut dna_storage = vec![0u8; left_rna.len()];
+
+        let left_dna = Self::Dna::new(&left).expect("DNA");
+        let right_dna = Self::Dna::new(&right).expect("DNA");
+        assert_eq!(
+            left_dna == right_dna,
+            left == right,
+            "{left:?} vs {right:?}"
+        );
+
+        let left_rna_given = Self::Rna::new(&left_rna).expect("RNA");
+        let right_rna_given = Self::Rna::new(&right_rna).expect("RNA");
+        assert_eq!(
+            left_rna_given == right_rna_given,
+            left == right,
+            "{left:?} vs {right:?}"
+        );
+
+        let left_transcribed = left_dna.into_rna(&mut left_storage);
+        let right_transcribed = right_dna.into_rna(&mut right_storage);
+        assert_eq!(
+            left_transcribed == right_transcribed,
+            left == right,
+            "{left:?} vs {right:?}"
+        );
+        assert!(left_transcribed == left_rna_given, "{left:?}");
+        assert_eq!(
+            right_transcribed == left_rna_given,
+            left == right,
+            "{left:?} vs {right:?}"
+        );
+        assert!(
+            left_rna_given.into_dna(&mut dna_storage) == left_dna,
+            "{left:?}"
+        );
+    }
+}
+
+/// The tests of [`Tests`] that `api_tests!(differential_with_storage: T)` runs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! differential_with_storage_list {
+    ([$($args:tt)*]) => {
+        $crate::api_tests! { @list [$($args)*] [
+            test_dna_agrees_with_reference()
+            test_rna_agrees_with_reference()
+            test_equality_agrees_with_reference()
+        ] }
+    };
+}
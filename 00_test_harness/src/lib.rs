@@ -1,5 +1,9 @@
-#![feature(generic_associated_types)]
-#![feature(associated_type_defaults)]
-
+pub mod api_tests_cross_capacity;
+pub mod api_tests_ct_eq;
 pub mod api_tests_mut;
-pub mod api_tests_read_only;
\ No newline at end of file
+pub mod api_tests_read_only;
+pub mod api_tests_size_of;
+pub mod api_tests_translate;
+pub mod differential;
+#[doc(hidden)]
+pub mod macros;
@@ -0,0 +1,322 @@
+//! [`api_tests!`](crate::api_tests) generates one `#[test]` per `test_*` function of a `Tests`
+//! trait. Then a failing test doesn't hide the others, and the test output names each of them.
+
+use core::fmt::Debug;
+
+/// What a `test_*` function returns: either nothing, or [`utils::OurResult`]. Not a part of the
+/// public API - used by [`api_tests!`](crate::api_tests) only.
+#[doc(hidden)]
+pub trait TestOutcome {
+    fn check(self);
+}
+
+impl TestOutcome for () {
+    fn check(self) {}
+}
+
+impl<E: Debug> TestOutcome for Result<(), E> {
+    fn check(self) {
+        if let Err(err) = self {
+            panic!("test failed: {err:?}");
+        }
+    }
+}
+
+/// Generate a module with one `#[test]` per `test_*` function of the given suite, run on type `T`
+/// (which implements the suite's `Tests` trait):
+///
+/// - `api_tests!(read_only: T)` for [`crate::api_tests_read_only::Tests`], including the IUPAC
+///   tests,
+/// - `api_tests!(read_only_with_storage: T)` for
+///   [`crate::api_tests_read_only::with_storage::Tests`],
+/// - `api_tests!(mutation: T)` for [`crate::api_tests_mut::Tests`], including the transactional
+///   test,
+/// - `api_tests!(ct_eq: T)` for [`crate::api_tests_ct_eq::Tests`],
+/// - `api_tests!(complement_in_place: T)`, `api_tests!(translate: T)` and
+///   `api_tests!(size_of: T)`,
+/// - `api_tests!(differential: T)` for [`crate::differential::Tests`], and
+///   `api_tests!(differential_with_storage: T)` for [`crate::differential::with_storage::Tests`],
+/// - `api_tests!(capacity: T)` for [`crate::differential::Tests`] with a limited `MAX_LEN`, and
+///   `api_tests!(cross_capacity: T)` for [`crate::api_tests_cross_capacity::Tests`],
+/// - `api_tests!(wipe_on_mut: T, with_storage_leaked)` for
+///   [`crate::api_tests_mut::wipe_on_mut::RnaTests`] (implemented directly, or through
+///   [`crate::api_tests_mut::wipe_on_mut::Tests`]), and the same for `wipe_on_mut_in_storage`
+///   ([`crate::api_tests_mut::wipe_on_mut::RnaInStorageTests`]), `wipe_on_clone` and
+///   `wipe_on_drop`, where `with_storage_leaked` is a
+///   [`WithStorageLeaked`](crate::api_tests_mut::WithStorageLeaked) function.
+/// - `api_tests!(wipe_on_clone_dna: T)` and `api_tests!(wipe_on_drop_dna: T, with_storage_leaked)`
+///   for `DnaTests` of [`crate::api_tests_mut::wipe_on_clone`] and
+///   [`crate::api_tests_mut::wipe_on_drop`], where `with_storage_leaked` is a
+///   [`WithDnaStorageLeaked`](crate::api_tests_mut::WithDnaStorageLeaked) function.
+///
+/// Append `skip: [test_a, test_b]` to opt out of tests that don't apply to the implementation. A
+/// skipped test is generated with `#[ignore = "not applicable"]`, so the test output reports it as
+/// ignored. A misspelled name fails to compile.
+///
+/// The module is named after the suite, and it imports everything from its parent module (so `T`
+/// and `with_storage_leaked` can be private there).
+///
+/// Each suite's list of tests is a `*_list!` macro next to its trait, such as
+/// [`read_only_list!`](crate::read_only_list). A test in this module checks that the lists name
+/// every `test_*` function of their traits.
+#[macro_export]
+macro_rules! api_tests {
+    (read_only: $t:ty $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::read_only_list!(
+            [@suite read_only, $crate::api_tests_read_only::Tests, $t, [$($($skip)*)?]]
+        );
+    };
+    (read_only_with_storage: $t:ty $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::read_only_with_storage_list!(
+            [@suite read_only_with_storage, $crate::api_tests_read_only::with_storage::Tests, $t,
+                [$($($skip)*)?]]
+        );
+    };
+    (cross_capacity: $t:ty $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::cross_capacity_list!(
+            [@suite cross_capacity, $crate::api_tests_cross_capacity::Tests, $t, [$($($skip)*)?]]
+        );
+    };
+    (ct_eq: $t:ty $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::ct_eq_list!(
+            [@suite ct_eq, $crate::api_tests_ct_eq::Tests, $t, [$($($skip)*)?]]
+        );
+    };
+    (mutation: $t:ty $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::mutation_list!(
+            [@suite mutation, $crate::api_tests_mut::Tests, $t, [$($($skip)*)?]]
+        );
+    };
+    (complement_in_place: $t:ty $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::complement_in_place_list!(
+            [@suite complement_in_place, $crate::api_tests_mut::complement_in_place::Tests, $t,
+                [$($($skip)*)?]]
+        );
+    };
+    (translate: $t:ty $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::translate_list!(
+            [@suite translate, $crate::api_tests_translate::Tests, $t, [$($($skip)*)?]]
+        );
+    };
+    (differential: $t:ty $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::differential_list!(
+            [@suite differential, $crate::differential::Tests, $t, [$($($skip)*)?]]
+        );
+    };
+    (differential_with_storage: $t:ty $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::differential_with_storage_list!(
+            [@suite differential_with_storage, $crate::differential::with_storage::Tests, $t,
+                [$($($skip)*)?]]
+        );
+    };
+    (capacity: $t:ty $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::capacity_list!(
+            [@suite capacity, $crate::differential::Tests, $t, [$($($skip)*)?]]
+        );
+    };
+    (size_of: $t:ty $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::size_of_list!(
+            [@suite size_of, $crate::api_tests_size_of::Tests, $t, [$($($skip)*)?]]
+        );
+    };
+    (wipe_on_mut: $t:ty, $leaked:expr $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::wipe_on_mut_list!(
+            [@suite wipe_on_mut, $crate::api_tests_mut::wipe_on_mut::RnaTests, $t,
+                [$($($skip)*)?]] $leaked
+        );
+    };
+    (wipe_on_mut_in_storage: $t:ty, $leaked:expr $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::wipe_on_mut_in_storage_list!(
+            [@suite wipe_on_mut_in_storage,
+                $crate::api_tests_mut::wipe_on_mut::RnaInStorageTests, $t, [$($($skip)*)?]] $leaked
+        );
+    };
+    (wipe_on_clone: $t:ty, $leaked:expr $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::wipe_on_clone_list!(
+            [@suite wipe_on_clone, $crate::api_tests_mut::wipe_on_clone::Tests, $t,
+                [$($($skip)*)?]] $leaked
+        );
+    };
+    (wipe_on_drop: $t:ty, $leaked:expr $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::wipe_on_drop_list!(
+            [@suite wipe_on_drop, $crate::api_tests_mut::wipe_on_drop::Tests, $t,
+                [$($($skip)*)?]] $leaked
+        );
+    };
+    (wipe_on_clone_dna: $t:ty $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::wipe_on_clone_dna_list!(
+            [@suite wipe_on_clone_dna, $crate::api_tests_mut::wipe_on_clone::DnaTests, $t,
+                [$($($skip)*)?]]
+        );
+    };
+    (wipe_on_drop_dna: $t:ty, $leaked:expr $(, skip: [$($skip:ident),* $(,)?])? $(,)?) => {
+        $crate::wipe_on_drop_dna_list!(
+            [@suite wipe_on_drop_dna, $crate::api_tests_mut::wipe_on_drop::DnaTests, $t,
+                [$($($skip)*)?]] $leaked
+        );
+    };
+    // Called back by the `*_list!` macros with the list of tests of a suite.
+    (@list [@suite $($args:tt)*] [$($list:tt)*]) => {
+        $crate::api_tests!(@suite $($args)*, [$($list)*]);
+    };
+    (@list [@names] [$($name:ident ($($arg:tt)*))*]) => {
+        [$(stringify!($name)),*]
+    };
+    (@suite $module:ident, $tests:path, $t:ty, [$($skip:ident)*],
+        [$($name:ident ($($arg:tt)*))*]) => {
+        // Pass `$` on, so that the suite can define a macro with its own metavariables.
+        $crate::api_tests!(@suite_with_dollar ($) $module, $tests, $t, [$($skip)*],
+            [$($name ($($arg)*))*]);
+    };
+    (@suite_with_dollar ($d:tt) $module:ident, $tests:path, $t:ty, [$($skip:ident)*],
+        [$($name:ident ($($arg:tt)*))*]) => {
+        mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            // Fail to compile if a skipped test doesn't exist.
+            const _: () = {
+                $(let _ = <$t as $tests>::$skip;)*
+            };
+
+            // A `macro_rules!` can compare identifiers only against literal ones. So this has one
+            // rule per skipped name, which adds `#[ignore]`.
+            macro_rules! test_or_skip {
+                $(($skip $d($d test:tt)*) => {
+                    #[test]
+                    #[ignore = "not applicable"]
+                    $d($d test)*
+                };)*
+                ($d other:ident $d($d test:tt)*) => {
+                    #[test]
+                    $d($d test)*
+                };
+            }
+
+            $(
+                test_or_skip! {
+                    $name
+                    fn $name() {
+                        $crate::macros::TestOutcome::check(<$t as $tests>::$name($($arg)*));
+                    }
+                }
+            )*
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    /// The names of the `test_*` functions of `pub trait $name` in `source`, sorted.
+    fn test_fns<'a>(source: &'a str, name: &str) -> Vec<&'a str> {
+        let start = source.find(&format!("pub trait {name}")).expect("trait");
+        let body = &source[start..];
+        let body = &body[..body.find("\n}\n").expect("end of trait")];
+        let mut names: Vec<_> = body
+            .lines()
+            .filter_map(|line| line.trim_start().strip_prefix("fn "))
+            .filter_map(|signature| signature.split(['(', '<']).next())
+            .filter(|name| name.starts_with("test_"))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    fn sorted(lists: &[&[&'static str]]) -> Vec<&'static str> {
+        let mut names = lists.concat();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn test_suites_list_all_tests() {
+        let suites = [
+            (
+                include_str!("api_tests_read_only.rs"),
+                "Tests",
+                sorted(&[&crate::read_only_list!([@names])]),
+            ),
+            (
+                include_str!("api_tests_read_only/with_storage.rs"),
+                "Tests",
+                sorted(&[&crate::read_only_with_storage_list!([@names])]),
+            ),
+            (
+                include_str!("api_tests_cross_capacity.rs"),
+                "Tests",
+                sorted(&[&crate::cross_capacity_list!([@names])]),
+            ),
+            (
+                include_str!("api_tests_ct_eq.rs"),
+                "Tests",
+                sorted(&[&crate::ct_eq_list!([@names])]),
+            ),
+            (
+                include_str!("api_tests_mut.rs"),
+                "Tests",
+                sorted(&[&crate::mutation_list!([@names])]),
+            ),
+            (
+                include_str!("api_tests_mut/complement_in_place.rs"),
+                "Tests",
+                sorted(&[&crate::complement_in_place_list!([@names])]),
+            ),
+            (
+                include_str!("api_tests_translate.rs"),
+                "Tests",
+                sorted(&[&crate::translate_list!([@names])]),
+            ),
+            (
+                include_str!("differential.rs"),
+                "Tests",
+                sorted(&[
+                    &crate::differential_list!([@names]),
+                    &crate::capacity_list!([@names]),
+                ]),
+            ),
+            (
+                include_str!("differential/with_storage.rs"),
+                "Tests",
+                sorted(&[&crate::differential_with_storage_list!([@names])]),
+            ),
+            (
+                include_str!("api_tests_size_of.rs"),
+                "Tests",
+                sorted(&[&crate::size_of_list!([@names])]),
+            ),
+            (
+                include_str!("api_tests_mut/wipe_on_mut.rs"),
+                "RnaTests",
+                sorted(&[&crate::wipe_on_mut_list!([@names] ())]),
+            ),
+            (
+                include_str!("api_tests_mut/wipe_on_mut.rs"),
+                "RnaInStorageTests",
+                sorted(&[&crate::wipe_on_mut_in_storage_list!([@names] ())]),
+            ),
+            (
+                include_str!("api_tests_mut/wipe_on_clone.rs"),
+                "Tests",
+                sorted(&[&crate::wipe_on_clone_list!([@names] ())]),
+            ),
+            (
+                include_str!("api_tests_mut/wipe_on_clone.rs"),
+                "DnaTests",
+                sorted(&[&crate::wipe_on_clone_dna_list!([@names])]),
+            ),
+            (
+                include_str!("api_tests_mut/wipe_on_drop.rs"),
+                "Tests",
+                sorted(&[&crate::wipe_on_drop_list!([@names] ())]),
+            ),
+            (
+                include_str!("api_tests_mut/wipe_on_drop.rs"),
+                "DnaTests",
+                sorted(&[&crate::wipe_on_drop_dna_list!([@names] ())]),
+            ),
+        ];
+        for (source, name, listed) in suites {
+            assert_eq!(test_fns(source, name), listed, "{name}");
+        }
+    }
+}
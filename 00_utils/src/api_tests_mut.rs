@@ -15,9 +15,11 @@ pub type WithStorageLeakedCallBack<'a, RES: 'a> = &'a dyn Fn(&mut dyn Iterator<I
 /// `test_` functions from [`Tests`] trait, so that `test_` functions here can detect leakage.
 ///
 /// This exposes `self`'s whole storage (including any unused data; mapped to bytes if needed).
+/// It takes `&mut`, so that a heap-based implementation can reach its unused capacity soundly
+/// (through [`Vec::spare_capacity_mut`](alloc::vec::Vec::spare_capacity_mut)).
 #[allow(type_alias_bounds)]
 pub type WithStorageLeaked<'a, RNA: RnaTraitMutLeakStorage<'a>, RES> =
-    &'a dyn Fn(&RNA, WithStorageLeakedCallBack<'a, RES>) -> RES;
+    &'a dyn Fn(&mut RNA, WithStorageLeakedCallBack<'a, RES>) -> RES;
 
 // @TODO duplicate of 00_test_harness/src/api_tests_mut.rs
 /// A helper.
@@ -34,7 +36,7 @@ fn cga_modified_to_u<'a, R: RnaTraitMut<'a>>() -> R {
 /// A helper.
 #[cfg(test)]
 fn leaks_g_or_a<'a, R: RnaTraitMutLeakStorage<'a>>(
-    rna: &R,
+    rna: &mut R,
     with_storage_leaked: WithStorageLeaked<'a, R, bool>,
 ) -> bool {
     #[allow(clippy::char_lit_as_u8)]
@@ -1,5 +1,45 @@
-const DNA_NUCLEOTIDES: &str = "GCTA";
-const RNA_NUCLEOTIDES: &str = "CGAU";
+use crate::{NucleotideError, OurResult};
+use core::str;
+
+/// All valid DNA nucleotides. Reported as `expected_alphabet` of
+/// [`NucleotideError::InvalidNucleotide`] by [`check_dna`].
+pub const DNA_NUCLEOTIDES: &str = "GCTA";
+/// All valid RNA nucleotides. Reported as `expected_alphabet` of
+/// [`NucleotideError::InvalidNucleotide`] by [`check_rna_str`] and related functions.
+pub const RNA_NUCLEOTIDES: &str = "CGAU";
+/// [`DNA_NUCLEOTIDES`], followed by IUPAC ambiguity codes. See [`Alphabet::Iupac`].
+pub const DNA_IUPAC_NUCLEOTIDES: &str = "GCTARYSWKMBDHVN";
+/// [`RNA_NUCLEOTIDES`], followed by IUPAC ambiguity codes. See [`Alphabet::Iupac`].
+pub const RNA_IUPAC_NUCLEOTIDES: &str = "CGAURYSWKMBDHVN";
+
+/// Which characters are valid nucleotides.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Alphabet {
+    /// Only [`DNA_NUCLEOTIDES`] or [`RNA_NUCLEOTIDES`]. Used by `new` constructors.
+    #[default]
+    Strict,
+    /// Also IUPAC ambiguity codes (`R` for a purine, `Y` for a pyrimidine, ... `N` for any
+    /// nucleotide): [`DNA_IUPAC_NUCLEOTIDES`] or [`RNA_IUPAC_NUCLEOTIDES`]. Used by `new_iupac`
+    /// constructors. [`crate::dna_to_rna`] and other mapping functions map ambiguity codes to their
+    /// complements (for example `R` to `Y`).
+    Iupac,
+}
+
+impl Alphabet {
+    pub fn dna_nucleotides(self) -> &'static str {
+        match self {
+            Alphabet::Strict => DNA_NUCLEOTIDES,
+            Alphabet::Iupac => DNA_IUPAC_NUCLEOTIDES,
+        }
+    }
+
+    pub fn rna_nucleotides(self) -> &'static str {
+        match self {
+            Alphabet::Strict => RNA_NUCLEOTIDES,
+            Alphabet::Iupac => RNA_IUPAC_NUCLEOTIDES,
+        }
+    }
+}
 
 /// Result of [`check`] and related functions.
 ///
@@ -9,41 +49,144 @@ type CheckResult = crate::OurResult<()>;
 
 /// Check that any characters from `chars_to_be_checked` are in `allowed_chars`. See [`check_dna`]
 /// or [`check_rna_str`] for result type description.
-fn check(to_be_checked: impl Iterator<Item = char>, allowed: &str) -> CheckResult {
-    for (i, c) in to_be_checked.enumerate() {
+fn check(to_be_checked: impl Iterator<Item = char>, allowed: &'static str) -> CheckResult {
+    let mut byte_offset = 0usize;
+    for (char_index, c) in to_be_checked.enumerate() {
         if !allowed.contains(c) {
-            return Err(i);
+            return Err(NucleotideError::InvalidNucleotide {
+                char_index,
+                byte_offset,
+                found: c,
+                expected_alphabet: allowed,
+            });
         }
+        byte_offset += c.len_utf8();
     }
     Ok(())
 }
 
 /// Check that `dna` contains DNA nucleotides only. On success return [`Ok(())`](Ok). On error
-/// return [`Err`] with a 0-based index of the first incorrect character.
+/// return [`Err`] with [`NucleotideError::InvalidNucleotide`] locating the first incorrect
+/// character.
 pub fn check_dna(dna: &str) -> CheckResult {
-    check(dna.chars(), DNA_NUCLEOTIDES)
+    check_dna_with(dna, Alphabet::Strict)
+}
+
+/// Like [`check_dna`], but accept nucleotides of the given `alphabet`.
+pub fn check_dna_with(dna: &str, alphabet: Alphabet) -> CheckResult {
+    check(dna.chars(), alphabet.dna_nucleotides())
 }
 
 /// Check that `rna_iter` yields RNA nucleotides only. On success return [`Ok(())`](Ok). On error
-/// return [`Err`] with a 0-based index of the first incorrect character.
+/// return [`Err`] with [`NucleotideError::InvalidNucleotide`] - see [`check_dna`].
 pub fn check_rna_char_iter(rna_iter: impl Iterator<Item = char>) -> CheckResult {
-    check(rna_iter, RNA_NUCLEOTIDES)
+    check_rna_char_iter_with(rna_iter, Alphabet::Strict)
+}
+
+/// Like [`check_rna_char_iter`], but accept nucleotides of the given `alphabet`.
+pub fn check_rna_char_iter_with(
+    rna_iter: impl Iterator<Item = char>,
+    alphabet: Alphabet,
+) -> CheckResult {
+    check(rna_iter, alphabet.rna_nucleotides())
 }
 
 /// Check that `rna` contains RNA nucleotides only. On success return [`Ok(())`](Ok). On error
-/// return [`Err`] with a 0-based index of the first incorrect character.
+/// return [`Err`] with [`NucleotideError::InvalidNucleotide`] - see [`check_dna`].
 pub fn check_rna_str(rna: &str) -> CheckResult {
-    check_rna_char_iter(rna.chars())
+    check_rna_str_with(rna, Alphabet::Strict)
+}
+
+/// Like [`check_rna_str`], but accept nucleotides of the given `alphabet`.
+pub fn check_rna_str_with(rna: &str, alphabet: Alphabet) -> CheckResult {
+    check_rna_char_iter_with(rna.chars(), alphabet)
 }
 
 /// Check that `rna` contains RNA nucleotides only. On success return [`Ok(())`](Ok). On error
-/// return [`Err`] with a 0-based index of the first incorrect character.
+/// return [`Err`] with [`NucleotideError::InvalidNucleotide`] - see [`check_dna`].
 pub fn check_rna_chars(rna: &[char]) -> CheckResult {
-    check_rna_char_iter(rna.iter().cloned())
+    check_rna_chars_with(rna, Alphabet::Strict)
+}
+
+/// Like [`check_rna_chars`], but accept nucleotides of the given `alphabet`.
+pub fn check_rna_chars_with(rna: &[char], alphabet: Alphabet) -> CheckResult {
+    check_rna_char_iter_with(rna.iter().cloned(), alphabet)
+}
+
+/// Check that `to_be_checked` bytes are in `allowed` (which is ASCII). All bytes before the first
+/// incorrect one are ASCII, so its index is both its `char_index` and its `byte_offset`. Its `found`
+/// is the character starting there, or [`char::REPLACEMENT_CHARACTER`] if that's not valid UTF-8.
+fn check_bytes(to_be_checked: &[u8], allowed: &'static str) -> CheckResult {
+    match to_be_checked
+        .iter()
+        .position(|b| !allowed.as_bytes().contains(b))
+    {
+        None => Ok(()),
+        Some(index) => Err(NucleotideError::InvalidNucleotide {
+            char_index: index,
+            byte_offset: index,
+            found: to_be_checked[index..]
+                .utf8_chunks()
+                .next()
+                .and_then(|chunk| chunk.valid().chars().next())
+                .unwrap_or(char::REPLACEMENT_CHARACTER),
+            expected_alphabet: allowed,
+        }),
+    }
+}
+
+/// Like [`check_dna`], but for bytes (for example raw sensor data), without requiring them to be
+/// UTF-8 first. On success they are ASCII - see also [`dna_bytes_as_str`].
+pub fn check_dna_bytes(dna: &[u8]) -> CheckResult {
+    check_dna_bytes_with(dna, Alphabet::Strict)
+}
+
+/// Like [`check_dna_bytes`], but accept nucleotides of the given `alphabet`.
+pub fn check_dna_bytes_with(dna: &[u8], alphabet: Alphabet) -> CheckResult {
+    check_bytes(dna, alphabet.dna_nucleotides())
+}
+
+/// Like [`check_rna_str`], but for bytes. See [`check_dna_bytes`].
+pub fn check_rna_bytes(rna: &[u8]) -> CheckResult {
+    check_rna_bytes_with(rna, Alphabet::Strict)
+}
+
+/// Like [`check_rna_bytes`], but accept nucleotides of the given `alphabet`.
+pub fn check_rna_bytes_with(rna: &[u8], alphabet: Alphabet) -> CheckResult {
+    check_bytes(rna, alphabet.rna_nucleotides())
+}
+
+/// `dna` as `&str` referring to the same bytes, if they are ASCII. It doesn't check the nucleotides
+/// themselves (that's up to `new` of the `&str`), so it costs one cheap pass only. Otherwise return
+/// the same error as [`check_dna_bytes`].
+pub fn dna_bytes_as_str(dna: &[u8]) -> OurResult<&str> {
+    ascii_as_str(dna, DNA_NUCLEOTIDES)
+}
+
+/// Like [`dna_bytes_as_str`], for RNA. See [`check_rna_bytes`].
+pub fn rna_bytes_as_str(rna: &[u8]) -> OurResult<&str> {
+    ascii_as_str(rna, RNA_NUCLEOTIDES)
+}
+
+fn ascii_as_str<'a>(nucleotides: &'a [u8], allowed: &'static str) -> OurResult<&'a str> {
+    if nucleotides.is_ascii() {
+        // SAFETY: ASCII is valid UTF-8.
+        Ok(unsafe { str::from_utf8_unchecked(nucleotides) })
+    } else {
+        // `allowed` is ASCII, so a non-ASCII byte fails the check (if nothing before it does).
+        Err(check_bytes(nucleotides, allowed).expect_err("a non-ASCII nucleotide"))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::NucleotideError;
+
+    /// A helper: the index of the first incorrect character, if any.
+    fn index(result: super::CheckResult) -> Option<usize> {
+        result.err().map(|e| e.index())
+    }
+
     #[test]
     fn test_check_dna_rna_valid() {
         assert!(super::check_dna("GCTA").is_ok());
@@ -52,33 +195,130 @@ mod tests {
 
     #[test]
     fn test_check_dna_rna_invalid() {
-        assert_eq!(super::check_dna("CU"), Err(1));
-        assert_eq!(super::check_rna_str("CT"), Err(1));
+        assert_eq!(index(super::check_dna("CU")), Some(1));
+        assert_eq!(index(super::check_rna_str("CT")), Some(1));
     }
 
     #[test]
     fn test_check_dna() {
         assert!(super::check_dna("GCTA").is_ok());
-        assert_eq!(super::check_dna("U"), Err(0));
-        assert_eq!(super::check_dna("GX"), Err(1));
+        assert_eq!(index(super::check_dna("U")), Some(0));
+        assert_eq!(index(super::check_dna("GX")), Some(1));
+    }
+
+    #[test]
+    fn test_check_dna_error_details() {
+        assert_eq!(
+            super::check_dna("GCéX"),
+            Err(NucleotideError::InvalidNucleotide {
+                char_index: 2,
+                byte_offset: 2,
+                found: 'é',
+                expected_alphabet: "GCTA",
+            })
+        );
+        assert_eq!(
+            super::check_rna_str("éCX"),
+            Err(NucleotideError::InvalidNucleotide {
+                char_index: 0,
+                byte_offset: 0,
+                found: 'é',
+                expected_alphabet: "CGAU",
+            })
+        );
     }
 
     #[test]
     fn test_check_rna_iter() {
         assert!(super::check_rna_char_iter("CGAU".chars()).is_ok());
-        assert_eq!(super::check_rna_char_iter("T".chars()), Err(0));
-        assert_eq!(super::check_rna_char_iter("GX".chars()), Err(1));
+        assert_eq!(index(super::check_rna_char_iter("T".chars())), Some(0));
+        assert_eq!(index(super::check_rna_char_iter("GX".chars())), Some(1));
     }
     #[test]
     fn test_check_rna_str() {
         assert!(super::check_rna_str("CGAU").is_ok());
-        assert_eq!(super::check_rna_str("T"), Err(0));
-        assert_eq!(super::check_rna_str("GX"), Err(1));
+        assert_eq!(index(super::check_rna_str("T")), Some(0));
+        assert_eq!(index(super::check_rna_str("GX")), Some(1));
     }
     #[test]
     fn test_check_rna_chars() {
         assert!(super::check_rna_chars(&['C', 'G', 'A', 'U']).is_ok());
-        assert_eq!(super::check_rna_chars(&['T']), Err(0));
-        assert_eq!(super::check_rna_chars(&['G', 'X']), Err(1));
+        assert_eq!(index(super::check_rna_chars(&['T'])), Some(0));
+        assert_eq!(index(super::check_rna_chars(&['G', 'X'])), Some(1));
+    }
+
+    #[test]
+    fn test_check_iupac() {
+        use super::Alphabet;
+
+        assert!(super::check_dna_with("GCTARYSWKMBDHVN", Alphabet::Iupac).is_ok());
+        assert!(super::check_rna_str_with("CGAURYSWKMBDHVN", Alphabet::Iupac).is_ok());
+        assert!(super::check_rna_chars_with(&['N', 'U'], Alphabet::Iupac).is_ok());
+        // Strict is the default, and it rejects ambiguity codes.
+        assert_eq!(
+            index(super::check_dna_with("GCN", Alphabet::default())),
+            Some(2)
+        );
+        assert_eq!(index(super::check_dna("GCN")), Some(2));
+        // Still, T isn't RNA, and U isn't DNA.
+        assert_eq!(
+            super::check_dna_with("NU", Alphabet::Iupac),
+            Err(NucleotideError::InvalidNucleotide {
+                char_index: 1,
+                byte_offset: 1,
+                found: 'U',
+                expected_alphabet: "GCTARYSWKMBDHVN",
+            })
+        );
+        assert_eq!(
+            index(super::check_rna_char_iter_with(
+                "RT".chars(),
+                Alphabet::Iupac
+            )),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_check_bytes() {
+        use super::Alphabet;
+
+        assert!(super::check_dna_bytes(b"GCTA").is_ok());
+        assert!(super::check_rna_bytes(b"CGAU").is_ok());
+        assert!(super::check_dna_bytes_with(b"GCN", Alphabet::Iupac).is_ok());
+        assert_eq!(
+            index(super::check_rna_bytes_with(b"RT", Alphabet::Iupac)),
+            Some(1)
+        );
+        // The same error as for the equivalent `&str`.
+        for input in ["GCU", "GCéX", "éCX", "GC\n"] {
+            assert_eq!(
+                super::check_dna_bytes(input.as_bytes()),
+                super::check_dna(input)
+            );
+            assert_eq!(
+                super::check_rna_bytes(input.as_bytes()),
+                super::check_rna_str(input)
+            );
+        }
+        // Not UTF-8
+        assert_eq!(
+            super::check_dna_bytes(b"GC\xff"),
+            Err(NucleotideError::InvalidNucleotide {
+                char_index: 2,
+                byte_offset: 2,
+                found: char::REPLACEMENT_CHARACTER,
+                expected_alphabet: "GCTA",
+            })
+        );
+        assert_eq!(super::rna_bytes_as_str(b"CGAU"), Ok("CGAU"));
+        // Only non-ASCII bytes fail. Then the error is the same as of the check.
+        assert_eq!(super::dna_bytes_as_str(b"GCX"), Ok("GCX"));
+        for input in [&b"GCX\xff"[..], b"GC\xffX", "GCé".as_bytes()] {
+            assert_eq!(
+                super::dna_bytes_as_str(input).unwrap_err(),
+                super::check_dna_bytes(input).unwrap_err()
+            );
+        }
     }
 }
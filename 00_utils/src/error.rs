@@ -0,0 +1,135 @@
+use core::fmt::{self, Display, Formatter};
+
+/// Error variant of [`crate::OurResult`]. It's `no_std`-friendly: it neither allocates, nor does it
+/// store any part of the input other than the offending `char`.
+///
+/// All variants can be located by a single 0-based index - see [`NucleotideError::index`]. That's
+/// what our Exercism exercise (and `api_tests_read_only::Tests` in `00_test_harness`) checks.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NucleotideError {
+    /// A character that is not in the expected alphabet (DNA or RNA nucleotides).
+    InvalidNucleotide {
+        /// 0-based index of the offending character, counted in `char`s.
+        char_index: usize,
+        /// 0-based offset of the offending character, counted in UTF-8 bytes.
+        byte_offset: usize,
+        found: char,
+        /// All characters that would be valid at `char_index`.
+        expected_alphabet: &'static str,
+    },
+    /// The given (or transcribed) nucleotides don't fit into the implementation's storage.
+    CapacityExceeded {
+        /// Maximum number of items (`char`s or bytes, depending on the implementation's storage).
+        capacity: usize,
+        /// Number of items (in the same unit as `capacity`) that would be needed.
+        required: usize,
+    },
+    /// The implementation requires an exact length, but the input is shorter or longer.
+    LengthMismatch { expected: usize, actual: usize },
+}
+
+impl NucleotideError {
+    /// 0-based index of the first item that caused this error. That is
+    /// - `char_index` for [`NucleotideError::InvalidNucleotide`],
+    /// - `capacity` (the first item that didn't fit) for [`NucleotideError::CapacityExceeded`], and
+    /// - the lesser of `expected` and `actual` (the first missing or extra item) for
+    ///   [`NucleotideError::LengthMismatch`].
+    pub fn index(&self) -> usize {
+        match *self {
+            Self::InvalidNucleotide { char_index, .. } => char_index,
+            Self::CapacityExceeded { capacity, .. } => capacity,
+            Self::LengthMismatch { expected, actual } => expected.min(actual),
+        }
+    }
+}
+
+impl Display for NucleotideError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNucleotide {
+                char_index,
+                byte_offset,
+                found,
+                expected_alphabet,
+            } => write!(
+                f,
+                "invalid nucleotide {found:?} at character index {char_index} (byte offset \
+                 {byte_offset}), expected one of \"{expected_alphabet}\""
+            ),
+            Self::CapacityExceeded { capacity, required } => write!(
+                f,
+                "capacity exceeded: {required} required, but only {capacity} available"
+            ),
+            Self::LengthMismatch { expected, actual } => {
+                write!(f, "length mismatch: expected {expected}, but got {actual}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for NucleotideError {}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::NucleotideError;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_index() {
+        let invalid = NucleotideError::InvalidNucleotide {
+            char_index: 3,
+            byte_offset: 5,
+            found: 'X',
+            expected_alphabet: "GCTA",
+        };
+        assert_eq!(invalid.index(), 3);
+
+        let capacity = NucleotideError::CapacityExceeded {
+            capacity: 4,
+            required: 7,
+        };
+        assert_eq!(capacity.index(), 4);
+
+        let too_short = NucleotideError::LengthMismatch {
+            expected: 4,
+            actual: 2,
+        };
+        assert_eq!(too_short.index(), 2);
+        let too_long = NucleotideError::LengthMismatch {
+            expected: 4,
+            actual: 6,
+        };
+        assert_eq!(too_long.index(), 4);
+    }
+
+    #[test]
+    fn test_display() {
+        let invalid = NucleotideError::InvalidNucleotide {
+            char_index: 1,
+            byte_offset: 2,
+            found: 'X',
+            expected_alphabet: "CGAU",
+        };
+        assert_eq!(
+            invalid.to_string(),
+            "invalid nucleotide 'X' at character index 1 (byte offset 2), expected one of \"CGAU\""
+        );
+        assert_eq!(
+            NucleotideError::CapacityExceeded {
+                capacity: 4,
+                required: 5
+            }
+            .to_string(),
+            "capacity exceeded: 5 required, but only 4 available"
+        );
+        assert_eq!(
+            NucleotideError::LengthMismatch {
+                expected: 3,
+                actual: 1
+            }
+            .to_string(),
+            "length mismatch: expected 3, but got 1"
+        );
+    }
+}
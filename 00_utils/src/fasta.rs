@@ -0,0 +1,420 @@
+//! FASTA format: a zero-copy [`FastaReader`] over `&[u8]`, and a line-wrapping [`FastaWriter`]
+//! over [`core::fmt::Write`]. Neither allocates.
+//!
+//! A record is a header line `>id description`, followed by any number of sequence lines. Lines end
+//! with `\n` or `\r\n`. Empty lines are allowed anywhere.
+//!
+//! All line and column numbers are 0-based, like [`crate::NucleotideError::index`]. Columns count
+//! `char`s.
+use core::fmt::{self, Display, Formatter, Write};
+use core::str::{self, Lines};
+
+use crate::checks::Alphabet;
+use crate::{NucleicAcid, NucleotidesView};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FastaError {
+    /// A non-empty line before the first header line.
+    MissingHeader { line: usize },
+    /// A header or sequence line is not valid UTF-8.
+    InvalidUtf8 { line: usize, column: usize },
+    /// A character of a sequence line is not a nucleotide. See
+    /// [`crate::NucleotideError::InvalidNucleotide`] for `found` and `expected_alphabet`.
+    InvalidNucleotide {
+        /// 0-based index of the record.
+        record: usize,
+        line: usize,
+        column: usize,
+        found: char,
+        expected_alphabet: &'static str,
+    },
+}
+
+impl Display for FastaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader { line } => {
+                write!(f, "line {line}: expected a header line starting with '>'")
+            }
+            Self::InvalidUtf8 { line, column } => {
+                write!(f, "line {line}, column {column}: invalid UTF-8")
+            }
+            Self::InvalidNucleotide {
+                record,
+                line,
+                column,
+                found,
+                expected_alphabet,
+            } => write!(
+                f,
+                "record {record}, line {line}, column {column}: invalid nucleotide {found:?}, \
+                 expected one of \"{expected_alphabet}\""
+            ),
+        }
+    }
+}
+
+impl core::error::Error for FastaError {}
+
+/// One FASTA record. It refers to the input of [`FastaReader`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Record<'a> {
+    /// 0-based index of this record.
+    pub index: usize,
+    /// The header up to the first whitespace, without `>`.
+    pub id: &'a str,
+    /// The rest of the header, without leading whitespace. Empty if none.
+    pub description: &'a str,
+    /// All sequence lines, line breaks included. Not validated yet.
+    sequence: &'a str,
+    /// Line number of the first sequence line.
+    first_sequence_line: usize,
+}
+
+impl<'a> Record<'a> {
+    /// Sequence lines, without their line breaks.
+    pub fn sequence_lines(&self) -> Lines<'a> {
+        self.sequence.lines()
+    }
+
+    /// Validate the sequence as DNA nucleotides, and return a lazy view of it. The view skips line
+    /// breaks, and it refers to the input of [`FastaReader`] - it copies nothing. Lazy `Dna`
+    /// implementations take it as their `View` variant.
+    pub fn dna(&self) -> Result<NucleotidesView<'a>, FastaError> {
+        self.dna_with(Alphabet::Strict)
+    }
+
+    /// Like [`Record::dna`], but accept nucleotides of the given `alphabet`.
+    pub fn dna_with(&self, alphabet: Alphabet) -> Result<NucleotidesView<'a>, FastaError> {
+        self.view(NucleicAcid::Dna, alphabet.dna_nucleotides())
+    }
+
+    /// Like [`Record::dna`], but for RNA nucleotides.
+    pub fn rna(&self) -> Result<NucleotidesView<'a>, FastaError> {
+        self.rna_with(Alphabet::Strict)
+    }
+
+    /// Like [`Record::rna`], but accept nucleotides of the given `alphabet`.
+    pub fn rna_with(&self, alphabet: Alphabet) -> Result<NucleotidesView<'a>, FastaError> {
+        self.view(NucleicAcid::Rna, alphabet.rna_nucleotides())
+    }
+
+    fn view(
+        &self,
+        stored: NucleicAcid,
+        allowed: &'static str,
+    ) -> Result<NucleotidesView<'a>, FastaError> {
+        for (line_offset, line) in self.sequence_lines().enumerate() {
+            for (column, c) in line.chars().enumerate() {
+                if !allowed.contains(c) {
+                    return Err(FastaError::InvalidNucleotide {
+                        record: self.index,
+                        line: self.first_sequence_line + line_offset,
+                        column,
+                        found: c,
+                        expected_alphabet: allowed,
+                    });
+                }
+            }
+        }
+        Ok(NucleotidesView::new_unchecked(self.sequence, stored))
+    }
+}
+
+/// [`Iterator`] of [`Record`]s of FASTA `input`. After the first error it ends.
+#[derive(Debug, Clone)]
+pub struct FastaReader<'a> {
+    /// The part of the input that we haven't read yet. It starts at the beginning of a line.
+    rest: &'a [u8],
+    /// Line number of the first line of `rest`.
+    line: usize,
+    /// Index of the next record.
+    record: usize,
+    failed: bool,
+}
+
+impl<'a> FastaReader<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Self {
+            rest: input,
+            line: 0,
+            record: 0,
+            failed: false,
+        }
+    }
+
+    /// Remove the first line (and its line break) from `rest`, and return it (without the line
+    /// break).
+    fn next_line(&mut self) -> &'a [u8] {
+        let (line, rest) = match self.rest.iter().position(|&b| b == b'\n') {
+            Some(end) => (&self.rest[..end], &self.rest[end + 1..]),
+            None => (self.rest, &self.rest[self.rest.len()..]),
+        };
+        self.rest = rest;
+        self.line += 1;
+        line.strip_suffix(b"\r").unwrap_or(line)
+    }
+
+    /// Skip empty lines before the first header. (Later empty lines belong to sequences.)
+    fn skip_empty_lines(&mut self) {
+        loop {
+            let mut probe = self.clone();
+            if probe.rest.is_empty() || !probe.next_line().is_empty() {
+                return;
+            }
+            *self = probe;
+        }
+    }
+
+    fn read_record(&mut self) -> Result<Record<'a>, FastaError> {
+        let header_line = self.line;
+        let header = self.next_line();
+        let header = match header.strip_prefix(b">") {
+            Some(header) => to_str(header, header_line, 1)?,
+            None => return Err(FastaError::MissingHeader { line: header_line }),
+        };
+        let (id, description) = match header.split_once(char::is_whitespace) {
+            Some((id, description)) => (id, description.trim_start()),
+            None => (header, ""),
+        };
+
+        let sequence_start = self.rest;
+        let first_sequence_line = self.line;
+        while !self.rest.is_empty() && !self.rest.starts_with(b">") {
+            self.next_line();
+        }
+        let sequence = &sequence_start[..sequence_start.len() - self.rest.len()];
+        let index = self.record;
+        self.record += 1;
+        Ok(Record {
+            index,
+            id,
+            description,
+            sequence: to_str(sequence, first_sequence_line, 0)?,
+            first_sequence_line,
+        })
+    }
+}
+
+impl<'a> Iterator for FastaReader<'a> {
+    type Item = Result<Record<'a>, FastaError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        self.skip_empty_lines();
+        if self.rest.is_empty() {
+            return None;
+        }
+        let result = self.read_record();
+        self.failed = result.is_err();
+        Some(result)
+    }
+}
+
+/// Convert `bytes` to `&str`, or report where they stop being UTF-8. `bytes` start at `line`
+/// (0-based) and `column` (0-based, in `char`s).
+fn to_str(bytes: &[u8], mut line: usize, mut column: usize) -> Result<&str, FastaError> {
+    str::from_utf8(bytes).map_err(|err| {
+        let valid = str::from_utf8(&bytes[..err.valid_up_to()]).expect("valid UTF-8 prefix");
+        for c in valid.chars() {
+            if c == '\n' {
+                line += 1;
+                column = 0;
+            } else if c != '\r' {
+                column += 1;
+            }
+        }
+        FastaError::InvalidUtf8 { line, column }
+    })
+}
+
+/// Write FASTA records into `out`, with sequence lines at most `width` characters long.
+#[derive(Debug)]
+pub struct FastaWriter<W> {
+    out: W,
+    width: usize,
+}
+
+impl<W: Write> FastaWriter<W> {
+    /// `width` of `0` means no wrapping (one sequence line per record).
+    pub fn new(out: W, width: usize) -> Self {
+        Self { out, width }
+    }
+
+    /// Write one record. Any `Rna` (or other sequence) that formats itself as bare nucleotides
+    /// with [`Display`] can be the `sequence`. An empty `description` is left out (with its
+    /// separating space).
+    pub fn write_record(
+        &mut self,
+        id: &str,
+        description: &str,
+        sequence: &impl Display,
+    ) -> fmt::Result {
+        if description.is_empty() {
+            writeln!(self.out, ">{id}")?;
+        } else {
+            writeln!(self.out, ">{id} {description}")?;
+        }
+        let mut lines = LineWrapper {
+            out: &mut self.out,
+            width: self.width,
+            column: 0,
+        };
+        write!(lines, "{sequence}")?;
+        if lines.column > 0 {
+            lines.out.write_char('\n')?;
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+/// Pass characters to `out`, with a line break after each `width` of them.
+struct LineWrapper<'w, W> {
+    out: &'w mut W,
+    width: usize,
+    /// Number of characters on the current line.
+    column: usize,
+}
+
+impl<'w, W: Write> Write for LineWrapper<'w, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.width > 0 && self.column == self.width {
+                self.out.write_char('\n')?;
+                self.column = 0;
+            }
+            self.out.write_char(c)?;
+            self.column += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::{FastaError, FastaReader, FastaWriter};
+    use crate::checks::{self, Alphabet};
+    use crate::NucleicAcid;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    const INPUT: &[u8] = b"\n>seq1 first sequence\nACGT\nGG\n\n>seq2\r\nTTA\r\n>empty\n";
+
+    #[test]
+    fn test_read() {
+        let records: Vec<_> = FastaReader::new(INPUT).map(Result::unwrap).collect();
+        assert_eq!(records.len(), 3);
+
+        assert_eq!(records[0].index, 0);
+        assert_eq!(records[0].id, "seq1");
+        assert_eq!(records[0].description, "first sequence");
+        assert!(records[0].sequence_lines().eq(["ACGT", "GG", ""]));
+        let dna = records[0].dna().unwrap();
+        assert!(dna.chars(NucleicAcid::Dna).eq("ACGTGG".chars()));
+        // Transcription is a complement.
+        assert!(dna
+            .complement()
+            .chars(NucleicAcid::Rna)
+            .eq("UGCACC".chars()));
+
+        assert_eq!(records[1].id, "seq2");
+        assert_eq!(records[1].description, "");
+        assert!(records[1]
+            .dna()
+            .unwrap()
+            .chars(NucleicAcid::Dna)
+            .eq("TTA".chars()));
+
+        assert_eq!(records[2].id, "empty");
+        assert_eq!(records[2].dna().unwrap().chars(NucleicAcid::Dna).count(), 0);
+
+        assert_eq!(FastaReader::new(b"").count(), 0);
+    }
+
+    #[test]
+    fn test_invalid_nucleotide() {
+        let mut reader = FastaReader::new(b">a\nACGT\n>b\nACGT\nAXGT\n");
+        assert!(reader.next().unwrap().unwrap().dna().is_ok());
+        let record = reader.next().unwrap().unwrap();
+        assert_eq!(
+            record.dna(),
+            Err(FastaError::InvalidNucleotide {
+                record: 1,
+                line: 4,
+                column: 1,
+                found: 'X',
+                expected_alphabet: checks::DNA_NUCLEOTIDES,
+            })
+        );
+        assert!(record.rna().is_err());
+        assert!(reader.next().is_none());
+
+        let record = FastaReader::new(b">a\nACGU\nRN").next().unwrap().unwrap();
+        assert!(record.rna().is_err());
+        assert!(record.rna_with(Alphabet::Iupac).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut reader = FastaReader::new(b"\nACGT\n>a\nACGT\n");
+        assert_eq!(
+            reader.next(),
+            Some(Err(FastaError::MissingHeader { line: 1 }))
+        );
+        assert_eq!(reader.next(), None);
+
+        let mut reader = FastaReader::new(b">a\nAC\nA\xffGT\n");
+        assert_eq!(
+            reader.next(),
+            Some(Err(FastaError::InvalidUtf8 { line: 2, column: 1 }))
+        );
+        assert_eq!(reader.next(), None);
+
+        assert_eq!(
+            FastaReader::new(b">\xe9\n").next(),
+            Some(Err(FastaError::InvalidUtf8 { line: 0, column: 1 }))
+        );
+    }
+
+    #[test]
+    fn test_write() {
+        let mut writer = FastaWriter::new(String::new(), 4);
+        writer
+            .write_record("seq1", "wrapped", &"CGAUCGAUC")
+            .unwrap();
+        writer.write_record("seq2", "", &"CGAU").unwrap();
+        writer.write_record("empty", "", &"").unwrap();
+        assert_eq!(
+            writer.into_inner(),
+            ">seq1 wrapped\nCGAU\nCGAU\nC\n>seq2\nCGAU\n>empty\n"
+        );
+
+        let mut writer = FastaWriter::new(String::new(), 0);
+        writer.write_record("seq", "", &"CGAUCGAUC").unwrap();
+        assert_eq!(writer.into_inner(), ">seq\nCGAUCGAUC\n");
+    }
+
+    /// What we write, we can read.
+    #[test]
+    fn test_round_trip() {
+        let mut writer = FastaWriter::new(String::new(), 3);
+        writer
+            .write_record("seq", "round trip", &"CGAUUAG")
+            .unwrap();
+        let written = writer.into_inner();
+        let record = FastaReader::new(written.as_bytes())
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.description, "round trip");
+        let rna = record.rna().unwrap();
+        assert!(rna.chars(NucleicAcid::Rna).eq("CGAUUAG".chars()));
+    }
+}
@@ -0,0 +1,500 @@
+//! FASTQ format: a zero-copy [`FastqReader`] over `&[u8]`. It doesn't allocate.
+//!
+//! A record has four lines: `@id description`, the sequence, `+` (optionally followed by anything,
+//! usually the id again), and the quality string - one character per nucleotide. Lines end with
+//! `\n` or `\r\n`. Empty lines between records are allowed.
+use core::fmt::{self, Display, Formatter};
+use core::str;
+
+use crate::checks::{self, Alphabet};
+use crate::{DnaTrait, NucleotideError, OurResult, RnaTrait};
+
+/// How quality characters encode Phred scores.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum PhredEncoding {
+    /// Sanger and Illumina 1.8+: score 0 is `!`.
+    #[default]
+    Phred33,
+    /// Illumina 1.3 to 1.7: score 0 is `@`.
+    Phred64,
+}
+
+impl PhredEncoding {
+    pub const fn offset(self) -> u8 {
+        match self {
+            Self::Phred33 => 33,
+            Self::Phred64 => 64,
+        }
+    }
+
+    /// Phred score of `quality_char`. [`None`] if it's out of range (below [`Self::offset`], or
+    /// above `~`).
+    pub fn decode(self, quality_char: u8) -> Option<u8> {
+        if (self.offset()..=b'~').contains(&quality_char) {
+            Some(quality_char - self.offset())
+        } else {
+            None
+        }
+    }
+
+    /// Quality character of Phred `score`. [`None`] if it's not representable.
+    pub fn encode(self, score: u8) -> Option<u8> {
+        score
+            .checked_add(self.offset())
+            .filter(|&quality_char| quality_char <= b'~')
+    }
+}
+
+/// Errors locate the record (0-based) and a 0-based index, like [`NucleotideError::index`]. See
+/// [`FastqError::index`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FastqError {
+    /// A missing line, a header not starting with `@`, a separator not starting with `+`, or a
+    /// header or sequence that's not UTF-8. `offset` is the 0-based byte offset (in the whole
+    /// input) of the offending line or byte.
+    Malformed { record: usize, offset: usize },
+    /// The sequence is not valid: either [`NucleotideError::InvalidNucleotide`] (indexed within
+    /// the sequence), or [`NucleotideError::LengthMismatch`] if the quality string is shorter or
+    /// longer than the sequence.
+    Sequence {
+        record: usize,
+        error: NucleotideError,
+    },
+    /// A quality character out of range of the [`PhredEncoding`]. `index` is 0-based within the
+    /// quality string.
+    InvalidQuality {
+        record: usize,
+        index: usize,
+        found: char,
+    },
+}
+
+impl FastqError {
+    /// 0-based index of the record.
+    pub fn record(&self) -> usize {
+        match *self {
+            Self::Malformed { record, .. }
+            | Self::Sequence { record, .. }
+            | Self::InvalidQuality { record, .. } => record,
+        }
+    }
+
+    /// 0-based index of the first item that caused this error. That is
+    /// - `offset` (in the whole input) for [`FastqError::Malformed`],
+    /// - [`NucleotideError::index`] (within the sequence) for [`FastqError::Sequence`], and
+    /// - `index` (within the quality string) for [`FastqError::InvalidQuality`].
+    pub fn index(&self) -> usize {
+        match *self {
+            Self::Malformed { offset, .. } => offset,
+            Self::Sequence { error, .. } => error.index(),
+            Self::InvalidQuality { index, .. } => index,
+        }
+    }
+}
+
+impl Display for FastqError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed { record, offset } => {
+                write!(f, "record {record}: malformed at byte offset {offset}")
+            }
+            Self::Sequence { record, error } => write!(f, "record {record}: {error}"),
+            Self::InvalidQuality {
+                record,
+                index,
+                found,
+            } => write!(
+                f,
+                "record {record}: invalid quality character {found:?} at index {index}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for FastqError {}
+
+/// One FASTQ read: its (validated) DNA sequence paired with its quality string. It refers to the
+/// input of [`FastqReader`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FastqRecord<'a> {
+    /// 0-based index of this record.
+    pub index: usize,
+    /// The header up to the first whitespace, without `@`.
+    pub id: &'a str,
+    /// The rest of the header, without leading whitespace. Empty if none.
+    pub description: &'a str,
+    sequence: &'a str,
+    /// As long as `sequence`, and all characters within the range of `encoding`.
+    quality: &'a [u8],
+    encoding: PhredEncoding,
+    alphabet: Alphabet,
+}
+
+impl<'a> FastqRecord<'a> {
+    /// DNA nucleotides, validated with [`FastqReader`]'s [`Alphabet`].
+    pub fn sequence(&self) -> &'a str {
+        self.sequence
+    }
+
+    /// Quality characters (still encoded).
+    pub fn quality(&self) -> &'a [u8] {
+        self.quality
+    }
+
+    /// Number of nucleotides (and of quality characters).
+    pub fn len(&self) -> usize {
+        self.sequence.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sequence.is_empty()
+    }
+
+    /// Phred scores, one per nucleotide.
+    pub fn scores(&self) -> impl DoubleEndedIterator<Item = u8> + ExactSizeIterator + 'a {
+        let encoding = self.encoding;
+        self.quality.iter().map(move |&quality_char| {
+            encoding
+                .decode(quality_char)
+                .expect("quality character validated by FastqReader")
+        })
+    }
+
+    /// Sub-read of nucleotides (and their qualities) from `start` (inclusive) to `end`
+    /// (exclusive). Panic if out of bounds - like slicing.
+    pub fn slice(&self, start: usize, end: usize) -> Self {
+        // The sequence is ASCII, so byte indices are char indices.
+        Self {
+            sequence: &self.sequence[start..end],
+            quality: &self.quality[start..end],
+            ..*self
+        }
+    }
+
+    /// Remove leading nucleotides with Phred score below `min_score`.
+    pub fn trim_start(&self, min_score: u8) -> Self {
+        let start = self
+            .scores()
+            .position(|score| score >= min_score)
+            .unwrap_or(self.len());
+        self.slice(start, self.len())
+    }
+
+    /// Remove trailing nucleotides with Phred score below `min_score`.
+    pub fn trim_end(&self, min_score: u8) -> Self {
+        let end = self
+            .scores()
+            .rposition(|score| score >= min_score)
+            .map_or(0, |last| last + 1);
+        self.slice(0, end)
+    }
+
+    /// Remove leading and trailing nucleotides with Phred score below `min_score`.
+    pub fn trim(&self, min_score: u8) -> Self {
+        self.trim_start(min_score).trim_end(min_score)
+    }
+
+    /// Transcribe the read into `Rna` of any [`RnaTrait`] implementation, through its `Dna` (which
+    /// refers to the read, so it copies nothing). Return an [`Err`] if `Rna` can't hold it (see
+    /// [`DnaTrait::try_into_rna`]).
+    pub fn transcribe<Dna, Rna>(&self) -> OurResult<Rna>
+    where
+        Dna: DnaTrait<'a, Rna>,
+        Rna: RnaTrait<'a> + 'a,
+    {
+        let dna = match self.alphabet {
+            Alphabet::Strict => Dna::new(self.sequence)?,
+            Alphabet::Iupac => Dna::new_iupac(self.sequence)?,
+        };
+        dna.try_into_rna()
+    }
+}
+
+/// [`Iterator`] of [`FastqRecord`]s of FASTQ `input`. After the first error it ends.
+#[derive(Debug, Clone)]
+pub struct FastqReader<'a> {
+    input: &'a [u8],
+    /// Byte offset of the part of `input` that we haven't read yet. It's at the beginning of a
+    /// line.
+    offset: usize,
+    /// Index of the next record.
+    record: usize,
+    encoding: PhredEncoding,
+    alphabet: Alphabet,
+    failed: bool,
+}
+
+impl<'a> FastqReader<'a> {
+    /// Accept [`Alphabet::Strict`] DNA nucleotides only.
+    pub fn new(input: &'a [u8], encoding: PhredEncoding) -> Self {
+        Self::with_alphabet(input, encoding, Alphabet::Strict)
+    }
+
+    pub fn with_alphabet(input: &'a [u8], encoding: PhredEncoding, alphabet: Alphabet) -> Self {
+        Self {
+            input,
+            offset: 0,
+            record: 0,
+            encoding,
+            alphabet,
+            failed: false,
+        }
+    }
+
+    /// Return the next line (without its line break) and its offset, and move past it. [`None`]
+    /// at the end of input.
+    fn next_line(&mut self) -> Option<(&'a [u8], usize)> {
+        let start = self.offset;
+        let rest = &self.input[start..];
+        if rest.is_empty() {
+            return None;
+        }
+        let line = match rest.iter().position(|&b| b == b'\n') {
+            Some(end) => {
+                self.offset += end + 1;
+                &rest[..end]
+            }
+            None => {
+                self.offset = self.input.len();
+                rest
+            }
+        };
+        Some((line.strip_suffix(b"\r").unwrap_or(line), start))
+    }
+
+    fn read_record(
+        &mut self,
+        header: &'a [u8],
+        header_offset: usize,
+    ) -> Result<FastqRecord<'a>, FastqError> {
+        let record = self.record;
+        let malformed = |offset| FastqError::Malformed { record, offset };
+        let header = header.strip_prefix(b"@").ok_or(malformed(header_offset))?;
+        let header = to_str(header, header_offset + 1).map_err(malformed)?;
+        let (id, description) = match header.split_once(char::is_whitespace) {
+            Some((id, description)) => (id, description.trim_start()),
+            None => (header, ""),
+        };
+
+        let end_of_input = self.input.len();
+        let (sequence, sequence_offset) = self.next_line().ok_or(malformed(end_of_input))?;
+        let (separator, separator_offset) = self.next_line().ok_or(malformed(end_of_input))?;
+        if !separator.starts_with(b"+") {
+            return Err(malformed(separator_offset));
+        }
+        let (quality, _) = self.next_line().ok_or(malformed(end_of_input))?;
+
+        let sequence = to_str(sequence, sequence_offset).map_err(malformed)?;
+        checks::check_dna_with(sequence, self.alphabet)
+            .map_err(|error| FastqError::Sequence { record, error })?;
+        if quality.len() != sequence.len() {
+            return Err(FastqError::Sequence {
+                record,
+                error: NucleotideError::LengthMismatch {
+                    expected: sequence.len(),
+                    actual: quality.len(),
+                },
+            });
+        }
+        if let Some(index) = quality
+            .iter()
+            .position(|&quality_char| self.encoding.decode(quality_char).is_none())
+        {
+            return Err(FastqError::InvalidQuality {
+                record,
+                index,
+                found: quality[index] as char,
+            });
+        }
+        Ok(FastqRecord {
+            index: record,
+            id,
+            description,
+            sequence,
+            quality,
+            encoding: self.encoding,
+            alphabet: self.alphabet,
+        })
+    }
+}
+
+impl<'a> Iterator for FastqReader<'a> {
+    type Item = Result<FastqRecord<'a>, FastqError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let (header, header_offset) = loop {
+            match self.next_line()? {
+                (b"", _) => continue,
+                header => break header,
+            }
+        };
+        let result = self.read_record(header, header_offset);
+        self.failed = result.is_err();
+        self.record += 1;
+        Some(result)
+    }
+}
+
+/// Convert `bytes` (starting at `offset` of the input) to `&str`, or return the offset of the first
+/// byte that isn't UTF-8.
+fn to_str(bytes: &[u8], offset: usize) -> Result<&str, usize> {
+    str::from_utf8(bytes).map_err(|err| offset + err.valid_up_to())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::{FastqError, FastqReader, PhredEncoding};
+    use crate::checks::{self, Alphabet};
+    use crate::NucleotideError;
+    use alloc::vec::Vec;
+
+    const INPUT: &[u8] =
+        b"@read1 first read\nACGTTGCA\n+\n!!I5?II#\n\n@read2\r\nGGCA\r\n+read2\r\nIIII\r\n";
+
+    #[test]
+    fn test_read() {
+        let records: Vec<_> = FastqReader::new(INPUT, PhredEncoding::Phred33)
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].index, 0);
+        assert_eq!(
+            (records[0].id, records[0].description),
+            ("read1", "first read")
+        );
+        assert_eq!(records[0].sequence(), "ACGTTGCA");
+        assert!(records[0].scores().eq([0, 0, 40, 20, 30, 40, 40, 2]));
+        assert_eq!((records[1].id, records[1].description), ("read2", ""));
+        assert_eq!(records[1].sequence(), "GGCA");
+        assert_eq!(records[1].quality(), b"IIII");
+        assert_eq!(FastqReader::new(b"\n", PhredEncoding::Phred33).count(), 0);
+    }
+
+    #[test]
+    fn test_phred_encoding() {
+        assert_eq!(PhredEncoding::Phred33.decode(b'!'), Some(0));
+        assert_eq!(PhredEncoding::Phred33.decode(b'I'), Some(40));
+        assert_eq!(PhredEncoding::Phred33.decode(b' '), None);
+        assert_eq!(PhredEncoding::Phred64.decode(b'h'), Some(40));
+        assert_eq!(PhredEncoding::Phred64.decode(b'?'), None);
+        assert_eq!(PhredEncoding::Phred64.encode(40), Some(b'h'));
+        assert_eq!(PhredEncoding::Phred33.encode(94), None);
+
+        let record = FastqReader::new(b"@r\nAC\n+\nh@\n", PhredEncoding::Phred64)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert!(record.scores().eq([40, 0]));
+        // `!` is valid Phred+33, but not Phred+64.
+        assert_eq!(
+            FastqReader::new(b"@r\nAC\n+\nh!\n", PhredEncoding::Phred64).next(),
+            Some(Err(FastqError::InvalidQuality {
+                record: 0,
+                index: 1,
+                found: '!'
+            }))
+        );
+    }
+
+    #[test]
+    fn test_trim() {
+        let record = FastqReader::new(INPUT, PhredEncoding::Phred33)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.trim_start(20).sequence(), "GTTGCA");
+        assert_eq!(record.trim_end(20).sequence(), "ACGTTGC");
+        let trimmed = record.trim(20);
+        assert_eq!(trimmed.sequence(), "GTTGC");
+        assert_eq!(trimmed.quality(), b"I5?II");
+        assert_eq!(trimmed.id, "read1");
+        assert!(record.trim(41).is_empty());
+        assert!(record.trim_end(41).is_empty());
+        assert_eq!(record.slice(2, 4).sequence(), "GT");
+    }
+
+    #[test]
+    fn test_errors() {
+        let first = |input: &'static [u8]| FastqReader::new(input, PhredEncoding::Phred33).next();
+        assert_eq!(
+            first(b">r\nAC\n+\nII\n"),
+            Some(Err(FastqError::Malformed {
+                record: 0,
+                offset: 0
+            }))
+        );
+        assert_eq!(
+            first(b"@r\nAC\n-\nII\n"),
+            Some(Err(FastqError::Malformed {
+                record: 0,
+                offset: 6
+            }))
+        );
+        assert_eq!(
+            first(b"@r\nAC\n+\n"),
+            Some(Err(FastqError::Malformed {
+                record: 0,
+                offset: 8
+            }))
+        );
+        assert_eq!(
+            first(b"@r\nAC\xff\n+\nIII\n"),
+            Some(Err(FastqError::Malformed {
+                record: 0,
+                offset: 5
+            }))
+        );
+        assert_eq!(
+            first(b"@r\nAI\n+\nII\n").map(|result| result.unwrap_err().index()),
+            Some(1)
+        );
+        assert_eq!(
+            first(b"@r\nACG\n+\nII\n"),
+            Some(Err(FastqError::Sequence {
+                record: 0,
+                error: NucleotideError::LengthMismatch {
+                    expected: 3,
+                    actual: 2
+                }
+            }))
+        );
+
+        let mut reader = FastqReader::new(
+            b"@a\nA\n+\nI\n@b\nAXA\n+\nIII\n@c\nA\n+\nI\n",
+            PhredEncoding::Phred33,
+        );
+        assert!(reader.next().unwrap().is_ok());
+        let error = reader.next().unwrap().unwrap_err();
+        assert_eq!((error.record(), error.index()), (1, 1));
+        assert_eq!(
+            error,
+            FastqError::Sequence {
+                record: 1,
+                error: NucleotideError::InvalidNucleotide {
+                    char_index: 1,
+                    byte_offset: 1,
+                    found: 'X',
+                    expected_alphabet: checks::DNA_NUCLEOTIDES,
+                }
+            }
+        );
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_iupac() {
+        let input = b"@r\nACNT\n+\nIIII\n";
+        assert!(FastqReader::new(input, PhredEncoding::Phred33)
+            .next()
+            .unwrap()
+            .is_err());
+        let record = FastqReader::with_alphabet(input, PhredEncoding::Phred33, Alphabet::Iupac)
+            .next()
+            .unwrap();
+        assert_eq!(record.unwrap().sequence(), "ACNT");
+    }
+}
@@ -0,0 +1,56 @@
+use core::fmt::Debug;
+use core::marker::PhantomData;
+
+/// Type of the `len` field of array-based `Rna` (and of `Dna` materialized from it): `u8`, `u16`
+/// or `usize`. On a 64-bit target a capacity of up to 255 nucleotides needs one byte for its
+/// length, rather than eight.
+pub trait LenType: Copy + Default + Eq + Debug + 'static {
+    /// The largest length (and hence the largest capacity) this type can hold.
+    const MAX: usize;
+
+    /// Narrow `len`. The caller must ensure `len <= Self::MAX` - see [`LenFits`].
+    fn from_usize(len: usize) -> Self;
+
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_len_type {
+    ($($len_type:ty),*) => {
+        $(
+            impl LenType for $len_type {
+                const MAX: usize = <$len_type>::MAX as usize;
+
+                fn from_usize(len: usize) -> Self {
+                    debug_assert!(len <= <Self as LenType>::MAX);
+                    len as $len_type
+                }
+
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_len_type!(u8, u16, usize);
+
+/// Compile-time check that length type `L` can hold any length up to `CAPACITY`. An
+/// implementation refers to [`LenFits::ASSERT`] where it creates an instance. Then a capacity too
+/// large for `L` fails to compile (once that code is instantiated with such `L` and `CAPACITY`).
+///
+/// ```
+/// let () = utils::LenFits::<u8, 255>::ASSERT;
+/// ```
+///
+/// ```compile_fail,E0080
+/// let () = utils::LenFits::<u8, 300>::ASSERT;
+/// ```
+pub struct LenFits<L, const CAPACITY: usize>(PhantomData<L>);
+
+impl<L: LenType, const CAPACITY: usize> LenFits<L, CAPACITY> {
+    pub const ASSERT: () = assert!(
+        CAPACITY <= L::MAX,
+        "The length type is too small for the capacity."
+    );
+}
@@ -2,20 +2,133 @@ use core::fmt::Debug;
 
 pub mod api_tests_mut;
 pub mod checks;
+mod error;
+pub mod fasta;
+pub mod fastq;
+mod len;
+pub mod protein;
+pub mod storage;
+mod transcriber;
+mod view;
+
+pub use error::NucleotideError;
+pub use len::{LenFits, LenType};
+pub use transcriber::Transcriber;
+pub use view::{MaterializedNucleotides, NucleicAcid, NucleotidesView, NucleotidesViewChars};
+
+use protein::{Protein, Translation};
 
 pub trait DnaTrait<'a, Rna>: Sized + PartialEq + Eq + Debug
 where
     Rna: RnaTrait<'a> + 'a,
 {
     fn new(dna: &'a str) -> OurResult<Self>;
+    /// Like [`DnaTrait::new`], but accept IUPAC ambiguity codes, too. See
+    /// [`checks::Alphabet::Iupac`].
+    fn new_iupac(dna: &'a str) -> OurResult<Self>;
+    /// Like [`DnaTrait::new`], but from bytes that don't need to be proven UTF-8 first. Errors
+    /// locate the same index as for the equivalent `&str` - see [`checks::check_dna_bytes`].
+    ///
+    /// The default implementation passes `dna` to [`DnaTrait::new`] as `&str` referring to the same
+    /// bytes (see [`checks::dna_bytes_as_str`]). So `new` checks the nucleotides only once.
+    fn new_from_bytes(dna: &'a [u8]) -> OurResult<Self> {
+        Self::new(checks::dna_bytes_as_str(dna)?)
+    }
     /// The common practice is that `into_*` methods consume `self` (rather than taking it by
     /// reference). However, it's OK to take by reference where possible - making the API flexible.
     #[allow(clippy::wrong_self_convention)]
     fn into_rna(&self) -> Rna;
+
+    /// Like [`DnaTrait::into_rna`], but never panic. Instead, return [`Err`] (for example
+    /// [`NucleotideError::CapacityExceeded`]) if `Rna` can't hold the transcribed nucleotides.
+    ///
+    /// The default implementation suits implementations whose [`DnaTrait::into_rna`] can't fail.
+    /// Implementations with a limited storage must override it.
+    #[allow(clippy::wrong_self_convention)]
+    fn try_into_rna(&self) -> OurResult<Rna> {
+        Ok(self.into_rna())
+    }
 }
 
 pub trait RnaTrait<'a>: Sized + PartialEq + Eq + Debug {
     fn new(rna: &'a str) -> OurResult<Self>;
+    /// Like [`RnaTrait::new`], but accept IUPAC ambiguity codes, too. See
+    /// [`checks::Alphabet::Iupac`].
+    fn new_iupac(rna: &'a str) -> OurResult<Self>;
+    /// Like [`DnaTrait::new_from_bytes`], for RNA. See [`checks::check_rna_bytes`].
+    fn new_from_bytes(rna: &'a [u8]) -> OurResult<Self> {
+        Self::new(checks::rna_bytes_as_str(rna)?)
+    }
+}
+
+/// Companion of [`RnaTrait`] for reverse transcription (RNA back to DNA, as in cDNA). It's separate
+/// from [`RnaTrait`], because only this needs to know the respective `Dna` type.
+pub trait RnaToDnaTrait<'a, Dna>: RnaTrait<'a> {
+    /// Like [`DnaTrait::into_rna`], but the other way: create a `Dna` instance based on `self`.
+    #[allow(clippy::wrong_self_convention)]
+    fn into_dna(&self) -> Dna;
+}
+
+/// Like [`DnaTrait`], but for implementations whose `Rna` doesn't own its nucleotides. Instead,
+/// [`DnaTraitWithStorage::into_rna`] transcribes into a storage slice passed by the caller, and the
+/// result refers to that slice.
+pub trait DnaTraitWithStorage<'a, Rna>: Sized + PartialEq + Eq + Debug
+where
+    Rna: RnaTrait<'a> + 'a,
+{
+    fn new(dna: &'a str) -> OurResult<Self>;
+    /// Like [`DnaTrait::new_iupac`].
+    fn new_iupac(dna: &'a str) -> OurResult<Self>;
+    /// Like [`DnaTrait::new_from_bytes`].
+    fn new_from_bytes(dna: &'a [u8]) -> OurResult<Self> {
+        Self::new(checks::dna_bytes_as_str(dna)?)
+    }
+    /// Transcribe `self` into `storage`, and return `Rna` referring to it. `storage` must be at
+    /// least as long as the UTF-8 length of `self`. Otherwise panic.
+    #[allow(clippy::wrong_self_convention)]
+    fn into_rna(&self, storage: &'a mut [u8]) -> Rna;
+}
+
+/// Companion of [`DnaTraitWithStorage`], like [`RnaToDnaTrait`] is of [`DnaTrait`].
+pub trait RnaToDnaTraitWithStorage<'a, Dna>: RnaTrait<'a> {
+    /// Reverse transcribe `self` into `storage`, and return `Dna` referring to it. Panic if
+    /// `storage` is too short.
+    #[allow(clippy::wrong_self_convention)]
+    fn into_dna(&self, storage: &'a mut [u8]) -> Dna;
+}
+
+/// Complementary nucleotides (A-T/U and C-G base pairs). Implemented by both `Dna` and `Rna`.
+pub trait ComplementTrait: Sized {
+    /// Create the complement of `self`: each nucleotide replaced with its pair, in the same order.
+    fn complement(&self) -> Self;
+    /// Create the reverse complement of `self`: the complement in the reverse order. That's how the
+    /// other strand reads in its own (5' to 3') direction.
+    fn reverse_complement(&self) -> Self;
+}
+
+/// In-place variants of [`ComplementTrait`]. Owned implementations modify their storage, without
+/// copying it.
+pub trait ComplementMutTrait: ComplementTrait {
+    fn complement_in_place(&mut self);
+    fn reverse_complement_in_place(&mut self);
+}
+
+/// Translation of RNA codons into a [`Protein`]. Implementations iterate over their nucleotides
+/// (transcribing them on the fly, if need be) - they don't store the RNA anywhere.
+pub trait TranslateTrait {
+    /// Translate `self` into `buffer`: either a caller-provided `&mut [u8]`, or an own array. See
+    /// [`Protein::new_from_rna`].
+    fn translate_into<B: AsRef<[u8]> + AsMut<[u8]>>(
+        &self,
+        buffer: B,
+        translation: Translation,
+    ) -> OurResult<Protein<B>>;
+
+    /// Translate `self` with the standard genetic code, up to the first stop codon, into an own
+    /// array of `P` amino acids.
+    fn translate<const P: usize>(&self) -> OurResult<Protein<[u8; P]>> {
+        self.translate_into([0; P], Translation::default())
+    }
 }
 
 pub trait RnaTraitMut<'a>: RnaTrait<'a> {
@@ -24,32 +137,121 @@ pub trait RnaTraitMut<'a>: RnaTrait<'a> {
     fn set_from_iter(&mut self, iter: &mut dyn Iterator<Item = char>) -> OurResult<()>;
 }
 
-/// Custom result type. It works with our Exercism exercise (the error variant uses `usize` to
-/// indicate a 0-based character index that is not a valid DNA/RNA nucleotide). Type parameter `T`
-/// is the success variant type, carrying a result as needed.
+/// Custom result type. It works with our Exercism exercise (the error variant carries a 0-based
+/// character index that is not a valid DNA/RNA nucleotide - see [`NucleotideError::index`]). Type
+/// parameter `T` is the success variant type, carrying a result as needed.
 ///
 // New to Rust? Question mark operator shortcuts on error and returns it here.
-pub type OurResult<T> = Result<T, usize>;
+pub type OurResult<T> = Result<T, NucleotideError>;
+
+/// Complement of an IUPAC ambiguity code. The same for DNA and RNA, because no ambiguity code is
+/// specific to either. [`None`] for anything else.
+fn ambiguity_code_complement(code: char) -> Option<char> {
+    let complement = match code {
+        'R' => 'Y', // A or G <-> C or T/U
+        'Y' => 'R',
+        'S' => 'S', // G or C
+        'W' => 'W', // A or T/U
+        'K' => 'M', // G or T/U <-> A or C
+        'M' => 'K',
+        'B' => 'V', // not A <-> not T/U
+        'V' => 'B',
+        'D' => 'H', // not C <-> not G
+        'H' => 'D',
+        'N' => 'N', // any
+        _ => return None,
+    };
+    Some(complement)
+}
 
-/// Translate DNA nucleotide `dna_nucl` to a RNA nucleaotide. [`panic`] if `dna_nucl` is invalid.
+/// Translate DNA nucleotide `dna_nucl` to a RNA nucleaotide. That's also a complement, so IUPAC
+/// ambiguity codes map to their complements (for example `R` to `Y`). [`panic`] if `dna_nucl` is
+/// invalid.
 pub fn dna_to_rna(dna_nucl: char) -> char {
     match dna_nucl {
         'G' => 'C',
         'C' => 'G',
         'T' => 'A',
         'A' => 'U',
-        _ => panic!("Unrecognized nucleotide {dna_nucl}."),
+        _ => ambiguity_code_complement(dna_nucl)
+            .unwrap_or_else(|| panic!("Unrecognized nucleotide {dna_nucl}.")),
+    }
+}
+
+/// Translate RNA nucleotide `rna_nucl` to a DNA nucleotide. This is the inverse of [`dna_to_rna`].
+/// [`panic`] if `rna_nucl` is invalid.
+pub fn rna_to_dna(rna_nucl: char) -> char {
+    match rna_nucl {
+        'C' => 'G',
+        'G' => 'C',
+        'A' => 'T',
+        'U' => 'A',
+        _ => ambiguity_code_complement(rna_nucl)
+            .unwrap_or_else(|| panic!("Unrecognized nucleotide {rna_nucl}.")),
+    }
+}
+
+/// Complementary DNA nucleotide of `dna_nucl`. [`panic`] if `dna_nucl` is invalid.
+pub fn dna_complement(dna_nucl: char) -> char {
+    match dna_nucl {
+        'G' => 'C',
+        'C' => 'G',
+        'T' => 'A',
+        'A' => 'T',
+        _ => ambiguity_code_complement(dna_nucl)
+            .unwrap_or_else(|| panic!("Unrecognized nucleotide {dna_nucl}.")),
+    }
+}
+
+/// Complementary RNA nucleotide of `rna_nucl`. [`panic`] if `rna_nucl` is invalid.
+pub fn rna_complement(rna_nucl: char) -> char {
+    match rna_nucl {
+        'G' => 'C',
+        'C' => 'G',
+        'U' => 'A',
+        'A' => 'U',
+        _ => ambiguity_code_complement(rna_nucl)
+            .unwrap_or_else(|| panic!("Unrecognized nucleotide {rna_nucl}.")),
+    }
+}
+
+/// Complement `nucleotides` (ASCII bytes) in place, and reverse them if `reverse`. `complement` is
+/// either [`dna_complement`] or [`rna_complement`].
+pub fn complement_bytes(nucleotides: &mut [u8], complement: fn(char) -> char, reverse: bool) {
+    for nucl in nucleotides.iter_mut() {
+        *nucl = complement(*nucl as char) as u8;
+    }
+    if reverse {
+        nucleotides.reverse();
     }
 }
 
 /// Iterate over `rna_iter` until its end. Transform its characters to UTF-8 and store them in
 /// `result`. Return number (length) of copied UTF-8 bytes. Panic if `result` doesn't have enough
-/// space.
+/// space. See also [`try_char_iter_to_bytes`].
 pub fn char_iter_to_bytes(result: &mut [u8], char_iter: impl Iterator<Item = char>) -> usize {
+    try_char_iter_to_bytes(result, char_iter).expect("enough space for UTF-8 bytes")
+}
+
+/// Like [`char_iter_to_bytes`], but never panic. If `result` doesn't have enough space, return
+/// [`NucleotideError::CapacityExceeded`]. (Its `required` field is the number of UTF-8 bytes of
+/// the whole `char_iter`, so this consumes `char_iter` until its end even on error.) On error
+/// `result` contains the UTF-8 bytes of the characters that did fit.
+pub fn try_char_iter_to_bytes(
+    result: &mut [u8],
+    mut char_iter: impl Iterator<Item = char>,
+) -> OurResult<usize> {
     let mut char_to_utf8 = [0u8; 4];
     let mut result_idx = 0usize;
-    for c in char_iter {
+    while let Some(c) = char_iter.next() {
         let utf8 = c.encode_utf8(&mut char_to_utf8[..]);
+        if result_idx + utf8.len() > result.len() {
+            let remaining: usize = char_iter.map(char::len_utf8).sum();
+            return Err(NucleotideError::CapacityExceeded {
+                capacity: result.len(),
+                required: result_idx + utf8.len() + remaining,
+            });
+        }
         // Prefer not the following two lines due to the function call overhead.
         // result[result_idx..result_idx + utf8.len()].copy_from_slice(&utf8.as_bytes()[..utf8.len()]);
         // result_idx += utf8.len();
@@ -61,7 +263,38 @@ pub fn char_iter_to_bytes(result: &mut [u8], char_iter: impl Iterator<Item = cha
             result_idx += 1;
         }
     }
-    result_idx
+    Ok(result_idx)
+}
+
+/// Overwrite `bytes` with zeros. The writes are volatile, so the compiler doesn't remove them as
+/// dead stores - not even right before `bytes` get dropped or deallocated.
+pub fn wipe(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // SAFETY: `byte` is a valid, aligned and exclusive reference.
+        unsafe { core::ptr::write_volatile(byte, 0) };
+    }
+    // Don't let the compiler reorder any later accesses (for example, a deallocation) before the
+    // writes above.
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// Compare `left` and `right` in time that depends on their lengths only, not on their content:
+/// read all bytes of both, with no early return, and don't branch on them. The shorter one compares
+/// as if padded with zeros, so that storages of different capacities (whose unused bytes are zero)
+/// can compare equal.
+pub fn ct_eq(left: impl IntoIterator<Item = u8>, right: impl IntoIterator<Item = u8>) -> bool {
+    let (mut left, mut right) = (left.into_iter(), right.into_iter());
+    let mut diff = 0u8;
+    loop {
+        // This branches on the lengths (which are public), but not on the bytes.
+        let (left_byte, right_byte) = match (left.next(), right.next()) {
+            (None, None) => break,
+            (left_byte, right_byte) => (left_byte.unwrap_or(0), right_byte.unwrap_or(0)),
+        };
+        diff |= left_byte ^ right_byte;
+    }
+    // Best-effort: keep the compiler from turning the loop above into an early exit.
+    core::hint::black_box(diff) == 0
 }
 
 // Not public - not a part of public API.
@@ -98,7 +331,7 @@ impl CharBytesIter {
 }
 
 pub fn char_iter_to_byte_iter(char_iter: impl Iterator<Item = char>) -> impl Iterator<Item = u8> {
-    char_iter.flat_map(|c| CharBytesIter::new(c))
+    char_iter.flat_map(CharBytesIter::new)
 }
 
 #[cfg(test)]
@@ -117,5 +350,156 @@ mod tests {
         super::dna_to_rna('U');
     }
 
-    // @TODO test for char_iter_to_bytes()
+    #[test]
+    fn test_rna_to_dna() {
+        assert_eq!(super::rna_to_dna('C'), 'G');
+        assert_eq!(super::rna_to_dna('G'), 'C');
+        assert_eq!(super::rna_to_dna('A'), 'T');
+        assert_eq!(super::rna_to_dna('U'), 'A');
+        for dna_nucl in "GCTA".chars() {
+            assert_eq!(super::rna_to_dna(super::dna_to_rna(dna_nucl)), dna_nucl);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rna_to_dna_panic_invalid() {
+        super::rna_to_dna('T');
+    }
+
+    #[test]
+    fn test_ambiguity_codes() {
+        assert_eq!(super::dna_to_rna('R'), 'Y');
+        assert_eq!(super::dna_to_rna('K'), 'M');
+        assert_eq!(super::dna_to_rna('N'), 'N');
+        assert_eq!(super::rna_to_dna('B'), 'V');
+        assert_eq!(super::dna_complement('D'), 'H');
+        assert_eq!(super::rna_complement('S'), 'S');
+        for code in "RYSWKMBDHVN".chars() {
+            assert_eq!(super::rna_to_dna(super::dna_to_rna(code)), code);
+            assert_eq!(super::dna_complement(super::dna_complement(code)), code);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dna_to_rna_panic_invalid_ambiguity_code() {
+        super::dna_to_rna('X');
+    }
+
+    #[test]
+    fn test_complement() {
+        for (dna_nucl, rna_nucl) in "GCTA".chars().zip("GCUA".chars()) {
+            assert_eq!(
+                super::dna_complement(super::dna_complement(dna_nucl)),
+                dna_nucl
+            );
+            assert_eq!(
+                super::rna_complement(super::rna_complement(rna_nucl)),
+                rna_nucl
+            );
+            // Transcription is a complement into the other alphabet.
+            assert_eq!(super::dna_to_rna(dna_nucl), super::rna_complement(rna_nucl));
+        }
+        assert_eq!(super::dna_complement('A'), 'T');
+        assert_eq!(super::rna_complement('A'), 'U');
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dna_complement_panic_invalid() {
+        super::dna_complement('U');
+    }
+
+    #[test]
+    fn test_char_iter_to_bytes() {
+        let mut result = [0u8; 4];
+        assert_eq!(super::char_iter_to_bytes(&mut result, "CGé".chars()), 4);
+        assert_eq!(&result, "CGé".as_bytes());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_char_iter_to_bytes_panic_no_space() {
+        super::char_iter_to_bytes(&mut [0u8; 2], "CGA".chars());
+    }
+
+    #[test]
+    fn test_try_char_iter_to_bytes() {
+        let mut result = [0u8; 4];
+        assert_eq!(
+            super::try_char_iter_to_bytes(&mut result, "CGA".chars()),
+            Ok(3)
+        );
+        assert_eq!(&result[..3], b"CGA");
+    }
+
+    #[test]
+    fn test_try_char_iter_to_bytes_no_space() {
+        let mut result = [0u8; 3];
+        assert_eq!(
+            super::try_char_iter_to_bytes(&mut result, "CGAUé".chars()),
+            Err(crate::NucleotideError::CapacityExceeded {
+                capacity: 3,
+                required: 6
+            })
+        );
+        // A multi-byte character that would be split doesn't get stored partially.
+        let mut result = [0u8; 3];
+        assert_eq!(
+            super::try_char_iter_to_bytes(&mut result, "CGé".chars()),
+            Err(crate::NucleotideError::CapacityExceeded {
+                capacity: 3,
+                required: 4
+            })
+        );
+        assert_eq!(&result, b"CG\0");
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(super::ct_eq(*b"CGAU", *b"CGAU"));
+        assert!(!super::ct_eq(*b"CGAU", *b"CGAA"));
+        assert!(!super::ct_eq(*b"CGAU", *b"CGA"));
+        // Zero padding
+        assert!(super::ct_eq(*b"CGA\0\0", *b"CGA"));
+        assert!(super::ct_eq([], [0, 0]));
+    }
+
+    /// The number of bytes read doesn't depend on where the first mismatch is (if any).
+    #[test]
+    fn test_ct_eq_operations_dont_depend_on_mismatch() {
+        use core::cell::Cell;
+        let count_reads = |mismatch: Option<usize>| {
+            let left = [b'A'; 40];
+            let mut right = left;
+            if let Some(index) = mismatch {
+                right[index] = b'C';
+            }
+            let reads = Cell::new(0usize);
+            let equal = super::ct_eq(
+                left.iter().copied().inspect(|_| reads.set(reads.get() + 1)),
+                right
+                    .iter()
+                    .copied()
+                    .inspect(|_| reads.set(reads.get() + 1)),
+            );
+            assert_eq!(equal, mismatch.is_none());
+            reads.get()
+        };
+        let reads_if_equal = count_reads(None);
+        assert_eq!(reads_if_equal, 80);
+        for mismatch in [0, 1, 20, 39] {
+            assert_eq!(count_reads(Some(mismatch)), reads_if_equal);
+        }
+    }
+
+    #[test]
+    fn test_wipe() {
+        let mut bytes = *b"CGAU";
+        super::wipe(&mut bytes[1..3]);
+        assert_eq!(&bytes, b"C\0\0U");
+        super::wipe(&mut bytes);
+        assert_eq!(bytes, [0; 4]);
+    }
 }
@@ -0,0 +1,386 @@
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+use core::str;
+
+use crate::{NucleotideError, OurResult};
+
+/// Amino acid (one-letter code) that a stop codon translates to, if [`StopCodons::Emit`].
+pub const STOP: char = '*';
+/// Amino acid (one-letter code) of a codon that contains an ambiguity code (or any non-`UCAG`
+/// nucleotide), because we don't resolve ambiguities.
+pub const UNKNOWN: char = 'X';
+
+/// Genetic code (a mapping of codons to amino acids), as numbered by NCBI (`transl_table`). Not all
+/// NCBI tables are here. Start codons don't matter, since we translate from the first nucleotide.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum CodonTable {
+    /// 1
+    #[default]
+    Standard,
+    /// 2
+    VertebrateMitochondrial,
+    /// 3
+    YeastMitochondrial,
+    /// 4 - Mold, Protozoan, and Coelenterate Mitochondrial; Mycoplasma; Spiroplasma.
+    MoldMitochondrial,
+    /// 5
+    InvertebrateMitochondrial,
+    /// 6 - Ciliate, Dasycladacean and Hexamita Nuclear.
+    CiliateNuclear,
+    /// 9 - Echinoderm and Flatworm Mitochondrial.
+    EchinodermMitochondrial,
+    /// 10
+    EuplotidNuclear,
+    /// 11 - Bacterial, Archaeal and Plant Plastid. Its amino acids equal [`CodonTable::Standard`].
+    Bacterial,
+    /// 12
+    AlternativeYeastNuclear,
+    /// 13
+    AscidianMitochondrial,
+    /// 14
+    AlternativeFlatwormMitochondrial,
+}
+
+impl CodonTable {
+    /// All tables, in the order of their NCBI numbers.
+    pub const ALL: [Self; 12] = [
+        Self::Standard,
+        Self::VertebrateMitochondrial,
+        Self::YeastMitochondrial,
+        Self::MoldMitochondrial,
+        Self::InvertebrateMitochondrial,
+        Self::CiliateNuclear,
+        Self::EchinodermMitochondrial,
+        Self::EuplotidNuclear,
+        Self::Bacterial,
+        Self::AlternativeYeastNuclear,
+        Self::AscidianMitochondrial,
+        Self::AlternativeFlatwormMitochondrial,
+    ];
+
+    /// NCBI number (`transl_table`) of this table.
+    pub const fn ncbi_id(self) -> u8 {
+        match self {
+            Self::Standard => 1,
+            Self::VertebrateMitochondrial => 2,
+            Self::YeastMitochondrial => 3,
+            Self::MoldMitochondrial => 4,
+            Self::InvertebrateMitochondrial => 5,
+            Self::CiliateNuclear => 6,
+            Self::EchinodermMitochondrial => 9,
+            Self::EuplotidNuclear => 10,
+            Self::Bacterial => 11,
+            Self::AlternativeYeastNuclear => 12,
+            Self::AscidianMitochondrial => 13,
+            Self::AlternativeFlatwormMitochondrial => 14,
+        }
+    }
+
+    /// The table with NCBI number `id`. [`None`] if unknown (or not implemented).
+    pub fn from_ncbi_id(id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|table| table.ncbi_id() == id)
+    }
+
+    /// Amino acids of all 64 codons, in the NCBI order: the first, second and third nucleotide each
+    /// iterate over `U` (`T`), `C`, `A`, `G` - the third one the fastest. Copied from NCBI's
+    /// `AAs = ...` lines.
+    const fn amino_acids(self) -> &'static [u8; 64] {
+        match self {
+            Self::Standard | Self::Bacterial => {
+                b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG"
+            }
+            Self::VertebrateMitochondrial => {
+                b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG"
+            }
+            Self::YeastMitochondrial => {
+                b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG"
+            }
+            Self::MoldMitochondrial => {
+                b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG"
+            }
+            Self::InvertebrateMitochondrial => {
+                b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG"
+            }
+            Self::CiliateNuclear => {
+                b"FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG"
+            }
+            Self::EchinodermMitochondrial => {
+                b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG"
+            }
+            Self::EuplotidNuclear => {
+                b"FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG"
+            }
+            Self::AlternativeYeastNuclear => {
+                b"FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG"
+            }
+            Self::AscidianMitochondrial => {
+                b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSGGVVVVAAAADDEEGGGG"
+            }
+            Self::AlternativeFlatwormMitochondrial => {
+                b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG"
+            }
+        }
+    }
+
+    /// Amino acid (one-letter code) of RNA `codon`. [`STOP`] for a stop codon. [`UNKNOWN`] if any
+    /// nucleotide is not one of `UCAG` (for example, it's an ambiguity code).
+    pub fn translate_codon(self, codon: [char; 3]) -> char {
+        let mut index = 0usize;
+        for nucleotide in codon {
+            let code = match nucleotide {
+                'U' => 0,
+                'C' => 1,
+                'A' => 2,
+                'G' => 3,
+                _ => return UNKNOWN,
+            };
+            index = index * 4 + code;
+        }
+        self.amino_acids()[index] as char
+    }
+}
+
+/// What to do on a stop codon.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum StopCodons {
+    /// End the protein at the first stop codon (and exclude the stop codon).
+    #[default]
+    Terminate,
+    /// Emit [`STOP`] and continue.
+    Emit,
+}
+
+/// Parameters of translation. The default is the standard genetic code, ending at the first stop
+/// codon.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Translation {
+    pub table: CodonTable,
+    pub stop_codons: StopCodons,
+}
+
+/// Lazy translation: an [`Iterator`] of amino acids (one-letter codes) over an [`Iterator`] of RNA
+/// nucleotides. It consumes three nucleotides per amino acid, and it ignores an incomplete codon
+/// at the end.
+#[derive(Debug, Clone)]
+pub struct AminoAcids<I> {
+    rna_chars: I,
+    translation: Translation,
+    finished: bool,
+}
+
+impl<I: Iterator<Item = char>> AminoAcids<I> {
+    pub fn new(rna_chars: I, translation: Translation) -> Self {
+        Self {
+            rna_chars,
+            translation,
+            finished: false,
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for AminoAcids<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.finished {
+            return None;
+        }
+        let mut codon = ['\0'; 3];
+        for nucleotide in &mut codon {
+            match self.rna_chars.next() {
+                Some(c) => *nucleotide = c,
+                None => {
+                    self.finished = true;
+                    return None;
+                }
+            }
+        }
+        let amino_acid = self.translation.table.translate_codon(codon);
+        if amino_acid == STOP && self.translation.stop_codons == StopCodons::Terminate {
+            self.finished = true;
+            return None;
+        }
+        Some(amino_acid)
+    }
+}
+
+impl<I: Iterator<Item = char>> FusedIterator for AminoAcids<I> {}
+
+/// A protein: a sequence of amino acids (one-letter codes, and [`STOP`] if
+/// [`StopCodons::Emit`]). It stores them (as ASCII) in `B`, which is either a caller-provided
+/// `&mut [u8]`, or an own `[u8; P]`.
+#[derive(Clone, Copy)]
+pub struct Protein<B> {
+    amino_acids: B,
+    /// Length of the used part of `amino_acids`.
+    len: usize,
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Protein<B> {
+    /// Translate `rna_chars` and store the amino acids in `buffer`. Return
+    /// [`NucleotideError::CapacityExceeded`] (in amino acids) if they don't fit. (We keep
+    /// translating, so that we report how many amino acids there are.)
+    pub fn new_from_rna(
+        mut buffer: B,
+        rna_chars: impl Iterator<Item = char>,
+        translation: Translation,
+    ) -> OurResult<Self> {
+        let storage = buffer.as_mut();
+        let capacity = storage.len();
+        let mut required = 0usize;
+        for amino_acid in AminoAcids::new(rna_chars, translation) {
+            if let Some(slot) = storage.get_mut(required) {
+                *slot = amino_acid as u8;
+            }
+            required += 1;
+        }
+        if required > capacity {
+            return Err(NucleotideError::CapacityExceeded { capacity, required });
+        }
+        Ok(Self {
+            amino_acids: buffer,
+            len: required,
+        })
+    }
+}
+
+impl<B: AsRef<[u8]>> Protein<B> {
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.amino_acids.as_ref()[..self.len])
+            .expect("ASCII one-letter codes of amino acids")
+    }
+
+    /// Number of amino acids.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Proteins are equal if their amino acids are, regardless of their storage.
+impl<L: AsRef<[u8]>, R: AsRef<[u8]>> PartialEq<Protein<R>> for Protein<L> {
+    fn eq(&self, other: &Protein<R>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+impl<B: AsRef<[u8]>> Eq for Protein<B> {}
+
+impl<B: AsRef<[u8]>> PartialEq<str> for Protein<B> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+impl<B: AsRef<[u8]>> PartialEq<&str> for Protein<B> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<B: AsRef<[u8]>> Debug for Protein<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Protein(\"{}\")", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AminoAcids, CodonTable, Protein, StopCodons, Translation, STOP, UNKNOWN};
+    use crate::NucleotideError;
+
+    #[test]
+    fn test_translate_codon() {
+        let standard = CodonTable::Standard;
+        assert_eq!(standard.translate_codon(['A', 'U', 'G']), 'M');
+        assert_eq!(standard.translate_codon(['U', 'U', 'U']), 'F');
+        assert_eq!(standard.translate_codon(['G', 'G', 'G']), 'G');
+        assert_eq!(standard.translate_codon(['U', 'A', 'A']), STOP);
+        assert_eq!(standard.translate_codon(['U', 'A', 'G']), STOP);
+        assert_eq!(standard.translate_codon(['U', 'G', 'A']), STOP);
+        assert_eq!(standard.translate_codon(['A', 'U', 'N']), UNKNOWN);
+    }
+
+    #[test]
+    fn test_alternative_tables() {
+        let uga = ['U', 'G', 'A'];
+        assert_eq!(
+            CodonTable::VertebrateMitochondrial.translate_codon(uga),
+            'W'
+        );
+        assert_eq!(CodonTable::EuplotidNuclear.translate_codon(uga), 'C');
+        assert_eq!(CodonTable::Bacterial.translate_codon(uga), STOP);
+        let aga = ['A', 'G', 'A'];
+        assert_eq!(
+            CodonTable::VertebrateMitochondrial.translate_codon(aga),
+            STOP
+        );
+        assert_eq!(
+            CodonTable::InvertebrateMitochondrial.translate_codon(aga),
+            'S'
+        );
+        assert_eq!(CodonTable::AscidianMitochondrial.translate_codon(aga), 'G');
+        let uaa = ['U', 'A', 'A'];
+        assert_eq!(CodonTable::CiliateNuclear.translate_codon(uaa), 'Q');
+        assert_eq!(
+            CodonTable::AlternativeFlatwormMitochondrial.translate_codon(uaa),
+            'Y'
+        );
+        assert_eq!(
+            CodonTable::YeastMitochondrial.translate_codon(['C', 'U', 'U']),
+            'T'
+        );
+        assert_eq!(
+            CodonTable::AlternativeYeastNuclear.translate_codon(['C', 'U', 'G']),
+            'S'
+        );
+        assert_eq!(
+            CodonTable::EchinodermMitochondrial.translate_codon(['A', 'A', 'A']),
+            'N'
+        );
+        assert_eq!(
+            CodonTable::MoldMitochondrial.translate_codon(['A', 'U', 'A']),
+            'I'
+        );
+    }
+
+    #[test]
+    fn test_ncbi_id() {
+        for table in CodonTable::ALL {
+            assert_eq!(CodonTable::from_ncbi_id(table.ncbi_id()), Some(table));
+        }
+        assert_eq!(CodonTable::from_ncbi_id(7), None);
+    }
+
+    #[test]
+    fn test_amino_acids() {
+        let terminate = Translation::default();
+        let emit = Translation {
+            stop_codons: StopCodons::Emit,
+            ..Translation::default()
+        };
+        assert!(AminoAcids::new("AUGUUUUAAGGG".chars(), terminate).eq("MF".chars()));
+        assert!(AminoAcids::new("AUGUUUUAAGGG".chars(), emit).eq("MF*G".chars()));
+        // An incomplete codon at the end
+        assert!(AminoAcids::new("AUGUU".chars(), terminate).eq("M".chars()));
+        assert!(AminoAcids::new("".chars(), terminate).eq("".chars()));
+    }
+
+    #[test]
+    fn test_protein() {
+        let mut buffer = [0u8; 3];
+        let protein =
+            Protein::new_from_rna(&mut buffer[..], "AUGUUU".chars(), Translation::default());
+        assert_eq!(protein.unwrap(), "MF");
+        let protein = Protein::new_from_rna([0u8; 2], "AUGUUU".chars(), Translation::default());
+        assert_eq!(protein.unwrap().len(), 2);
+        assert_eq!(
+            Protein::new_from_rna([0u8; 1], "AUGUUUGGG".chars(), Translation::default()),
+            Err(NucleotideError::CapacityExceeded {
+                capacity: 1,
+                required: 3
+            })
+        );
+    }
+}
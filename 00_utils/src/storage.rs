@@ -0,0 +1,727 @@
+//! [`Rna`] and [`Dna`] generic over their [`Storage`]: an own array, a caller's `&mut [u8]`, or
+//! (with `alloc` feature) a [`VecStorage`]. Crates whose `Rna` differs only in how it holds its
+//! bytes alias it with their own storage type.
+//!
+//! What differs between those crates besides the storage (wiping on drop, redacted [`Debug`],
+//! constant-time [`PartialEq`], transactional mutation) is up to the storage type, too. So each
+//! crate keeps its own features: Cargo would unify features of this shared crate.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use crate::api_tests_mut::RnaTraitMutLeakStorage;
+use crate::checks::{self, Alphabet};
+use crate::protein::{Protein, Translation};
+use crate::{
+    ComplementMutTrait, ComplementTrait, DnaTrait, LenType, NucleicAcid, NucleotideError,
+    OurResult, RnaToDnaTrait, RnaTrait, RnaTraitMut, TranslateTrait,
+};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::marker::PhantomData;
+use core::str;
+
+/// Bytes that [`Rna`] stores its (UTF-8 encoded) nucleotides in, from the start.
+pub trait Storage {
+    /// Capacity, if fixed at compile time. Then [`Rna`] with a length type too small for it fails
+    /// to compile. Otherwise [`Rna`] checks the length at runtime.
+    const CAPACITY: Option<usize> = None;
+    /// Whether [`Rna`]'s [`Debug`] hides the nucleotides.
+    const REDACT_DEBUG: bool = false;
+    /// Whether [`Rna`]'s [`PartialEq`] is [`Rna::ct_eq`]. (If either side's storage says so.)
+    const CONSTANT_TIME_EQ: bool = false;
+
+    /// The whole storage, including any unused bytes (which [`Rna`] keeps zeroed).
+    fn bytes(&self) -> &[u8];
+
+    /// The part of the storage that can be modified: all of it, or nothing if read-only.
+    fn bytes_mut(&mut self) -> &mut [u8];
+
+    /// Store UTF-8 bytes of `chars` from the start, and return their number. On error return
+    /// [`NucleotideError::CapacityExceeded`]. Growable storages override this.
+    fn store(&mut self, chars: impl Iterator<Item = char>) -> OurResult<usize> {
+        crate::try_char_iter_to_bytes(self.bytes_mut(), chars)
+    }
+
+    /// An empty storage of the same capacity, if cheap enough to create. Then
+    /// [`RnaTraitMut::set_from_iter`] stages the new nucleotides in it, and on error `Rna` keeps
+    /// its previous nucleotides. On [`None`] it stores them in place, and on error it's left empty.
+    fn staging(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// A [`Storage`] that can be created for given (already validated) nucleotides. `'a` is their
+/// lifetime, for storages that refer to them rather than copy them.
+pub trait FromNucleotides<'a>: Storage + Sized {
+    /// Return [`NucleotideError::CapacityExceeded`] if `nucleotides` don't fit.
+    fn from_nucleotides(nucleotides: &'a str) -> OurResult<Self>;
+}
+
+/// A [`Storage`] whose capacity is a const generic. See [`Rna::clone_max_size`].
+pub trait FixedCapacity: Storage {
+    /// The same kind of storage, with capacity `N`.
+    type WithCapacity<const N: usize>: for<'x> FromNucleotides<'x>;
+}
+
+/// A [`Storage`] whose [`Dna`] and [`Rna`] transcribe into each other ([`DnaTrait`] and
+/// [`RnaToDnaTrait`]). Opt-in: a crate that pairs its `Rna` with its own `Dna` type doesn't
+/// implement it for its storage, or `into_dna` would be ambiguous.
+pub trait Transcribable: for<'x> FromNucleotides<'x> {}
+
+/// Transactional: it's cheap to stage in another array.
+impl<const N: usize> Storage for [u8; N] {
+    const CAPACITY: Option<usize> = Some(N);
+
+    fn bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        self
+    }
+
+    fn staging(&self) -> Option<Self> {
+        Some([0; N])
+    }
+}
+
+impl<'a, const N: usize> FromNucleotides<'a> for [u8; N] {
+    fn from_nucleotides(nucleotides: &'a str) -> OurResult<Self> {
+        let mut result = [0; N];
+        result
+            .get_mut(..nucleotides.len())
+            .ok_or(NucleotideError::CapacityExceeded {
+                capacity: N,
+                required: nucleotides.len(),
+            })?
+            .copy_from_slice(nucleotides.as_bytes());
+        Ok(result)
+    }
+}
+
+impl<const N: usize> FixedCapacity for [u8; N] {
+    type WithCapacity<const M: usize> = [u8; M];
+}
+
+impl<const N: usize> Transcribable for [u8; N] {}
+
+/// A caller's storage. Not transactional: there's nowhere else to stage.
+impl Storage for &mut [u8] {
+    fn bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+/// A slice can't be created out of nothing, other than an empty one. So this is useful for empty
+/// `nucleotides` only - see [`Rna::new_in`] instead.
+impl<'a> FromNucleotides<'a> for &mut [u8] {
+    fn from_nucleotides(nucleotides: &'a str) -> OurResult<Self> {
+        if nucleotides.is_empty() {
+            Ok(&mut [])
+        } else {
+            Err(NucleotideError::CapacityExceeded {
+                capacity: 0,
+                required: nucleotides.len(),
+            })
+        }
+    }
+}
+
+/// A heap storage that grows as needed. Its length is the number of used bytes, so it has no unused
+/// bytes - other than its spare capacity, which it keeps zeroed.
+///
+/// It wipes every allocation it's done with: [`Storage::store`] grows it by hand (rather than
+/// letting [`Vec`] reallocate), wiping the old allocation before freeing it, and [`Drop`] wipes the
+/// whole capacity. What it doesn't cover: the caller's input, copies that callers make of the
+/// nucleotides (for example, of [`Rna::as_str`]), and anything left on the stack or in registers.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Default)]
+pub struct VecStorage(Vec<u8>);
+
+#[cfg(feature = "alloc")]
+impl VecStorage {
+    /// Overwrite the whole capacity with zeros, and clear. Keep the allocation.
+    fn wipe(&mut self) {
+        // This doesn't reallocate.
+        self.0.resize(self.0.capacity(), 0);
+        crate::wipe(&mut self.0);
+        self.0.clear();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Storage for VecStorage {
+    fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+
+    /// Reserve the least number of bytes that `chars` yields up front. If it yields more, move to
+    /// an allocation twice as large, and wipe the old one.
+    fn store(&mut self, chars: impl Iterator<Item = char>) -> OurResult<usize> {
+        self.wipe();
+        // Each `char` is at least one byte.
+        self.0.reserve_exact(chars.size_hint().0);
+        for byte in crate::char_iter_to_byte_iter(chars) {
+            if self.0.len() == self.0.capacity() {
+                let mut grown = Vec::with_capacity(2 * self.0.capacity().max(4));
+                grown.extend_from_slice(&self.0);
+                drop(Self(core::mem::replace(&mut self.0, grown)));
+            }
+            self.0.push(byte);
+        }
+        Ok(self.0.len())
+    }
+
+    fn staging(&self) -> Option<Self> {
+        Some(Self::default())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Drop for VecStorage {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> FromNucleotides<'a> for VecStorage {
+    fn from_nucleotides(nucleotides: &'a str) -> OurResult<Self> {
+        Ok(Self(nucleotides.as_bytes().to_vec()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Transcribable for VecStorage {}
+
+/// Which nucleic acid [`Nucleotides`] holds: [`DnaKind`] or [`RnaKind`].
+pub trait AcidKind {
+    const ACID: NucleicAcid;
+}
+
+/// Marker of [`Dna`].
+#[derive(Clone, Copy)]
+pub enum DnaKind {}
+
+/// Marker of [`Rna`].
+#[derive(Clone, Copy)]
+pub enum RnaKind {}
+
+impl AcidKind for DnaKind {
+    const ACID: NucleicAcid = NucleicAcid::Dna;
+}
+
+impl AcidKind for RnaKind {
+    const ACID: NucleicAcid = NucleicAcid::Rna;
+}
+
+/// Nucleotides of `K` ([`DnaKind`] or [`RnaKind`]), stored in `S`. `L` is the type of the length
+/// field (see [`LenType`]). Use it through [`Dna`] or [`Rna`].
+///
+/// It's [`Copy`] and [`Clone`] if `S` is. We don't derive [`PartialEq`], so that we can compare
+/// instances regardless of their storage.
+#[derive(Clone, Copy)]
+pub struct Nucleotides<K, S, L = usize> {
+    storage: S,
+    len: L,
+    kind: PhantomData<K>,
+}
+
+/// DNA (DNA nucleotide sequence), stored in `S`. Unlike `Dna<'a>(&'a str)` of other crates it
+/// doesn't refer to its input, so it can outlive it, and it can be created from an iterator. See
+/// [`Nucleotides`].
+pub type Dna<S, L = usize> = Nucleotides<DnaKind, S, L>;
+
+/// RNA (RNA nucleotide sequence), stored in `S`. See [`Nucleotides`].
+pub type Rna<S, L = usize> = Nucleotides<RnaKind, S, L>;
+
+impl<K: AcidKind, S: Storage, L: LenType> Nucleotides<K, S, L> {
+    /// Fail to compile (once used) if `L` can't hold [`Storage::CAPACITY`]:
+    ///
+    /// ```compile_fail,E0080
+    /// let _ = utils::storage::Rna::<[u8; 300], u8>::default();
+    /// ```
+    const LEN_FITS: () = assert!(
+        match S::CAPACITY {
+            Some(capacity) => capacity <= L::MAX,
+            None => true,
+        },
+        "The length type is too small for the capacity."
+    );
+
+    fn from_parts(storage: S, len: L) -> Self {
+        let () = Self::LEN_FITS;
+        Self {
+            storage,
+            len,
+            kind: PhantomData,
+        }
+    }
+
+    /// Empty instance in `storage`. We zero `storage` (the modifiable part of it), so that no
+    /// previous data stays in its unused bytes.
+    pub fn new_in(mut storage: S) -> Self {
+        crate::wipe(storage.bytes_mut());
+        Self::from_parts(storage, L::default())
+    }
+
+    /// Store `chars_iter` in `storage`. Return [`NucleotideError::CapacityExceeded`] if they don't
+    /// fit.
+    pub fn new_from_iter_in(
+        storage: S,
+        chars_iter: impl Iterator<Item = char>,
+        alphabet: Alphabet,
+    ) -> OurResult<Self> {
+        let mut result = Self::new_in(storage);
+        result.set_from_iter_with(chars_iter, alphabet)?;
+        Ok(result)
+    }
+
+    /// Like [`RnaTraitMut::set_from_iter`], but of the given `alphabet` (and for [`Dna`], too).
+    /// Transactional if `S` has [`Storage::staging`]. We zero any unused bytes, so that they don't
+    /// keep previous nucleotides.
+    pub fn set_from_iter_with(
+        &mut self,
+        chars_iter: impl Iterator<Item = char>,
+        alphabet: Alphabet,
+    ) -> OurResult<()> {
+        match self.storage.staging() {
+            Some(mut staged) => {
+                let result = Self::store(&mut staged, chars_iter, alphabet);
+                if let Ok(len) = result {
+                    // Unused bytes of `staged` are zero. So this purges our leftover data, too.
+                    core::mem::swap(&mut self.storage, &mut staged);
+                    self.len = len;
+                }
+                // Wipe the staging copy (or our previous storage, if swapped).
+                crate::wipe(staged.bytes_mut());
+                result.map(|_| ())
+            }
+            None => {
+                let previous_len = self.len.to_usize();
+                match Self::store(&mut self.storage, chars_iter, alphabet) {
+                    Ok(len) => {
+                        self.len = len;
+                        if len.to_usize() < previous_len {
+                            crate::wipe(
+                                &mut self.storage.bytes_mut()[len.to_usize()..previous_len],
+                            );
+                        }
+                        Ok(())
+                    }
+                    Err(err) => {
+                        // A read-only storage hasn't changed, so it keeps its nucleotides.
+                        let bytes = self.storage.bytes_mut();
+                        if !bytes.is_empty() {
+                            crate::wipe(bytes);
+                            self.len = L::default();
+                        }
+                        Err(err)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Store `chars_iter` in `storage`, validate them, and return their length.
+    fn store(
+        storage: &mut S,
+        chars_iter: impl Iterator<Item = char>,
+        alphabet: Alphabet,
+    ) -> OurResult<L> {
+        let len = storage.store(chars_iter)?;
+        Self::check(Self::used_str(storage, len), alphabet)?;
+        Self::narrow(len)
+    }
+
+    fn check(nucleotides: &str, alphabet: Alphabet) -> OurResult<()> {
+        match K::ACID {
+            NucleicAcid::Dna => checks::check_dna_with(nucleotides, alphabet),
+            NucleicAcid::Rna => checks::check_rna_str_with(nucleotides, alphabet),
+        }
+    }
+
+    /// `len` as `L`. Only storages without [`Storage::CAPACITY`] can exceed it.
+    fn narrow(len: usize) -> OurResult<L> {
+        if len > L::MAX {
+            return Err(NucleotideError::CapacityExceeded {
+                capacity: L::MAX,
+                required: len,
+            });
+        }
+        Ok(L::from_usize(len))
+    }
+
+    fn used_str(storage: &S, len: usize) -> &str {
+        str::from_utf8(&storage.bytes()[..len]).expect("UTF-8 encoded string of nucleotides")
+    }
+
+    pub fn as_str(&self) -> &str {
+        Self::used_str(&self.storage, self.len.to_usize())
+    }
+
+    /// The whole storage, including the unused bytes. Not a part of the public API - used by tests
+    /// that check that no previous nucleotides leak.
+    #[doc(hidden)]
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Constant-time equality. Compare the whole storage of both (up to the larger one), not only
+    /// the used part, and don't stop at the first difference. Hence the time depends on the
+    /// storage sizes only, not on the nucleotides (or on where they differ). Unused bytes are
+    /// always zero, so they don't affect the result.
+    pub fn ct_eq<S2: Storage, L2: LenType>(&self, other: &Nucleotides<K, S2, L2>) -> bool {
+        self.ct_eq_probed(other, || ())
+    }
+
+    /// [`Nucleotides::ct_eq`], calling `probe` once per byte it reads and once when it compares
+    /// the lengths. Tests count the calls, which mustn't depend on the nucleotides. Not a part of
+    /// the public API.
+    #[doc(hidden)]
+    pub fn ct_eq_probed<S2: Storage, L2: LenType>(
+        &self,
+        other: &Nucleotides<K, S2, L2>,
+        probe: impl Fn(),
+    ) -> bool {
+        let probe = &probe;
+        let bytes_equal = crate::ct_eq(
+            self.storage.bytes().iter().copied().inspect(|_| probe()),
+            other.storage.bytes().iter().copied().inspect(|_| probe()),
+        );
+        // `&`, not `&&`: compare the lengths even if the bytes differ.
+        bytes_equal & {
+            probe();
+            self.len.to_usize() == other.len.to_usize()
+        }
+    }
+
+    /// Copy to a storage of capacity `N`. Panic if `self`'s nucleotides don't fit. Fail to compile
+    /// if `L` can't hold `N`.
+    pub fn clone_max_size<const N: usize>(&self) -> Nucleotides<K, S::WithCapacity<N>, L>
+    where
+        S: FixedCapacity,
+    {
+        let len = self.len.to_usize();
+        assert!(len <= N, "Calling clone_max_size on an instance with len={}, but the target maximum size is insufficient: {}.", len, N);
+        Nucleotides::try_from(self).expect("enough capacity")
+    }
+}
+
+impl<K: AcidKind, S: for<'x> FromNucleotides<'x>, L: LenType> Nucleotides<K, S, L> {
+    /// Accept [`Alphabet::Strict`] nucleotides only. See [`Nucleotides::new_from_iter_with`].
+    pub fn new_from_iter(chars_iter: impl Iterator<Item = char>) -> OurResult<Self> {
+        Self::new_from_iter_with(chars_iter, Alphabet::Strict)
+    }
+
+    /// Like [`Nucleotides::new_from_iter_in`], in a new storage.
+    pub fn new_from_iter_with(
+        chars_iter: impl Iterator<Item = char>,
+        alphabet: Alphabet,
+    ) -> OurResult<Self> {
+        Self::new_from_iter_in(S::from_nucleotides("")?, chars_iter, alphabet)
+    }
+}
+
+impl<'a, K: AcidKind, S: FromNucleotides<'a>, L: LenType> Nucleotides<K, S, L> {
+    fn new_from_str(nucleotides: &'a str, alphabet: Alphabet) -> OurResult<Self> {
+        Self::check(nucleotides, alphabet)?;
+        let len = Self::narrow(nucleotides.len())?;
+        Ok(Self::from_parts(S::from_nucleotides(nucleotides)?, len))
+    }
+}
+
+/// Transcribe into the same kind of storage, as [`Rna`] of the same `S` and `L`.
+impl<'a, S: Transcribable + 'a, L: LenType> DnaTrait<'a, Rna<S, L>> for Dna<S, L> {
+    /// Create a new [`Dna`] instance with given DNA nucleotides. On error return [`Err`] with a
+    /// [`NucleotideError`] locating the first incorrect character, or
+    /// [`NucleotideError::CapacityExceeded`] if `S` can't hold `dna`.
+    fn new(dna: &'a str) -> OurResult<Self> {
+        Self::new_from_str(dna, Alphabet::Strict)
+    }
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        Self::new_from_str(dna, Alphabet::Iupac)
+    }
+
+    fn into_rna(&self) -> Rna<S, L> {
+        self.try_into_rna().expect("RNA")
+    }
+
+    /// Return [`NucleotideError::CapacityExceeded`] if a new `S` can't hold the transcribed
+    /// nucleotides.
+    fn try_into_rna(&self) -> OurResult<Rna<S, L>> {
+        // `self` may be of either alphabet. Its transcription stays within Alphabet::Iupac.
+        Rna::new_from_iter_with(
+            self.as_str().chars().map(crate::dna_to_rna),
+            Alphabet::Iupac,
+        )
+    }
+}
+
+impl<'a, S: FromNucleotides<'a>, L: LenType> RnaTrait<'a> for Rna<S, L> {
+    /// Create a new [`Rna`] instance with given RNA nucleotides. If `rna` is valid, return
+    /// [`Ok(Rna)`](Ok<Rna>) containing the new instance. On error return [`Err`] with a
+    /// [`NucleotideError`] locating the first incorrect character, or
+    /// [`NucleotideError::CapacityExceeded`] if `S` can't hold `rna`.
+    fn new(rna: &'a str) -> OurResult<Self> {
+        Self::new_from_str(rna, Alphabet::Strict)
+    }
+
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        Self::new_from_str(rna, Alphabet::Iupac)
+    }
+}
+
+impl<'a, S: Transcribable, L: LenType> RnaToDnaTrait<'a, Dna<S, L>> for Rna<S, L> {
+    /// Panic if a new `S` can't hold the reverse transcribed nucleotides.
+    fn into_dna(&self) -> Dna<S, L> {
+        Dna::new_from_iter_with(
+            self.as_str().chars().map(crate::rna_to_dna),
+            Alphabet::Iupac,
+        )
+        .expect("DNA")
+    }
+}
+
+impl<'a, S: FromNucleotides<'a>, L: LenType> RnaTraitMut<'a> for Rna<S, L> {
+    /// Return [`NucleotideError::CapacityExceeded`] if `iter` doesn't fit in the modifiable part of
+    /// the storage. See [`Nucleotides::set_from_iter_with`].
+    fn set_from_iter(&mut self, iter: &mut dyn Iterator<Item = char>) -> OurResult<()> {
+        self.set_from_iter_with(iter, Alphabet::Strict)
+    }
+}
+
+impl<'a, S: FromNucleotides<'a>, L: LenType> RnaTraitMutLeakStorage<'a> for Rna<S, L> {}
+
+impl<S: Storage, L: LenType> TranslateTrait for Rna<S, L> {
+    fn translate_into<B: AsRef<[u8]> + AsMut<[u8]>>(
+        &self,
+        buffer: B,
+        translation: Translation,
+    ) -> OurResult<Protein<B>> {
+        Protein::new_from_rna(buffer, self.as_str().chars(), translation)
+    }
+}
+
+/// An empty instance. Fail to compile if `L` can't hold `S`'s capacity.
+impl<K: AcidKind, S: for<'x> FromNucleotides<'x>, L: LenType> Default for Nucleotides<K, S, L> {
+    fn default() -> Self {
+        Self::new_in(S::from_nucleotides("").expect("storage for no nucleotides"))
+    }
+}
+
+/// Copy to a different storage. Return [`NucleotideError::CapacityExceeded`] if the nucleotides
+/// don't fit in it.
+impl<K: AcidKind, S, L: LenType, S2: Storage, L2: LenType> TryFrom<&Nucleotides<K, S2, L2>>
+    for Nucleotides<K, S, L>
+where
+    S: for<'x> FromNucleotides<'x>,
+{
+    type Error = NucleotideError;
+
+    fn try_from(other: &Nucleotides<K, S2, L2>) -> OurResult<Self> {
+        let nucleotides = other.as_str();
+        let len = Self::narrow(nucleotides.len())?;
+        Ok(Self::from_parts(S::from_nucleotides(nucleotides)?, len))
+    }
+}
+
+/// Compare the nucleotides (the used part of storages) only, so that it works across storages. Not
+/// constant-time, unless either storage asks for [`Nucleotides::ct_eq`].
+impl<K: AcidKind, S: Storage, L: LenType, S2: Storage, L2: LenType>
+    PartialEq<Nucleotides<K, S2, L2>> for Nucleotides<K, S, L>
+{
+    fn eq(&self, other: &Nucleotides<K, S2, L2>) -> bool {
+        if S::CONSTANT_TIME_EQ || S2::CONSTANT_TIME_EQ {
+            self.ct_eq(other)
+        } else {
+            self.as_str() == other.as_str()
+        }
+    }
+}
+impl<K: AcidKind, S: Storage, L: LenType> Eq for Nucleotides<K, S, L> {}
+
+/// For implementations that can't share instances other than by reference (their storage can't be
+/// cloned).
+impl<K: AcidKind, S: Storage, L: LenType, S2: Storage, L2: LenType>
+    PartialEq<&Nucleotides<K, S2, L2>> for Nucleotides<K, S, L>
+{
+    fn eq(&self, other: &&Nucleotides<K, S2, L2>) -> bool {
+        *self == **other
+    }
+}
+impl<K: AcidKind, S: Storage, L: LenType, S2: Storage, L2: LenType>
+    PartialEq<Nucleotides<K, S2, L2>> for &Nucleotides<K, S, L>
+{
+    fn eq(&self, other: &Nucleotides<K, S2, L2>) -> bool {
+        **self == *other
+    }
+}
+
+impl<K: AcidKind, S: Storage, L: LenType> Display for Nucleotides<K, S, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Show as `Dna("...")` or `Rna("...")`. With [`Storage::REDACT_DEBUG`] don't show the nucleotides
+/// (for example in logs or panic messages). [`Display`] still does.
+impl<K: AcidKind, S: Storage, L: LenType> Debug for Nucleotides<K, S, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        let name = match K::ACID {
+            NucleicAcid::Dna => "Dna",
+            NucleicAcid::Rna => "Rna",
+        };
+        if S::REDACT_DEBUG {
+            write!(f, "{name}(<redacted>)")
+        } else {
+            write!(f, "{name}(\"{self}\")")
+        }
+    }
+}
+
+impl<K: AcidKind, S: Storage, L: LenType> Nucleotides<K, S, L> {
+    /// Complement the used bytes in place, and reverse them if `reverse`.
+    fn complement_used(&mut self, reverse: bool) {
+        let complement = match K::ACID {
+            NucleicAcid::Dna => crate::dna_complement,
+            NucleicAcid::Rna => crate::rna_complement,
+        };
+        let len = self.len.to_usize();
+        crate::complement_bytes(&mut self.storage.bytes_mut()[..len], complement, reverse);
+    }
+}
+
+/// Only for storages that can be cloned (and so modified - unlike a read-only one).
+impl<K: AcidKind + Clone, S: Storage + Clone, L: LenType> ComplementTrait for Nucleotides<K, S, L> {
+    fn complement(&self) -> Self {
+        let mut result = self.clone();
+        result.complement_in_place();
+        result
+    }
+
+    fn reverse_complement(&self) -> Self {
+        let mut result = self.clone();
+        result.reverse_complement_in_place();
+        result
+    }
+}
+
+impl<K: AcidKind + Clone, S: Storage + Clone, L: LenType> ComplementMutTrait
+    for Nucleotides<K, S, L>
+{
+    fn complement_in_place(&mut self) {
+        self.complement_used(false);
+    }
+
+    fn reverse_complement_in_place(&mut self) {
+        self.complement_used(true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rna;
+    #[cfg(feature = "alloc")]
+    use super::{Storage, VecStorage};
+    use crate::checks::Alphabet;
+    use crate::{NucleotideError, OurResult, RnaTrait, RnaTraitMut};
+
+    #[test]
+    fn test_array_is_transactional() -> OurResult<()> {
+        let mut rna = Rna::<[u8; 4]>::new("CGAU")?;
+        assert!(rna.set_from_iter(&mut "UAX".chars()).is_err());
+        assert_eq!(rna, Rna::<[u8; 4]>::new("CGAU")?);
+        assert_eq!(
+            rna.set_from_iter(&mut "UAGCA".chars()),
+            Err(NucleotideError::CapacityExceeded {
+                capacity: 4,
+                required: 5
+            })
+        );
+        rna.set_from_iter(&mut "UA".chars())?;
+        assert_eq!(rna.storage(), b"UA\0\0");
+        Ok(())
+    }
+
+    #[test]
+    fn test_slice_in_place() -> OurResult<()> {
+        let mut storage = [0u8; 4];
+        let mut rna =
+            Rna::<&mut [u8]>::new_from_iter_in(&mut storage, "CGAU".chars(), Alphabet::Strict)?;
+        assert_eq!(rna, Rna::<[u8; 4]>::new("CGAU")?);
+        rna.set_from_iter(&mut "UA".chars())?;
+        assert_eq!(rna.storage(), b"UA\0\0");
+        // Not transactional: on error the whole slice is zeroed, and `rna` is empty.
+        assert!(rna.set_from_iter(&mut "GX".chars()).is_err());
+        assert_eq!(rna, Rna::<[u8; 4]>::new("")?);
+        assert_eq!(storage, [0; 4]);
+
+        assert!(Rna::<&mut [u8]>::new("").is_ok());
+        assert_eq!(
+            Rna::<&mut [u8]>::new("CG"),
+            Err(NucleotideError::CapacityExceeded {
+                capacity: 0,
+                required: 2
+            })
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_vec_grows() -> OurResult<()> {
+        let mut rna = Rna::<VecStorage>::new("CG")?;
+        rna.set_from_iter(&mut "CGAUCGAUCGAUCGAUCGAUCGAUCGAUCGAUCGAUCGAUCGAU".chars())?;
+        assert_eq!(rna.as_str().len(), 44);
+        assert!(rna.set_from_iter(&mut "UX".chars()).is_err());
+        assert_eq!(rna.as_str().len(), 44);
+        rna.set_from_iter(&mut "UA".chars())?;
+        assert_eq!(rna.storage().bytes(), b"UA");
+        assert_eq!(rna, Rna::<[u8; 2]>::new("UA")?);
+        Ok(())
+    }
+
+    /// An iterator that doesn't tell its length makes [`VecStorage::store`] move to larger
+    /// allocations on its own.
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_vec_grows_without_size_hint() -> OurResult<()> {
+        let nucleotides = "CGAU".repeat(10);
+        let mut rna = Rna::<VecStorage>::new("")?;
+        rna.set_from_iter(&mut nucleotides.chars().filter(|_| true))?;
+        assert_eq!(rna.as_str(), nucleotides);
+        assert!(rna.storage().0.capacity() >= nucleotides.len());
+        Ok(())
+    }
+
+    /// A storage without a fixed capacity is limited by the length type at runtime.
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_vec_len_type_limit() {
+        let long = [b'A'; 256];
+        let long = core::str::from_utf8(&long).unwrap();
+        assert_eq!(
+            Rna::<VecStorage, u8>::new(long),
+            Err(NucleotideError::CapacityExceeded {
+                capacity: 255,
+                required: 256
+            })
+        );
+        assert!(Rna::<VecStorage, u8>::new(&long[1..]).is_ok());
+    }
+}
@@ -0,0 +1,221 @@
+use core::str;
+
+use crate::checks::Alphabet;
+use crate::{NucleotideError, OurResult};
+
+/// Streaming transcription of DNA that arrives in pieces (for example from UART or DMA), without
+/// the whole sequence in memory. Feed it `&[u8]` chunks with [`Transcriber::push`] or
+/// [`Transcriber::push_into`], then call [`Transcriber::finish`].
+///
+/// It validates the nucleotides with a running index. So an error has the same `char_index` and
+/// `byte_offset` as [`crate::checks::check_dna`] would report for all chunks concatenated. (All
+/// valid nucleotides are ASCII - one byte each - so the two are equal.)
+///
+/// The first error sticks: any further [`Transcriber::push`] and [`Transcriber::finish`] return it
+/// again, without processing anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transcriber {
+    alphabet: Alphabet,
+    /// Number of nucleotides (bytes) transcribed so far.
+    len: usize,
+    error: Option<NucleotideError>,
+}
+
+impl Default for Transcriber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transcriber {
+    /// Accept [`Alphabet::Strict`] DNA nucleotides only.
+    pub fn new() -> Self {
+        Self::with_alphabet(Alphabet::Strict)
+    }
+
+    pub fn with_alphabet(alphabet: Alphabet) -> Self {
+        Self {
+            alphabet,
+            len: 0,
+            error: None,
+        }
+    }
+
+    /// Validate and transcribe `dna_chunk`, and pass each RNA nucleotide (an ASCII byte) to `sink`.
+    /// On error `sink` has received the nucleotides before the invalid one.
+    pub fn push(&mut self, dna_chunk: &[u8], mut sink: impl FnMut(u8)) -> OurResult<()> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let allowed = self.alphabet.dna_nucleotides();
+        for (i, &byte) in dna_chunk.iter().enumerate() {
+            // `allowed` is ASCII. So a byte is valid if and only if its `char` is.
+            if !allowed.as_bytes().contains(&byte) {
+                let error = NucleotideError::InvalidNucleotide {
+                    char_index: self.len,
+                    byte_offset: self.len,
+                    found: first_char(&dna_chunk[i..]),
+                    expected_alphabet: allowed,
+                };
+                self.error = Some(error);
+                return Err(error);
+            }
+            sink(crate::dna_to_rna(byte as char) as u8);
+            self.len += 1;
+        }
+        Ok(())
+    }
+
+    /// Like [`Transcriber::push`], but store the RNA nucleotides in `rna_buffer`, starting at its
+    /// beginning. Return their number (the same as the length of `dna_chunk`).
+    ///
+    /// Return [`NucleotideError::CapacityExceeded`] if `rna_buffer` is shorter than `dna_chunk`. In
+    /// that case this doesn't process anything (and the error doesn't stick), so the caller can
+    /// retry with a longer buffer, or with a shorter chunk.
+    pub fn push_into(&mut self, dna_chunk: &[u8], rna_buffer: &mut [u8]) -> OurResult<usize> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if dna_chunk.len() > rna_buffer.len() {
+            return Err(NucleotideError::CapacityExceeded {
+                capacity: rna_buffer.len(),
+                required: dna_chunk.len(),
+            });
+        }
+        let mut rna_slots = rna_buffer.iter_mut();
+        self.push(dna_chunk, |rna_byte| {
+            *rna_slots.next().expect("buffer as long as the chunk") = rna_byte;
+        })?;
+        Ok(dna_chunk.len())
+    }
+
+    /// Number of nucleotides transcribed so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// End the stream. Return the total number of transcribed nucleotides, or the first error.
+    pub fn finish(self) -> OurResult<usize> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.len),
+        }
+    }
+}
+
+/// The first `char` of UTF-8 `bytes`, for error reporting. The chunk may end in the middle of a
+/// multi-byte `char`, or `bytes` may not be UTF-8 at all - then [`char::REPLACEMENT_CHARACTER`].
+fn first_char(bytes: &[u8]) -> char {
+    let bytes = &bytes[..bytes.len().min(4)];
+    let valid = match str::from_utf8(bytes) {
+        Ok(valid) => valid,
+        Err(err) => str::from_utf8(&bytes[..err.valid_up_to()]).expect("valid UTF-8 prefix"),
+    };
+    valid.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Transcriber;
+    use crate::checks::{self, Alphabet};
+    use crate::NucleotideError;
+
+    #[test]
+    fn test_chunks() {
+        let mut transcriber = Transcriber::new();
+        let mut rna = [0u8; 12];
+        let mut len = 0;
+        for chunk in ["ACGT", "", "GGTC", "TTAG"] {
+            len += transcriber
+                .push_into(chunk.as_bytes(), &mut rna[len..])
+                .unwrap();
+        }
+        assert_eq!(&rna, b"UGCACCAGAAUC");
+        assert_eq!(transcriber.len(), 12);
+        assert_eq!(transcriber.finish(), Ok(12));
+    }
+
+    #[test]
+    fn test_sink() {
+        let mut transcriber = Transcriber::with_alphabet(Alphabet::Iupac);
+        let mut rna = [0u8; 4];
+        let mut len = 0;
+        for chunk in [&b"AR"[..], b"YN"] {
+            transcriber
+                .push(chunk, |byte| {
+                    rna[len] = byte;
+                    len += 1;
+                })
+                .unwrap();
+        }
+        assert_eq!(&rna, b"UYRN");
+        assert_eq!(transcriber.finish(), Ok(4));
+    }
+
+    #[test]
+    fn test_error_index_as_check_dna() {
+        for (chunks, whole) in [
+            (["ACG", "TUX"], "ACGTUX"),
+            (["ACG", "T\u{e9}"], "ACGT\u{e9}"),
+            (["X", "A"], "XA"),
+        ] {
+            let mut transcriber = Transcriber::new();
+            let result = chunks
+                .iter()
+                .try_for_each(|chunk| transcriber.push(chunk.as_bytes(), |_| {}));
+            assert_eq!(result, checks::check_dna(whole));
+            assert_eq!(transcriber.finish(), checks::check_dna(whole).map(|_| 0));
+        }
+    }
+
+    #[test]
+    fn test_error_sticks() {
+        let mut transcriber = Transcriber::new();
+        assert!(transcriber.push(b"AXA", |_| {}).is_err());
+        let mut called = false;
+        assert_eq!(
+            transcriber
+                .push(b"AC", |_| called = true)
+                .unwrap_err()
+                .index(),
+            1
+        );
+        assert!(!called);
+        assert_eq!(transcriber.finish().unwrap_err().index(), 1);
+    }
+
+    /// A multi-byte character split across chunks.
+    #[test]
+    fn test_split_character() {
+        let mut transcriber = Transcriber::new();
+        assert_eq!(
+            transcriber.push(&"A\u{e9}".as_bytes()[..2], |_| {}),
+            Err(NucleotideError::InvalidNucleotide {
+                char_index: 1,
+                byte_offset: 1,
+                found: char::REPLACEMENT_CHARACTER,
+                expected_alphabet: checks::DNA_NUCLEOTIDES,
+            })
+        );
+    }
+
+    #[test]
+    fn test_buffer_too_short() {
+        let mut transcriber = Transcriber::new();
+        let mut rna = [0u8; 2];
+        assert_eq!(
+            transcriber.push_into(b"ACG", &mut rna),
+            Err(NucleotideError::CapacityExceeded {
+                capacity: 2,
+                required: 3
+            })
+        );
+        assert_eq!(transcriber.push_into(b"AC", &mut rna), Ok(2));
+        assert_eq!(&rna, b"UG");
+        assert_eq!(transcriber.finish(), Ok(2));
+    }
+}
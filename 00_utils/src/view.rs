@@ -0,0 +1,350 @@
+use crate::checks::{self, Alphabet};
+use crate::OurResult;
+use core::str::Chars;
+
+/// Which kind of nucleotides (characters) are stored, or expected.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NucleicAcid {
+    /// Nucleotides from [`crate::checks::DNA_NUCLEOTIDES`].
+    Dna,
+    /// Nucleotides from [`crate::checks::RNA_NUCLEOTIDES`].
+    Rna,
+}
+
+/// A lazy (never materialized) complement, reverse complement, or reverse of given (already
+/// validated) nucleotides. It only refers to the nucleotides, and it's [`Copy`]. So lazy `Dna`/`Rna`
+/// implementations can keep it in an enum variant, next to their `&str`- and `&[u8]`-based variants.
+///
+/// The view itself is agnostic of whether it represents DNA or RNA. Its consumer chooses that when
+/// calling [`NucleotidesView::chars`]. That's also why transcription (either way) of a view is only
+/// [`NucleotidesView::complement`] - the rest is renaming `T` to `U` or vice versa.
+///
+/// It skips line breaks (`\n` and `\r`). So it can also view a multi-line sequence without copying
+/// it - see [`crate::fasta::Record::dna`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct NucleotidesView<'a> {
+    nucleotides: &'a str,
+    stored: NucleicAcid,
+    complemented: bool,
+    reversed: bool,
+}
+
+impl<'a> NucleotidesView<'a> {
+    /// A view of `nucleotides` as they are. `stored` tells what `nucleotides` contain. They're checked
+    /// against [`Alphabet::Iupac`] of `stored`, since [`NucleotidesView::chars`] can map only those.
+    pub fn new(nucleotides: &'a str, stored: NucleicAcid) -> OurResult<Self> {
+        match stored {
+            NucleicAcid::Dna => checks::check_dna_with(nucleotides, Alphabet::Iupac)?,
+            NucleicAcid::Rna => checks::check_rna_str_with(nucleotides, Alphabet::Iupac)?,
+        }
+        Ok(Self::new_unchecked(nucleotides, stored))
+    }
+
+    /// Like [`NucleotidesView::new`], for ASCII bytes. See [`checks::check_dna_bytes`].
+    pub fn new_from_bytes(nucleotides: &'a [u8], stored: NucleicAcid) -> OurResult<Self> {
+        let nucleotides = match stored {
+            NucleicAcid::Dna => checks::dna_bytes_as_str(nucleotides)?,
+            NucleicAcid::Rna => checks::rna_bytes_as_str(nucleotides)?,
+        };
+        Self::new(nucleotides, stored)
+    }
+
+    /// Like [`NucleotidesView::new`], for `nucleotides` already checked by the caller (possibly with
+    /// line breaks).
+    pub(crate) fn new_unchecked(nucleotides: &'a str, stored: NucleicAcid) -> Self {
+        Self {
+            nucleotides,
+            stored,
+            complemented: false,
+            reversed: false,
+        }
+    }
+
+    pub fn complement(self) -> Self {
+        Self {
+            complemented: !self.complemented,
+            ..self
+        }
+    }
+
+    pub fn reverse_complement(self) -> Self {
+        Self {
+            complemented: !self.complemented,
+            reversed: !self.reversed,
+            ..self
+        }
+    }
+
+    /// Iterate over the viewed nucleotides, as nucleotides of `nucleic_acid`.
+    pub fn chars(&self, nucleic_acid: NucleicAcid) -> NucleotidesViewChars<'a> {
+        let map: fn(char) -> char = match (self.stored, nucleic_acid, self.complemented) {
+            (NucleicAcid::Dna, NucleicAcid::Dna, false)
+            | (NucleicAcid::Rna, NucleicAcid::Rna, false) => |c| c,
+            (NucleicAcid::Dna, NucleicAcid::Dna, true) => crate::dna_complement,
+            (NucleicAcid::Rna, NucleicAcid::Rna, true) => crate::rna_complement,
+            (NucleicAcid::Dna, NucleicAcid::Rna, true) => crate::dna_to_rna,
+            (NucleicAcid::Rna, NucleicAcid::Dna, true) => crate::rna_to_dna,
+            (NucleicAcid::Dna, NucleicAcid::Rna, false) => {
+                |c| crate::dna_to_rna(crate::dna_complement(c))
+            }
+            (NucleicAcid::Rna, NucleicAcid::Dna, false) => {
+                |c| crate::rna_to_dna(crate::rna_complement(c))
+            }
+        };
+        NucleotidesViewChars {
+            chars: self.nucleotides.chars(),
+            map,
+            reversed: self.reversed,
+        }
+    }
+}
+
+/// [`Iterator`] returned by [`NucleotidesView::chars`]. It has one type regardless of the view's
+/// direction and complementing, so lazy implementations can name it.
+#[derive(Clone)]
+pub struct NucleotidesViewChars<'a> {
+    chars: Chars<'a>,
+    map: fn(char) -> char,
+    reversed: bool,
+}
+
+impl<'a> Iterator for NucleotidesViewChars<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        loop {
+            let c = if self.reversed {
+                self.chars.next_back()
+            } else {
+                self.chars.next()
+            }?;
+            if c != '\n' && c != '\r' {
+                return Some((self.map)(c));
+            }
+        }
+    }
+}
+
+/// Payload of the `Materialized` variant of a lazy `Dna` - see [`crate::lazy_dna_impls`]. Each
+/// implementation stores it its own way (bytes, `char`s, or packed bits).
+pub trait MaterializedNucleotides: Clone {
+    /// Whether `Dna`'s [`Debug`](core::fmt::Debug) hides these nucleotides.
+    const REDACT_DEBUG: bool = false;
+
+    /// Iterate over the DNA nucleotides.
+    fn chars(&self) -> impl Iterator<Item = char> + '_;
+
+    /// Complement the nucleotides in place, and reverse them if `reverse`.
+    fn complement_in_place(&mut self, reverse: bool);
+}
+
+/// Implement what lazy `Dna` enums share. Each has variants `GivenNucleotides(&'a str)`,
+/// `Materialized(T)` where `T` implements [`MaterializedNucleotides`], and
+/// `View(`[`NucleotidesView`]`<'a>)`. This generates:
+/// - private `with_chars` and `with_rna_chars`, which call a closure with an iterator over the
+///   nucleotides (as DNA, or transcribed to RNA),
+/// - [`Debug`](core::fmt::Debug) in the format of a derived newtype: `Dna("...")`,
+/// - [`crate::ComplementTrait`] and [`crate::ComplementMutTrait`]. Only `Materialized` has storage
+///   to modify. Other variants become a `View`, so that they don't need to fit in any storage.
+///
+/// Pass the type with its lifetime and parameters, followed by the generic parameters in brackets:
+/// `utils::lazy_dna_impls!(DnaImpl<'a, M, L> [const M: usize, L: LenType]);`
+#[macro_export]
+macro_rules! lazy_dna_impls {
+    ($dna:ident<$lt:lifetime $(, $param:ident)*> [$($generics:tt)*]) => {
+        impl<$lt, $($generics)*> $dna<$lt $(, $param)*> {
+            /// Call `closure` with an iterator over `self`'s DNA nucleotides. Our variants have
+            /// different iterator types (and a view can't be represented by a `&str`), hence `dyn`.
+            fn with_chars<R>(
+                &self,
+                closure: impl FnOnce(&mut dyn Iterator<Item = char>) -> R,
+            ) -> R {
+                match self {
+                    Self::GivenNucleotides(dna) => closure(&mut dna.chars()),
+                    Self::Materialized(dna) => {
+                        closure(&mut $crate::MaterializedNucleotides::chars(dna))
+                    }
+                    Self::View(view) => closure(&mut view.chars($crate::NucleicAcid::Dna)),
+                }
+            }
+
+            /// Like `with_chars`, but transcribed to RNA. `self` may be of either alphabet, so
+            /// its transcription is within [`Alphabet::Iupac`]($crate::checks::Alphabet::Iupac).
+            fn with_rna_chars<R>(
+                &self,
+                closure: impl FnOnce(&mut dyn Iterator<Item = char>) -> R,
+            ) -> R {
+                self.with_chars(|dna_chars| closure(&mut dna_chars.map($crate::dna_to_rna)))
+            }
+
+            /// Copy and complement a `Materialized`. Otherwise create a `View`.
+            fn complemented(&self, reverse: bool) -> Self {
+                match self {
+                    Self::Materialized(dna) => {
+                        let mut dna = dna.clone();
+                        $crate::MaterializedNucleotides::complement_in_place(&mut dna, reverse);
+                        Self::Materialized(dna)
+                    }
+                    Self::GivenNucleotides(dna) => {
+                        let view = $crate::NucleotidesView::new(*dna, $crate::NucleicAcid::Dna)
+                            .expect("DNA nucleotides");
+                        Self::View(if reverse {
+                            view.reverse_complement()
+                        } else {
+                            view.complement()
+                        })
+                    }
+                    Self::View(view) => Self::View(if reverse {
+                        view.reverse_complement()
+                    } else {
+                        view.complement()
+                    }),
+                }
+            }
+
+            /// Complement a `Materialized` in place. Other variants become a `View`.
+            fn complement_self(&mut self, reverse: bool) {
+                match self {
+                    Self::Materialized(dna) => {
+                        $crate::MaterializedNucleotides::complement_in_place(dna, reverse)
+                    }
+                    _ => *self = self.complemented(reverse),
+                }
+            }
+        }
+
+        impl<$lt, $($generics)*> ::core::fmt::Debug for $dna<$lt $(, $param)*> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                fn redacted<T: $crate::MaterializedNucleotides>(_: &T) -> bool {
+                    T::REDACT_DEBUG
+                }
+                match self {
+                    Self::Materialized(dna) if redacted(dna) => f.write_str("Dna(<redacted>)"),
+                    _ => {
+                        f.write_str("Dna(\"")?;
+                        self.with_chars(|chars| {
+                            for c in chars {
+                                ::core::fmt::Write::write_char(f, c)?;
+                            }
+                            Ok(())
+                        })?;
+                        f.write_str("\")")
+                    }
+                }
+            }
+        }
+
+        impl<$lt, $($generics)*> $crate::ComplementTrait for $dna<$lt $(, $param)*> {
+            fn complement(&self) -> Self {
+                self.complemented(false)
+            }
+
+            fn reverse_complement(&self) -> Self {
+                self.complemented(true)
+            }
+        }
+
+        impl<$lt, $($generics)*> $crate::ComplementMutTrait for $dna<$lt $(, $param)*> {
+            fn complement_in_place(&mut self) {
+                self.complement_self(false);
+            }
+
+            fn reverse_complement_in_place(&mut self) {
+                self.complement_self(true);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NucleicAcid, NucleotidesView};
+    use crate::NucleotideError;
+
+    #[test]
+    fn test_chars() {
+        let view = NucleotidesView::new("AACGT", NucleicAcid::Dna).unwrap();
+        assert!(view.chars(NucleicAcid::Dna).eq("AACGT".chars()));
+        assert!(view.chars(NucleicAcid::Rna).eq("AACGU".chars()));
+
+        let complement = view.complement();
+        assert!(complement.chars(NucleicAcid::Dna).eq("TTGCA".chars()));
+        assert!(complement.chars(NucleicAcid::Rna).eq("UUGCA".chars()));
+
+        let reverse_complement = view.reverse_complement();
+        assert!(reverse_complement
+            .chars(NucleicAcid::Dna)
+            .eq("ACGTT".chars()));
+        assert!(reverse_complement
+            .chars(NucleicAcid::Rna)
+            .eq("ACGUU".chars()));
+
+        // Reverse without complement.
+        let reverse = reverse_complement.complement();
+        assert!(reverse.chars(NucleicAcid::Dna).eq("TGCAA".chars()));
+    }
+
+    #[test]
+    fn test_rna_based_chars() {
+        let view = NucleotidesView::new("AACGU", NucleicAcid::Rna).unwrap();
+        assert!(view.chars(NucleicAcid::Dna).eq("AACGT".chars()));
+        assert!(view
+            .complement()
+            .chars(NucleicAcid::Rna)
+            .eq("UUGCA".chars()));
+        assert!(view
+            .complement()
+            .chars(NucleicAcid::Dna)
+            .eq("TTGCA".chars()));
+        assert_eq!(view.complement().complement(), view);
+        assert_eq!(view.reverse_complement().reverse_complement(), view);
+    }
+
+    #[test]
+    fn test_line_breaks_skipped() {
+        let view = NucleotidesView::new_unchecked("AAC\nG\r\nT\n", NucleicAcid::Dna);
+        assert!(view.chars(NucleicAcid::Dna).eq("AACGT".chars()));
+        assert!(view
+            .reverse_complement()
+            .chars(NucleicAcid::Rna)
+            .eq("ACGUU".chars()));
+    }
+
+    #[test]
+    fn test_new_rejects_invalid_nucleotides() {
+        assert!(NucleotidesView::new("ACGTRYN", NucleicAcid::Dna).is_ok());
+        assert!(matches!(
+            NucleotidesView::new("hello", NucleicAcid::Dna),
+            Err(NucleotideError::InvalidNucleotide {
+                char_index: 0,
+                found: 'h',
+                ..
+            })
+        ));
+        assert!(matches!(
+            NucleotidesView::new("ACGT", NucleicAcid::Rna),
+            Err(NucleotideError::InvalidNucleotide {
+                char_index: 3,
+                found: 'T',
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_new_from_bytes() {
+        assert_eq!(
+            NucleotidesView::new_from_bytes(b"ACGTN", NucleicAcid::Dna),
+            NucleotidesView::new("ACGTN", NucleicAcid::Dna)
+        );
+        assert!(matches!(
+            NucleotidesView::new_from_bytes(b"ACGU", NucleicAcid::Dna),
+            Err(NucleotideError::InvalidNucleotide {
+                char_index: 3,
+                found: 'U',
+                ..
+            })
+        ));
+        assert!(NucleotidesView::new_from_bytes(b"AC\xffU", NucleicAcid::Rna).is_err());
+    }
+}
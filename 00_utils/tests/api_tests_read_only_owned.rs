@@ -0,0 +1,79 @@
+#![no_std]
+
+//! The read-only harness on [`utils::storage::Dna`] in an own array and (with `alloc`) in a
+//! [`VecStorage`](utils::storage::VecStorage). Unlike the lazy `Dna` of most implementations, it
+//! outlives its input.
+
+use test_harness::api_tests_read_only::Tests;
+use utils::storage::{Dna, Rna};
+use utils::{DnaTrait, NucleotideError, OurResult, RnaToDnaTrait, RnaTrait};
+
+const LONG_DNA: &str = "GATTACAGATTACAGATTACAGATTACAGATTACAGATTACAGATTACA";
+
+mod array {
+    use super::*;
+
+    struct T {}
+    impl Tests for T {
+        type Dna<'a> = Dna<[u8; 40]>;
+        type Rna<'a> = Rna<[u8; 40]>;
+        const REDACTED_DEBUG: bool = false;
+    }
+
+    test_harness::api_tests!(read_only: T);
+}
+
+#[cfg(feature = "alloc")]
+mod vec {
+    use super::*;
+    use utils::storage::VecStorage;
+
+    struct T {}
+    impl Tests for T {
+        type Dna<'a> = Dna<VecStorage>;
+        type Rna<'a> = Rna<VecStorage>;
+        const REDACTED_DEBUG: bool = false;
+    }
+
+    test_harness::api_tests!(read_only: T);
+
+    #[test]
+    fn test_new_from_iter() -> OurResult<()> {
+        let dna = Dna::<[u8; 40]>::new_from_iter("GATTACA".chars())?;
+        assert_eq!(dna, Dna::<VecStorage>::new("GATTACA")?);
+        assert_eq!(
+            Dna::<VecStorage>::new_from_iter("GAUUACA".chars())
+                .unwrap_err()
+                .index(),
+            2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_capacity_limit() {
+        assert_eq!(Dna::<VecStorage>::new(LONG_DNA).unwrap().as_str(), LONG_DNA);
+    }
+}
+
+#[test]
+fn test_new_from_iter() -> OurResult<()> {
+    let input = *b"GATTACA";
+    let dna = Dna::<[u8; 40]>::new_from_iter(input.iter().map(|&b| char::from(b)))?;
+    assert_eq!(dna, Dna::<[u8; 40]>::new("GATTACA")?);
+    assert_eq!(dna.into_rna(), Rna::<[u8; 40]>::new("CUAAUGU")?);
+    assert_eq!(Rna::<[u8; 40]>::new("CUAAUGU")?.into_dna(), dna);
+    Ok(())
+}
+
+#[test]
+fn test_capacity_exceeded() {
+    assert_eq!(
+        Dna::<[u8; 40]>::new(LONG_DNA),
+        Err(NucleotideError::CapacityExceeded {
+            capacity: 40,
+            required: 49
+        })
+    );
+    assert!(Dna::<[u8; 40]>::new_from_iter(LONG_DNA[..40].chars()).is_ok());
+}
@@ -0,0 +1,13 @@
+//! Security unit test. Like [`super::api_tests_mut_wipe_on_mut`], it needs private access to
+//! [`dna::Rna`]'s [`String`].
+use crate as dna;
+
+use super::api_tests_mut_wipe_on_mut::with_storage_leaked;
+use test_harness::api_tests_mut::wipe_on_clone::Tests;
+
+struct T {}
+impl Tests for T {
+    type Rna<'a> = dna::Rna;
+}
+
+test_harness::api_tests!(wipe_on_clone: T, with_storage_leaked);
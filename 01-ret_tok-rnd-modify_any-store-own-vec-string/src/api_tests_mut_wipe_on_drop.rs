@@ -0,0 +1,107 @@
+//! Security unit test (with `security` feature only). [`dna::Rna`]'s [`Drop`] deallocates its
+//! [`String`], so there's no storage left to inspect afterwards. Instead, [`Recorder`] (the global
+//! allocator of this test binary) keeps a copy of the block that a thread deallocated last, and our
+//! [`with_storage_leaked`] inspects that copy once `rna` has been dropped.
+extern crate std;
+
+use crate as dna;
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::Cell;
+use core::mem::MaybeUninit;
+use std::alloc::System;
+use test_harness::api_tests_mut::{self, wipe_on_drop::Tests};
+use utils::api_tests_mut::{WithStorageLeaked, WithStorageLeakedCallBack};
+use utils::{RnaTrait, RnaTraitMut};
+
+/// How many bytes of a deallocated block [`Recorder`] keeps at most. Enough for our tests.
+const RECORDED_LEN: usize = 64;
+
+/// Address, size, and the bytes (up to [`RECORDED_LEN`]) of a deallocated block.
+type Freed = (usize, usize, [MaybeUninit<u8>; RECORDED_LEN]);
+
+std::thread_local! {
+    /// Per thread, so that tests running in parallel don't overwrite each other's record.
+    static FREED: Cell<Option<Freed>> = const { Cell::new(None) };
+}
+
+/// [`System`] allocator that records the block deallocated last (see [`FREED`]).
+struct Recorder;
+
+#[global_allocator]
+static RECORDER: Recorder = Recorder;
+
+unsafe impl GlobalAlloc for Recorder {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: Our caller upholds the same contract.
+        let ptr = unsafe { System.alloc(layout) };
+        // A new block at the recorded address means the recorded block is gone.
+        let _ = FREED.try_with(|freed| {
+            if matches!(freed.get(), Some((address, ..)) if address == ptr as usize) {
+                freed.set(None);
+            }
+        });
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if layout.size() <= RECORDED_LEN {
+            let mut bytes = [MaybeUninit::uninit(); RECORDED_LEN];
+            // SAFETY: `ptr` is valid for reads of `layout.size()` bytes until we deallocate it
+            // below. `MaybeUninit` admits any bytes, even uninitialized ones.
+            unsafe {
+                ptr.cast::<MaybeUninit<u8>>()
+                    .copy_to_nonoverlapping(bytes.as_mut_ptr(), layout.size())
+            };
+            let _ = FREED.try_with(|freed| freed.set(Some((ptr as usize, layout.size(), bytes))));
+        }
+        // SAFETY: Our caller upholds the same contract.
+        unsafe { System.dealloc(ptr, layout) };
+    }
+}
+
+/// Like [`super::api_tests_mut_wipe_on_mut::with_storage_leaked`], but once `rna` has been dropped,
+/// inspect the copy of its storage that [`Recorder`] kept.
+fn with_storage_leaked(
+    rna: &mut dna::Rna,
+    with_storage_leaked_call_back: WithStorageLeakedCallBack<bool>,
+) -> bool {
+    // SAFETY: We only read the address. (If `rna` has been dropped, its `Vec` is still in place,
+    // but it must not access its storage.)
+    let address = unsafe { rna.0.as_mut_vec() }.as_ptr() as usize;
+    match FREED.with(Cell::get) {
+        Some((freed, size, bytes)) if freed == address => {
+            let mut bytes_iter = bytes[..size].iter().map(|byte| {
+                // SAFETY: Our tests create `rna` with no spare capacity, so its whole block has
+                // been initialized.
+                unsafe { byte.assume_init() }
+            });
+            with_storage_leaked_call_back(&mut bytes_iter)
+        }
+        _ => super::api_tests_mut_wipe_on_mut::with_storage_leaked(
+            rna,
+            with_storage_leaked_call_back,
+        ),
+    }
+}
+type _TWithStorageLeaked<'a> = WithStorageLeaked<'a, dna::Rna, bool>;
+const _CHECK_WITH_STORAGE_LEAKED_FUNCTION_SIGNATURE: _TWithStorageLeaked = &with_storage_leaked;
+
+struct T {}
+impl Tests for T {
+    type Rna<'a> = dna::Rna;
+}
+
+test_harness::api_tests!(wipe_on_drop: T, with_storage_leaked);
+
+#[test]
+fn test_wipe_string_zeroes_whole_capacity() {
+    let mut rna = dna::Rna::new("CGAUUAGC").unwrap();
+    // Leave leftover capacity, which a plain `String::clear` wouldn't zero.
+    rna.set_from_iter(&mut "UA".chars()).unwrap();
+    let capacity = rna.0.capacity();
+
+    super::wipe_string(&mut rna.0);
+    assert_eq!(rna.0.capacity(), capacity);
+    assert!(with_storage_leaked(&mut rna, &api_tests_mut::all_zero));
+}
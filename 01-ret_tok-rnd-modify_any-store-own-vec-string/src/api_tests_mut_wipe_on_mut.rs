@@ -0,0 +1,45 @@
+//! Security unit test. This can't be an integration test (under ../tests/), because it needs
+//! private access to [`dna::Rna`]'s [`String`], including its unused capacity.
+use crate as dna;
+
+use test_harness::api_tests_mut::wipe_on_mut::Tests;
+use utils::api_tests_mut::{WithStorageLeaked, WithStorageLeakedCallBack};
+use utils::{RnaTrait, RnaTraitMut};
+
+struct T {}
+impl Tests for T {
+    type Dna<'a> = dna::Dna;
+    type Rna<'a> = dna::Rna;
+}
+
+pub(super) fn with_storage_leaked(
+    rna: &mut dna::Rna,
+    with_storage_leaked_call_back: WithStorageLeakedCallBack<bool>,
+) -> bool {
+    // SAFETY: We don't modify the bytes, so they stay valid UTF-8.
+    let bytes = unsafe { rna.0.as_mut_vec() };
+    let mut storage = bytes.clone();
+    storage.extend(bytes.spare_capacity_mut().iter().map(|byte| {
+        // SAFETY: The whole capacity has been initialized: our tests create `rna` with no spare
+        // capacity, and then they only shorten it (or wipe it whole).
+        unsafe { byte.assume_init() }
+    }));
+    with_storage_leaked_call_back(&mut storage.into_iter())
+}
+type _TWithStorageLeaked<'a> = WithStorageLeaked<'a, dna::Rna, bool>;
+const _CHECK_WITH_STORAGE_LEAKED_FUNCTION_SIGNATURE: _TWithStorageLeaked = &with_storage_leaked;
+
+test_harness::api_tests!(wipe_on_mut: T, with_storage_leaked);
+
+#[test]
+fn test_capacity_reused() {
+    let mut rna = dna::Rna::new("CGAUUAGC").unwrap();
+    let capacity = rna.0.capacity();
+    rna.set_from_iter(&mut "UA".chars()).unwrap();
+    assert_eq!(rna, dna::Rna::new("UA").unwrap());
+    assert_eq!(rna.0.capacity(), capacity);
+
+    assert_eq!(rna.set_from_iter(&mut "AT".chars()).unwrap_err().index(), 1);
+    assert_eq!(rna, dna::Rna::new("").unwrap());
+    assert_eq!(rna.0.capacity(), capacity);
+}
@@ -3,10 +3,22 @@
 extern crate alloc;
 
 use alloc::{borrow::ToOwned, string::String};
-use core::fmt::Debug;
+use core::fmt::{self, Debug, Display, Formatter};
 // #[cfg(test)]
 // use test_harness;
-use utils::{checks, DnaTrait, OurResult, RnaTrait};
+use utils::api_tests_mut::RnaTraitMutLeakStorage;
+use utils::checks::Alphabet;
+use utils::{
+    checks, ComplementMutTrait, ComplementTrait, DnaTrait, OurResult, RnaToDnaTrait, RnaTrait,
+    RnaTraitMut,
+};
+
+#[cfg(test)]
+mod api_tests_mut_wipe_on_clone;
+#[cfg(all(test, feature = "security"))]
+mod api_tests_mut_wipe_on_drop;
+#[cfg(test)]
+mod api_tests_mut_wipe_on_mut;
 
 /// DNA (DNA nucleotide sequence).  
 ///
@@ -27,18 +39,33 @@ pub struct Dna(String);
 ///
 /// If it was created based on DNA, all nucleotides have been translated to RNA ones, and stored
 /// here. (That is different to all other implementations in neighbor crates.)
-#[derive(Debug, PartialEq, Eq, Clone)]
+///
+/// With `security` feature it wipes its [`String`]'s whole capacity on drop (before deallocation),
+/// and its [`Debug`] redacts the nucleotides.
+#[cfg_attr(not(feature = "security"), derive(Debug))]
+#[derive(PartialEq, Eq, Clone)]
 pub struct Rna(String);
 
+impl Display for Rna {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&self.0)
+    }
+}
+
 impl<'a> DnaTrait<'a, Rna> for Dna {
     /// Create a new [`Dna`] instance with given DNA nucleotides. If `dna` is valid, return  
-    /// [`Some(Dna)`](Some<Dna>) containing the new instance. On error return [`Err`] with a 0-based
-    /// index of the first incorrect character.
+    /// [`Some(Dna)`](Some<Dna>) containing the new instance. On error return [`Err`] with a
+    /// [`NucleotideError`](utils::NucleotideError) locating the first incorrect character.
     fn new(dna: &'a str) -> OurResult<Self> {
         checks::check_dna(dna)?;
         Ok(Self(dna.to_owned()))
     }
 
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, Alphabet::Iupac)?;
+        Ok(Self(dna.to_owned()))
+    }
+
     /// Create an [`Rna`] instance based on `self`. Transcript all nucleotides to RNA (and store
     /// them in the result [`Rna`] instance).
     fn into_rna(&self) -> Rna {
@@ -53,10 +80,128 @@ impl<'a> DnaTrait<'a, Rna> for Dna {
 
 impl<'a> RnaTrait<'a> for Rna {
     /// Create a new [`Rna`] instance with given RNA nucleotides. If `rna` is valid, return  
-    /// [`Some(Rna)`](Some<Rna>) containing the new instance. On error return [`Err`] with a 0-based
-    /// index of the first incorrect character.
+    /// [`Some(Rna)`](Some<Rna>) containing the new instance. On error return [`Err`] with a
+    /// [`NucleotideError`](utils::NucleotideError) locating the first incorrect character.
     fn new(rna: &'a str) -> OurResult<Self> {
         checks::check_rna_str(rna)?;
         Ok(Self(rna.to_owned()))
     }
+
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str_with(rna, Alphabet::Iupac)?;
+        Ok(Self(rna.to_owned()))
+    }
+}
+
+impl<'a> RnaTraitMut<'a> for Rna {
+    /// Reuse the [`String`]'s allocation (unless `iter` yields more than its capacity). On error
+    /// leave `self` empty.
+    fn set_from_iter(&mut self, iter: &mut dyn Iterator<Item = char>) -> OurResult<()> {
+        wipe_string(&mut self.0);
+        self.0.extend(iter);
+        if let Err(err) = checks::check_rna_str(&self.0) {
+            wipe_string(&mut self.0);
+            return Err(err);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> RnaTraitMutLeakStorage<'a> for Rna {}
+
+/// Overwrite `nucleotides` with zeros, and clear it. Keep its capacity. Otherwise [`String::clear`]
+/// would leave the previous content in the (now unused) capacity. We zero the whole capacity, so
+/// it's initialized.
+fn wipe_string(nucleotides: &mut String) {
+    let mut bytes = core::mem::take(nucleotides).into_bytes();
+    // This doesn't reallocate.
+    bytes.resize(bytes.capacity(), 0);
+    utils::wipe(&mut bytes);
+    bytes.clear();
+    *nucleotides = String::from_utf8(bytes).expect("empty, hence valid UTF-8");
+}
+
+#[cfg(feature = "security")]
+impl Debug for Rna {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Rna(<redacted>)")
+    }
+}
+
+#[cfg(feature = "security")]
+impl Drop for Rna {
+    fn drop(&mut self) {
+        wipe_string(&mut self.0);
+    }
+}
+
+impl<'a> RnaToDnaTrait<'a, Dna> for Rna {
+    /// Create a [`Dna`] instance based on `self`. Transcript all nucleotides back to DNA (and store
+    /// them in the result [`Dna`] instance).
+    fn into_dna(&self) -> Dna {
+        match self {
+            Rna(rna) => {
+                let dna_chars = rna.chars().map(utils::rna_to_dna).collect();
+                Dna(dna_chars)
+            }
+        }
+    }
+}
+
+/// Complement `nucleotides` in place, and reverse them if `reverse`. Reuse the [`String`]'s
+/// allocation. `complement` is either [`utils::dna_complement`] or [`utils::rna_complement`].
+fn complement_string_in_place(
+    nucleotides: &mut String,
+    complement: fn(char) -> char,
+    reverse: bool,
+) {
+    let mut bytes = core::mem::take(nucleotides).into_bytes();
+    // Nucleotides are ASCII, hence one byte each, and so is their complement.
+    for nucl in bytes.iter_mut() {
+        *nucl = complement(*nucl as char) as u8;
+    }
+    if reverse {
+        bytes.reverse();
+    }
+    *nucleotides = String::from_utf8(bytes).expect("UTF-8 encoded string of nucleotides");
+}
+
+impl ComplementTrait for Dna {
+    fn complement(&self) -> Self {
+        Dna(self.0.chars().map(utils::dna_complement).collect())
+    }
+
+    fn reverse_complement(&self) -> Self {
+        Dna(self.0.chars().rev().map(utils::dna_complement).collect())
+    }
+}
+
+impl ComplementMutTrait for Dna {
+    fn complement_in_place(&mut self) {
+        complement_string_in_place(&mut self.0, utils::dna_complement, false);
+    }
+
+    fn reverse_complement_in_place(&mut self) {
+        complement_string_in_place(&mut self.0, utils::dna_complement, true);
+    }
+}
+
+impl ComplementTrait for Rna {
+    fn complement(&self) -> Self {
+        Rna(self.0.chars().map(utils::rna_complement).collect())
+    }
+
+    fn reverse_complement(&self) -> Self {
+        Rna(self.0.chars().rev().map(utils::rna_complement).collect())
+    }
+}
+
+impl ComplementMutTrait for Rna {
+    fn complement_in_place(&mut self) {
+        complement_string_in_place(&mut self.0, utils::rna_complement, false);
+    }
+
+    fn reverse_complement_in_place(&mut self) {
+        complement_string_in_place(&mut self.0, utils::rna_complement, true);
+    }
 }
@@ -0,0 +1,40 @@
+//! Security unit test. This can't be an integration test (under ../tests/), because it needs
+//! private access to [`dna::RnaImpl::rna`]. Nucleotides are ASCII, so the shared test sees each
+//! `char` as one byte. We run it for several capacities.
+use crate as dna;
+
+use test_harness::api_tests_mut::wipe_on_mut::Tests;
+use utils::api_tests_mut::{WithStorageLeaked, WithStorageLeakedCallBack};
+
+struct T<const M: usize> {}
+impl<const M: usize> Tests for T<M> {
+    type Dna<'a> = dna::DnaImpl<'a, M>;
+    type Rna<'a> = dna::RnaImpl<M>;
+}
+
+fn with_storage_leaked<const M: usize>(
+    rna: &mut dna::RnaImpl<M>,
+    with_storage_leaked_call_back: WithStorageLeakedCallBack<bool>,
+) -> bool {
+    let mut bytes_iter = rna.rna.iter().map(|&c| c as u8);
+    with_storage_leaked_call_back(&mut bytes_iter)
+}
+type _TWithStorageLeaked<'a> = WithStorageLeaked<'a, dna::Rna, bool>;
+const _CHECK_WITH_STORAGE_LEAKED_FUNCTION_SIGNATURE: _TWithStorageLeaked = &with_storage_leaked;
+
+mod capacity_3 {
+    use super::*;
+    test_harness::api_tests!(wipe_on_mut: T<3>, with_storage_leaked::<3>);
+}
+mod capacity_4 {
+    use super::*;
+    test_harness::api_tests!(wipe_on_mut: T<4>, with_storage_leaked::<4>);
+}
+mod capacity_default {
+    use super::*;
+    test_harness::api_tests!(wipe_on_mut: T<{ dna::DEFAULT_MAX_NUCLEOTIDES }>, with_storage_leaked);
+}
+mod capacity_100 {
+    use super::*;
+    test_harness::api_tests!(wipe_on_mut: T<100>, with_storage_leaked::<100>);
+}
@@ -1,79 +1,264 @@
 //! no_std and heapless (bare metal/embedded-friendly)
 #![no_std]
 
-use core::fmt::{self, Debug, Formatter};
-// @TODO RnaTraitMut
-use utils::{checks, DnaTrait, OurResult, RnaTrait};
+use core::fmt::{self, Debug, Display, Formatter};
+use utils::api_tests_mut::RnaTraitMutLeakStorage;
+use utils::checks::Alphabet;
+use utils::{
+    checks, ComplementMutTrait, ComplementTrait, DnaTrait, LenFits, LenType,
+    MaterializedNucleotides, NucleotideError, NucleotidesView, OurResult, RnaToDnaTrait, RnaTrait,
+    RnaTraitMut,
+};
 
-/// This is higher than `32`, so that we make sure to implement [`Default`] ourselves. ([`Default`]
-/// can be derived for arrays only up to size `32`.)
-const MAX_NUM_RNA_NUCLEOTIDES: usize = 40;
+#[cfg(test)]
+mod api_tests_mut_wipe_on_mut;
+
+/// Capacity of [`Rna`]. This is higher than `32`, so that we make sure to implement [`Default`]
+/// ourselves. ([`Default`] can be derived for arrays only up to size `32`.)
+const DEFAULT_MAX_NUCLEOTIDES: usize = 40;
 
 // @TODO Others: Derive/impl Clone.
 
-/// DNA (DNA nucleotide sequence). `Dna` itself is `&str` slice-based. (Sufficient for our purpose.)
-/// Only `Rna` is array-based.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Dna<'a>(&'a str);
+/// DNA (DNA nucleotide sequence). Given `Dna` is `&str` slice-based. (Sufficient for our purpose.)
+/// Only `Rna`, and `Dna` transcribed back from `Rna`, are array-based, with capacity `M`.
+#[derive(Clone, Copy)]
+pub enum DnaImpl<'a, const M: usize = DEFAULT_MAX_NUCLEOTIDES, L: LenType = usize> {
+    /// Represented by given DNA nucleotides. Returned by [`DnaImpl::new`].
+    GivenNucleotides(&'a str),
+    /// DNA nucleotides materialized (reverse transcribed) from [`RnaImpl`] by
+    /// [`RnaImpl::into_dna`].
+    Materialized(MaterializedDna<M, L>),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed. Returned
+    /// by [`ComplementTrait`] methods of [`DnaImpl::GivenNucleotides`], so that they don't need to
+    /// fit in any storage.
+    View(NucleotidesView<'a>),
+}
+
+pub type Dna<'a> = DnaImpl<'a, DEFAULT_MAX_NUCLEOTIDES>;
+
+/// Payload of [`DnaImpl::Materialized`]: the first `len` characters of `dna`. Its fields are
+/// private, so that only [`RnaImpl::into_dna`] creates it. Hence `len` never exceeds `M`, and the
+/// characters are DNA nucleotides.
+#[derive(Clone, Copy)]
+pub struct MaterializedDna<const M: usize, L: LenType> {
+    dna: [char; M],
+    len: L,
+}
 
-/// RNA (RNA nucleotide sequence). Storing RNA nucleotides.
-#[derive(PartialEq, Clone, Copy)]
-pub struct Rna {
-    rna: [char; MAX_NUM_RNA_NUCLEOTIDES],
-    len: usize,
+/// RNA (RNA nucleotide sequence). Storing up to `M` RNA nucleotides.
+///
+/// We don't derive [`PartialEq`], so that we can compare [`RnaImpl`] regardless of `M`.
+///
+/// `L` is the type of the length field. Here a smaller one doesn't save any space, because `char`
+/// alignment pads the struct anyway. See [`LenType`].
+///
+/// Every constructor (of [`DnaImpl`], too) fails to compile if `L` can't hold `M`:
+///
+/// ```
+/// use ret_tok_rnd_modify_any_store_own_arr_const_overall_limit_chars as dna;
+/// use utils::{DnaTrait, RnaTrait};
+/// let _ = dna::RnaImpl::<255, u8>::new("CGAU");
+/// let _ = dna::DnaImpl::<255, u8>::new("GCTA");
+/// ```
+///
+/// ```compile_fail,E0080
+/// use ret_tok_rnd_modify_any_store_own_arr_const_overall_limit_chars as dna;
+/// use utils::RnaTrait;
+/// let _ = dna::RnaImpl::<300, u8>::new("CGAU");
+/// ```
+///
+/// ```compile_fail,E0080
+/// use ret_tok_rnd_modify_any_store_own_arr_const_overall_limit_chars as dna;
+/// use utils::DnaTrait;
+/// let _ = dna::DnaImpl::<300, u8>::new("GCTA");
+/// ```
+#[derive(Clone, Copy)]
+pub struct RnaImpl<const M: usize = DEFAULT_MAX_NUCLEOTIDES, L: LenType = usize> {
+    rna: [char; M],
+    len: L,
 }
 
-impl<'a> DnaTrait<'a, Rna> for Dna<'a> {
+pub type Rna = RnaImpl<DEFAULT_MAX_NUCLEOTIDES>;
+
+impl<'a, const M: usize, L: LenType> DnaTrait<'a, RnaImpl<M, L>> for DnaImpl<'a, M, L> {
+    /// Fail to compile if `L` can't hold `M`, like every constructor of [`RnaImpl`] does.
     fn new(dna: &'a str) -> OurResult<Self> {
+        let () = LenFits::<L, M>::ASSERT;
         checks::check_dna(dna)?;
-        Ok(Self(dna))
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        let () = LenFits::<L, M>::ASSERT;
+        checks::check_dna_with(dna, Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    fn into_rna(&self) -> RnaImpl<M, L> {
+        self.try_into_rna().expect("RNA")
+    }
+
+    /// Return [`NucleotideError::CapacityExceeded`] if `self` is longer than `M`.
+    fn try_into_rna(&self) -> OurResult<RnaImpl<M, L>> {
+        self.with_rna_chars(|rna_chars| RnaImpl::new_from_iter(rna_chars, Alphabet::Iupac))
+    }
+}
+
+// Its `Debug` honors the default derived format of a newtype-based implementation.
+utils::lazy_dna_impls!(DnaImpl<'a, M, L> [const M: usize, L: LenType]);
+
+impl<const M: usize, L: LenType> MaterializedNucleotides for MaterializedDna<M, L> {
+    fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.dna[..self.len.to_usize()].iter().copied()
     }
 
-    fn into_rna(&self) -> Rna {
-        Rna::new_from_iter(self.0.chars().map(utils::dna_to_rna)).expect("RNA")
+    fn complement_in_place(&mut self, reverse: bool) {
+        let len = self.len.to_usize();
+        complement_in_place(&mut self.dna[..len], utils::dna_complement, reverse);
     }
 }
 
-impl<'a> RnaTrait<'a> for Rna {
+impl<'a, const M: usize, L: LenType, const R: usize, RL: LenType> PartialEq<DnaImpl<'_, R, RL>>
+    for DnaImpl<'a, M, L>
+{
+    fn eq(&self, other: &DnaImpl<'_, R, RL>) -> bool {
+        self.with_chars(|self_chars| other.with_chars(|other_chars| self_chars.eq(other_chars)))
+    }
+}
+impl<'a, const M: usize, L: LenType> Eq for DnaImpl<'a, M, L> {}
+
+impl<'a, const M: usize, L: LenType> RnaTrait<'a> for RnaImpl<M, L> {
     /// Create a new [`Rna`] instance with given RNA nucleotides -[`Rna::GivenNucleotides`] variant.
     /// If `rna` is valid, return  
-    /// [`Some(Rna)`](Some<Rna>) containing the new instance. On error return [`Err`] with a 0-based
-    /// index of the first incorrect character.
+    /// [`Some(Rna)`](Some<Rna>) containing the new instance. On error return [`Err`] with a
+    /// [`NucleotideError`](utils::NucleotideError) locating the first incorrect character.
     fn new(rna: &'a str) -> OurResult<Self> {
-        Self::new_from_iter(rna.chars())
+        Self::new_from_iter(rna.chars(), Alphabet::Strict)
+    }
+
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        Self::new_from_iter(rna.chars(), Alphabet::Iupac)
     }
 }
-impl Rna {
-    fn new_from_iter(rna_iter: impl Iterator<Item = char>) -> OurResult<Self> {
-        let mut result = Rna::default();
+impl<const M: usize, L: LenType> RnaImpl<M, L> {
+    fn new_from_iter(rna_iter: impl Iterator<Item = char>, alphabet: Alphabet) -> OurResult<Self> {
+        let mut result = Self::default();
+        result.set_from_iter_impl(rna_iter, alphabet)?;
+        Ok(result)
+    }
+
+    /// Return [`NucleotideError::CapacityExceeded`] if `rna_iter` yields more than `M` characters.
+    /// (We keep counting, so that we report how many it did yield.)
+    ///
+    /// We reset any unused slots to [`char::default`], so that they don't keep previous
+    /// nucleotides. On error we leave `self` empty.
+    fn set_from_iter_impl(
+        &mut self,
+        rna_iter: impl Iterator<Item = char>,
+        alphabet: Alphabet,
+    ) -> OurResult<()> {
+        let previous_len = self.len.to_usize();
+        let mut required = 0usize;
         for c in rna_iter {
-            result.rna[result.len] = c;
-            result.len += 1;
+            if required < M {
+                self.rna[required] = c;
+            }
+            required += 1;
         }
-        checks::check_rna_chars(result.chars())?;
-        Ok(result)
+        if required > M {
+            *self = Self::default();
+            return Err(NucleotideError::CapacityExceeded {
+                capacity: M,
+                required,
+            });
+        }
+        self.len = L::from_usize(required);
+        for slot in self.rna.iter_mut().take(previous_len).skip(required) {
+            *slot = char::default();
+        }
+        if let Err(err) = checks::check_rna_chars_with(self.chars(), alphabet) {
+            *self = Self::default();
+            return Err(err);
+        }
+        Ok(())
     }
 
     fn chars(&self) -> &[char] {
-        &self.rna[..self.len]
+        &self.rna[..self.len.to_usize()]
     }
 }
 
-impl Default for Rna {
+impl<'a, const M: usize, L: LenType> RnaToDnaTrait<'a, DnaImpl<'a, M, L>> for RnaImpl<M, L> {
+    /// Create a [`DnaImpl::Materialized`] instance with the same capacity `M`. It stores the
+    /// transcribed nucleotides in an array (like `self` does).
+    fn into_dna(&self) -> DnaImpl<'a, M, L> {
+        let mut dna = [char::default(); M];
+        for (dna_nucl, &rna_nucl) in dna.iter_mut().zip(self.chars()) {
+            *dna_nucl = utils::rna_to_dna(rna_nucl);
+        }
+        DnaImpl::Materialized(MaterializedDna { dna, len: self.len })
+    }
+}
+
+impl<const M: usize, L: LenType> Default for RnaImpl<M, L> {
+    /// Fail to compile if `L` can't hold `M`.
     fn default() -> Self {
+        let () = LenFits::<L, M>::ASSERT;
         Self {
-            rna: [char::default(); MAX_NUM_RNA_NUCLEOTIDES],
-            len: 0,
+            rna: [char::default(); M],
+            len: L::default(),
         }
     }
 }
 
-// @TODO RnaTraitMut
+impl<'a, const M: usize, L: LenType> RnaTraitMut<'a> for RnaImpl<M, L> {
+    fn set_from_iter(&mut self, iter: &mut dyn Iterator<Item = char>) -> OurResult<()> {
+        self.set_from_iter_impl(iter, Alphabet::Strict)
+    }
+}
+
+impl<'a, const M: usize, L: LenType> RnaTraitMutLeakStorage<'a> for RnaImpl<M, L> {}
 
+/// Compare the used part only, so that it works across capacities.
+impl<const M: usize, L: LenType, const R: usize, RL: LenType> PartialEq<RnaImpl<R, RL>>
+    for RnaImpl<M, L>
+{
+    fn eq(&self, other: &RnaImpl<R, RL>) -> bool {
+        self.chars() == other.chars()
+    }
+}
 /// Not necessary, but valid.
-impl Eq for Rna {}
+impl<const M: usize, L: LenType> Eq for RnaImpl<M, L> {}
+
+/// Copy to a different capacity. Return [`NucleotideError::CapacityExceeded`] if `rna`'s
+/// nucleotides don't fit in `M` characters.
+impl<const M: usize, L: LenType, const R: usize, RL: LenType> TryFrom<&RnaImpl<R, RL>>
+    for RnaImpl<M, L>
+{
+    type Error = NucleotideError;
+
+    fn try_from(rna: &RnaImpl<R, RL>) -> OurResult<Self> {
+        let len = rna.len.to_usize();
+        if len > M {
+            return Err(NucleotideError::CapacityExceeded {
+                capacity: M,
+                required: len,
+            });
+        }
+        let mut result = Self::default();
+        result.rna[..len].copy_from_slice(rna.chars());
+        result.len = L::from_usize(len);
+        Ok(result)
+    }
+}
+
+impl<const M: usize, L: LenType> Display for RnaImpl<M, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        self.chars().iter().try_for_each(|&c| write!(f, "{c}"))
+    }
+}
 
-impl Debug for Rna {
+impl<const M: usize, L: LenType> Debug for RnaImpl<M, L> {
     /// Compared to [../../no_heap-slices-iterator]([../../no_heap-slices-iterator),
     /// [Self::DnaBased] variant here doesn't have `self.iter()`. So we map DNA to RNA chars here.
     /// Honoring default derived format of a newtype-based implementation, so we can re-use same tests.
@@ -83,7 +268,7 @@ impl Debug for Rna {
         {
             extern crate alloc;
             use alloc::string::String;
-            write!(f, "RNA({})", self.chars().iter().collect::<String>())
+            write!(f, "Rna(\"{}\")", self.chars().iter().collect::<String>())
         }
         // But to make this heapless-compatible, we iterate over characters instead:
         #[cfg(not(feature = "with_heap"))]
@@ -94,3 +279,46 @@ impl Debug for Rna {
         }
     }
 }
+
+/// Complement `nucleotides` in place, and reverse them if `reverse`. `complement` is either
+/// [`utils::dna_complement`] or [`utils::rna_complement`].
+fn complement_in_place(nucleotides: &mut [char], complement: fn(char) -> char, reverse: bool) {
+    for nucl in nucleotides.iter_mut() {
+        *nucl = complement(*nucl);
+    }
+    if reverse {
+        nucleotides.reverse();
+    }
+}
+
+impl<const M: usize, L: LenType> ComplementTrait for RnaImpl<M, L> {
+    fn complement(&self) -> Self {
+        let mut result = *self;
+        result.complement_in_place();
+        result
+    }
+
+    fn reverse_complement(&self) -> Self {
+        let mut result = *self;
+        result.reverse_complement_in_place();
+        result
+    }
+}
+
+impl<const M: usize, L: LenType> ComplementMutTrait for RnaImpl<M, L> {
+    fn complement_in_place(&mut self) {
+        complement_in_place(
+            &mut self.rna[..self.len.to_usize()],
+            utils::rna_complement,
+            false,
+        );
+    }
+
+    fn reverse_complement_in_place(&mut self) {
+        complement_in_place(
+            &mut self.rna[..self.len.to_usize()],
+            utils::rna_complement,
+            true,
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! Security unit test. Like [`super::api_tests_mut_wipe_on_mut`], it checks the whole storage.
+use crate as dna;
+
+use super::api_tests_mut_wipe_on_mut::with_storage_leaked;
+use test_harness::api_tests_mut::wipe_on_clone::{DnaTests, Tests};
+
+struct T {}
+impl Tests for T {
+    type Rna<'a> = dna::Rna;
+}
+
+test_harness::api_tests!(wipe_on_clone: T, with_storage_leaked);
+
+impl DnaTests for T {
+    type Dna<'a> = dna::Dna<'a>;
+    type Rna<'a> = dna::Rna;
+}
+
+test_harness::api_tests!(wipe_on_clone_dna: T);
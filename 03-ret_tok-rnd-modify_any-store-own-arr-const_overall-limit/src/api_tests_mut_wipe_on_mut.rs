@@ -1,29 +1,42 @@
-///! Security unit test. This can't be an integration test (under ../tests/), because it needs
-///! private access to [`dna::Rna::rna`].
+//! Security unit test. It checks the whole storage (see [`utils::storage::Rna::storage`]), not only
+//! the used part. We run it for several capacities: the smallest that fits `CGA`, and the default
+//! one.
 use crate as dna;
 
 use test_harness::api_tests_mut::wipe_on_mut::Tests;
 use utils::api_tests_mut::{WithStorageLeaked, WithStorageLeakedCallBack};
+use utils::storage::Storage;
 
-struct T {}
-impl Tests for T {
-    type Dna<'a> = dna::Dna<'a>;
-    type Rna<'a> = dna::Rna;
+struct T<const M: usize> {}
+impl<const M: usize> Tests for T<M> {
+    type Dna<'a> = dna::DnaImpl<'a, M>;
+    type Rna<'a> = dna::RnaImpl<M>;
 }
 
-fn with_storage_leaked<RES>(
-    rna: &dna::Rna,
+pub(super) fn with_storage_leaked<const M: usize>(
+    rna: &mut dna::RnaImpl<M>,
     with_storage_leaked_call_back: WithStorageLeakedCallBack<bool>,
 ) -> bool {
-    let bytes = &rna.rna[..];
-    let mut bytes_iter = bytes.into_iter().cloned();
+    let bytes = rna.storage().bytes();
+    let mut bytes_iter = bytes.iter().cloned();
     with_storage_leaked_call_back(&mut bytes_iter)
 }
 type _TWithStorageLeaked<'a> = WithStorageLeaked<'a, dna::Rna, bool>;
-const _CHECK_WITH_STORAGE_LEAKED_FUNCTION_SIGNATURE: _TWithStorageLeaked =
-    &with_storage_leaked::<bool>;
+const _CHECK_WITH_STORAGE_LEAKED_FUNCTION_SIGNATURE: _TWithStorageLeaked = &with_storage_leaked;
 
-#[test]
-fn all_tests() {
-    T::test_modify_string_based_rna_mutation_does_not_leak(&with_storage_leaked::<bool>);
+mod capacity_3 {
+    use super::*;
+    test_harness::api_tests!(wipe_on_mut: T<3>, with_storage_leaked::<3>);
+}
+mod capacity_4 {
+    use super::*;
+    test_harness::api_tests!(wipe_on_mut: T<4>, with_storage_leaked::<4>);
+}
+mod capacity_default {
+    use super::*;
+    test_harness::api_tests!(wipe_on_mut: T<{ dna::DEFAULT_MAX_NUCLEOTIDES }>, with_storage_leaked);
+}
+mod capacity_100 {
+    use super::*;
+    test_harness::api_tests!(wipe_on_mut: T<100>, with_storage_leaked::<100>);
 }
@@ -1,104 +1,177 @@
 //! no_std heapless (bare metal/embedded-friendly)
 #![no_std]
-// generic_associated_types are not required for the implementation itself, but only for
-// `api_tests_mut_wipe_on_clone` unit tests.
-// #[cfg_attr(test, feature = "generic_associated_types" )]
-#![cfg_attr(test, feature(generic_associated_types))]
 
-use core::fmt::{self, Debug, Formatter};
 use core::str;
-use utils::api_tests_mut::RnaTraitMutLeakStorage;
-use utils::{checks, DnaTrait, OurResult, RnaTrait, RnaTraitMut};
+use utils::checks::Alphabet;
+use utils::storage::{self, FromNucleotides, Storage};
+use utils::{
+    checks, DnaTrait, LenType, MaterializedNucleotides, NucleotidesView, OurResult, RnaToDnaTrait,
+};
 
+#[cfg(test)]
+mod api_tests_mut_wipe_on_clone;
+#[cfg(all(test, feature = "security"))]
+mod api_tests_mut_wipe_on_drop;
 #[cfg(test)]
 mod api_tests_mut_wipe_on_mut;
 
-const MAX_NUM_RNA_NUCLEOTIDES: usize = 40;
+/// Capacity of [`Rna`] (and of [`Dna`] materialized from it), in bytes.
+const DEFAULT_MAX_NUCLEOTIDES: usize = 40;
+
+/// DNA (DNA nucleotide sequence). `M` is the capacity of [`DnaImpl::Materialized`] only.
+///
+/// We don't derive [`PartialEq`], so that we can compare [`DnaImpl`] regardless of `M` (and
+/// regardless of the variant).
+///
+/// With `security` feature it isn't [`Copy`]. See [`MaterializedDna`].
+#[cfg_attr(not(feature = "security"), derive(Copy))]
+#[derive(Clone)]
+pub enum DnaImpl<'a, const M: usize = DEFAULT_MAX_NUCLEOTIDES, L: LenType = usize> {
+    /// Represented by given DNA nucleotides. Returned by [`DnaImpl::new`].
+    GivenNucleotides(&'a str),
+    /// DNA nucleotides materialized (reverse transcribed) from [`RnaImpl`] by
+    /// [`RnaImpl::into_dna`].
+    Materialized(MaterializedDna<M, L>),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed. Returned
+    /// by [`utils::ComplementTrait`] methods of [`DnaImpl::GivenNucleotides`], so that they don't
+    /// need to fit in any storage.
+    View(NucleotidesView<'a>),
+}
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Dna<'a>(&'a str);
+pub type Dna<'a> = DnaImpl<'a, DEFAULT_MAX_NUCLEOTIDES>;
+
+/// Payload of [`DnaImpl::Materialized`]: the first `len` bytes of `dna`. The fields are private,
+/// so that only [`RnaImpl::into_dna`] creates it - with `len` within `M`, and ASCII nucleotides
+/// only. (Public fields would let safe code build a value that panics on use.)
+///
+/// Like [`ArrayStorage`], with `security` feature it isn't [`Copy`], it wipes itself on drop, and
+/// [`DnaImpl`]'s [`Debug`](core::fmt::Debug) redacts it.
+#[cfg_attr(not(feature = "security"), derive(Copy))]
+#[derive(Clone)]
+pub struct MaterializedDna<const M: usize, L: LenType> {
+    dna: [u8; M],
+    len: L,
+}
 
-#[derive(Clone, Copy)]
-pub struct Rna {
-    // New to Rust? u8 type is an unsigned 8 bit integer, also used to represent a byte.
-    rna: [u8; MAX_NUM_RNA_NUCLEOTIDES],
-    len: usize,
+#[cfg(feature = "security")]
+impl<const M: usize, L: LenType> Drop for MaterializedDna<M, L> {
+    fn drop(&mut self) {
+        utils::wipe(&mut self.dna);
+    }
 }
 
-impl<'a> DnaTrait<'a, Rna> for Dna<'a> {
-    fn new(dna: &'a str) -> OurResult<Self> {
-        checks::check_dna(dna)?;
-        Ok(Self(dna))
+/// [`RnaImpl`]'s storage: `M` bytes. With `security` feature it isn't [`Copy`], so that it doesn't
+/// get copied implicitly. It wipes itself on drop, and [`Rna`]'s [`Debug`] doesn't show the
+/// nucleotides. (Moves can still leave copies behind in memory that we don't wipe.)
+///
+/// Transactional: [`utils::RnaTraitMut::set_from_iter`] stages the new nucleotides in another
+/// array. On error [`Rna`] keeps its previous nucleotides.
+#[cfg_attr(not(feature = "security"), derive(Copy))]
+#[derive(Clone)]
+pub struct ArrayStorage<const M: usize>([u8; M]);
+
+impl<const M: usize> Storage for ArrayStorage<M> {
+    const CAPACITY: Option<usize> = Some(M);
+    const REDACT_DEBUG: bool = cfg!(feature = "security");
+    const CONSTANT_TIME_EQ: bool = cfg!(feature = "constant_time_eq");
+
+    fn bytes(&self) -> &[u8] {
+        &self.0
     }
 
-    fn into_rna(&self) -> Rna {
-        Rna::new_from_iter(self.0.chars().map(utils::dna_to_rna)).expect("RNA")
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+
+    fn staging(&self) -> Option<Self> {
+        Some(Self([0; M]))
     }
 }
 
-impl<'a> RnaTrait<'a> for Rna {
-    /// Create a new [`Rna`] instance with given RNA nucleotides. If `rna` is valid, return  
-    /// [`Some(Rna)`](Some<Rna>) containing the new instance. On error return [`Err`] with a 0-based
-    /// index of the first incorrect character.
-    fn new(rna: &'a str) -> OurResult<Self> {
-        Self::new_from_iter(rna.chars())
+impl<'a, const M: usize> FromNucleotides<'a> for ArrayStorage<M> {
+    fn from_nucleotides(nucleotides: &'a str) -> OurResult<Self> {
+        <[u8; M]>::from_nucleotides(nucleotides).map(Self)
     }
 }
 
-impl Rna {
-    /// We purge any extra leftover data.
-    fn set_from_iter_impl(&mut self, rna_iter: impl Iterator<Item = char>) -> OurResult<()> {
-        let previous_len = self.len;
+#[cfg(feature = "security")]
+impl<const M: usize> Drop for ArrayStorage<M> {
+    fn drop(&mut self) {
+        utils::wipe(&mut self.0);
+    }
+}
 
-        self.len = utils::char_iter_to_bytes(&mut self.rna, rna_iter);
-        for i in self.len..previous_len {
-            self.rna[i] = u8::default();
-        }
+/// RNA stored in an array of `M` bytes. See [`ArrayStorage`].
+///
+/// `L` is the type of the length field: `u8` suffices for the default capacity, saving 7 bytes
+/// over `usize` on a 64-bit target. See [`LenType`].
+pub type RnaImpl<const M: usize = DEFAULT_MAX_NUCLEOTIDES, L = usize> =
+    storage::Rna<ArrayStorage<M>, L>;
 
-        checks::check_rna_str(self.as_str())?;
-        Ok(())
+/// RNA with the default capacity (40 bytes).
+pub type Rna = RnaImpl<DEFAULT_MAX_NUCLEOTIDES>;
+
+impl<'a, const M: usize, L: LenType> DnaTrait<'a, RnaImpl<M, L>> for DnaImpl<'a, M, L> {
+    fn new(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna(dna)?;
+        Ok(Self::GivenNucleotides(dna))
     }
-    fn new_from_iter(rna_iter: impl Iterator<Item = char>) -> OurResult<Self> {
-        let mut result = Rna::default();
-        result.set_from_iter_impl(rna_iter)?;
-        Ok(result)
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(dna))
     }
 
-    fn as_str(&self) -> &str {
-        str::from_utf8(&self.rna[..self.len]).expect("UTF-8 encoded string of RNA nucleotides")
+    fn into_rna(&self) -> RnaImpl<M, L> {
+        self.try_into_rna().expect("RNA")
     }
-}
 
-impl<'a> RnaTraitMut<'a> for Rna {
-    fn set_from_iter(&mut self, iter: &mut dyn Iterator<Item = char>) -> OurResult<()> {
-        // This wouldn't compile without the extra .map() or some other chaining.
-        #[allow(clippy::map_identity)]
-        self.set_from_iter_impl(iter.map(core::convert::identity))
+    /// Return [`utils::NucleotideError::CapacityExceeded`] if the transcribed nucleotides don't
+    /// fit in `M` bytes.
+    fn try_into_rna(&self) -> OurResult<RnaImpl<M, L>> {
+        self.with_rna_chars(|rna_chars| RnaImpl::new_from_iter_with(rna_chars, Alphabet::Iupac))
     }
 }
 
-impl<'a> RnaTraitMutLeakStorage<'a> for Rna {}
-
-impl Default for Rna {
-    fn default() -> Self {
-        Self {
-            rna: [0; MAX_NUM_RNA_NUCLEOTIDES],
-            len: 0,
+impl<'a, const M: usize, L: LenType> RnaToDnaTrait<'a, DnaImpl<'a, M, L>> for RnaImpl<M, L> {
+    /// Create a [`DnaImpl::Materialized`] instance with the same capacity `M`. It stores the
+    /// transcribed nucleotides in an array (like `self` does).
+    fn into_dna(&self) -> DnaImpl<'a, M, L> {
+        let mut dna = [0; M];
+        let rna = self.as_str().as_bytes();
+        for (dna_nucl, &rna_nucl) in dna.iter_mut().zip(rna) {
+            // RNA nucleotides are ASCII, hence one byte each.
+            *dna_nucl = utils::rna_to_dna(rna_nucl as char) as u8;
         }
+        DnaImpl::Materialized(MaterializedDna {
+            dna,
+            len: L::from_usize(rna.len()),
+        })
     }
 }
 
-/// We could also `#[derive(PartialEq)]`. It could be even more efficient than our implementation,
-/// because [`as_str`] validates UTF-8 first.
-impl PartialEq for Rna {
-    fn eq(&self, other: &Self) -> bool {
-        self.as_str() == other.as_str()
+utils::lazy_dna_impls!(DnaImpl<'a, M, L> [const M: usize, L: LenType]);
+
+impl<const M: usize, L: LenType> MaterializedNucleotides for MaterializedDna<M, L> {
+    const REDACT_DEBUG: bool = cfg!(feature = "security");
+
+    fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        str::from_utf8(&self.dna[..self.len.to_usize()])
+            .expect("UTF-8 encoded string of DNA nucleotides")
+            .chars()
+    }
+
+    fn complement_in_place(&mut self, reverse: bool) {
+        let len = self.len.to_usize();
+        utils::complement_bytes(&mut self.dna[..len], utils::dna_complement, reverse);
     }
 }
-impl Eq for Rna {}
 
-impl Debug for Rna {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "Rna(\"{}\")", self.as_str())
+impl<'a, const M: usize, L: LenType, const R: usize, RL: LenType> PartialEq<DnaImpl<'_, R, RL>>
+    for DnaImpl<'a, M, L>
+{
+    fn eq(&self, other: &DnaImpl<'_, R, RL>) -> bool {
+        self.with_chars(|self_chars| other.with_chars(|other_chars| self_chars.eq(other_chars)))
     }
 }
+impl<'a, const M: usize, L: LenType> Eq for DnaImpl<'a, M, L> {}
@@ -0,0 +1,12 @@
+#![no_std]
+
+use ret_tok_rnd_modify_any_store_own_arr_const_overall_limit as dna;
+use test_harness::api_tests_mut::complement_in_place::Tests;
+
+struct T {}
+impl Tests for T {
+    type Dna<'a> = dna::Dna<'a>;
+    type Rna<'a> = dna::Rna;
+}
+
+test_harness::api_tests!(complement_in_place: T);
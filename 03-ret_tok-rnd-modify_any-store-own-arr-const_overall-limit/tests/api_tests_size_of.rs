@@ -0,0 +1,13 @@
+#![no_std]
+
+use ret_tok_rnd_modify_any_store_own_arr_const_overall_limit as dna;
+
+use utils::LenType;
+
+struct T {}
+impl test_harness::api_tests_size_of::Tests for T {
+    type Nucleotide = u8;
+    type Rna<const M: usize, L: LenType> = dna::RnaImpl<M, L>;
+}
+
+test_harness::api_tests!(size_of: T);
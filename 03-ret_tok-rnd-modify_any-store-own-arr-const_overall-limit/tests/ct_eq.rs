@@ -0,0 +1,29 @@
+#![no_std]
+
+use ret_tok_rnd_modify_any_store_own_arr_const_overall_limit as dna;
+
+use test_harness::api_tests_ct_eq::Tests;
+
+struct T {}
+impl Tests for T {
+    type Rna<'a> = dna::Rna;
+    type SmallRna<'a> = dna::RnaImpl<4>;
+
+    fn ct_eq(left: &dna::Rna, right: &dna::Rna) -> bool {
+        left.ct_eq(right)
+    }
+
+    fn small_ct_eq(left: &dna::RnaImpl<4>, right: &dna::Rna) -> bool {
+        left.ct_eq(right)
+    }
+
+    fn ct_eq_small(left: &dna::Rna, right: &dna::RnaImpl<4>) -> bool {
+        left.ct_eq(right)
+    }
+
+    fn ct_eq_probed(left: &dna::Rna, right: &dna::Rna, probe: &dyn Fn()) -> bool {
+        left.ct_eq_probed(right, probe)
+    }
+}
+
+test_harness::api_tests!(ct_eq: T);
@@ -0,0 +1,37 @@
+#![no_std]
+
+use ret_tok_rnd_modify_any_store_own_arr_const_overall_limit as dna;
+use utils::fastq::{FastqReader, PhredEncoding};
+use utils::{NucleotideError, RnaTrait};
+
+const INPUT: &[u8] = b"@read1\nACGTTGCA\n+\n!!I5?II#\n@read2\nACGTACGTACGTACGTACGTACGTACGTACGTACGTACGTA\n+\nIIIIIIIIIIIIIIIIIIIIIIIIIIIIIIIIIIIIIIIII\n";
+
+#[test]
+fn test_transcribe_trimmed() {
+    let record = FastqReader::new(INPUT, PhredEncoding::Phred33)
+        .next()
+        .unwrap()
+        .unwrap();
+    let rna: dna::Rna = record.trim(20).transcribe::<dna::Dna, _>().unwrap();
+    assert_eq!(rna, dna::Rna::new("CAACG").unwrap());
+}
+
+/// [`dna::Rna`] holds up to 40 nucleotides.
+#[test]
+fn test_transcribe_too_long() {
+    let record = FastqReader::new(INPUT, PhredEncoding::Phred33)
+        .nth(1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        record.transcribe::<dna::Dna, dna::Rna>(),
+        Err(NucleotideError::CapacityExceeded {
+            capacity: 40,
+            required: 41
+        })
+    );
+    assert!(record
+        .slice(0, 40)
+        .transcribe::<dna::Dna, dna::Rna>()
+        .is_ok());
+}
@@ -0,0 +1,37 @@
+//! Security unit test (with `security` feature only). Like
+//! [`super::api_tests_mut_wipe_on_mut`], it needs private access to [`dna::RnaImpl::rna`].
+use crate as dna;
+
+use super::api_tests_mut_wipe_on_mut::with_storage_leaked;
+use test_harness::api_tests_mut::wipe_on_drop::{DnaTests, Tests};
+use test_harness::api_tests_mut::WithDnaStorageLeaked;
+use utils::api_tests_mut::WithStorageLeakedCallBack;
+
+struct T {}
+impl Tests for T {
+    type Rna<'a> = dna::Rna;
+}
+
+test_harness::api_tests!(wipe_on_drop: T, with_storage_leaked);
+
+impl DnaTests for T {
+    type Dna<'a> = dna::Dna<'a>;
+    type Rna<'a> = dna::Rna;
+}
+
+/// Like [`with_storage_leaked`], for [`dna::DnaImpl::Materialized`] (and its private `dna` array).
+fn with_dna_storage_leaked(
+    dna: &mut dna::Dna,
+    with_storage_leaked_call_back: WithStorageLeakedCallBack<bool>,
+) -> bool {
+    let dna::DnaImpl::Materialized(materialized) = dna else {
+        panic!("materialized DNA expected");
+    };
+    let mut bytes_iter = materialized.dna.iter().cloned();
+    with_storage_leaked_call_back(&mut bytes_iter)
+}
+type _TWithDnaStorageLeaked<'a> = WithDnaStorageLeaked<'a, dna::Dna<'a>, bool>;
+const _CHECK_WITH_DNA_STORAGE_LEAKED_FUNCTION_SIGNATURE: _TWithDnaStorageLeaked =
+    &with_dna_storage_leaked;
+
+test_harness::api_tests!(wipe_on_drop_dna: T, with_dna_storage_leaked);
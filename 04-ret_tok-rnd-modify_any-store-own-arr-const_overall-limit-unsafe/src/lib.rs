@@ -1,106 +1,365 @@
 //! no_std heapless (bare metal/embedded-friendly)
 #![no_std]
-// Generic_associated_types are not required for the implementation itself, but only for
-// `api_tests_mut_wipe_on_clone` unit tests.
-// #![cfg_attr(test, feature = "generic_associated_types" )]
-#![cfg_attr(test, feature(generic_associated_types))]
 
-use core::fmt::{self, Debug, Formatter};
+use core::fmt::{self, Debug, Display, Formatter};
 use core::{slice, str};
 use utils::api_tests_mut::RnaTraitMutLeakStorage;
-use utils::{checks, DnaTrait, OurResult, RnaTrait, RnaTraitMut};
+use utils::checks::Alphabet;
+use utils::{
+    checks, ComplementMutTrait, ComplementTrait, DnaTrait, LenFits, LenType,
+    MaterializedNucleotides, NucleotideError, NucleotidesView, OurResult, RnaToDnaTrait, RnaTrait,
+    RnaTraitMut,
+};
 
+#[cfg(test)]
+mod api_tests_mut_wipe_on_clone;
+#[cfg(all(test, feature = "security"))]
+mod api_tests_mut_wipe_on_drop;
 #[cfg(test)]
 mod api_tests_mut_wipe_on_mut;
 
-const MAX_NUM_RNA_NUCLEOTIDES: usize = 40;
+/// Capacity of [`Rna`] (and of [`Dna`] materialized from it), in bytes.
+const DEFAULT_MAX_NUCLEOTIDES: usize = 40;
+
+/// DNA (DNA nucleotide sequence). `M` is the capacity of [`DnaImpl::Materialized`] only.
+///
+/// We don't derive [`PartialEq`], so that we can compare [`DnaImpl`] regardless of `M` (and
+/// regardless of the variant).
+///
+/// With `security` feature it isn't [`Copy`]. See [`MaterializedDna`].
+#[cfg_attr(not(feature = "security"), derive(Copy))]
+#[derive(Clone)]
+pub enum DnaImpl<'a, const M: usize = DEFAULT_MAX_NUCLEOTIDES, L: LenType = usize> {
+    /// Represented by given DNA nucleotides. Returned by [`DnaImpl::new`].
+    GivenNucleotides(&'a str),
+    /// DNA nucleotides materialized (reverse transcribed) from [`RnaImpl`] by
+    /// [`RnaImpl::into_dna`].
+    Materialized(MaterializedDna<M, L>),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed. Returned
+    /// by [`ComplementTrait`] methods of [`DnaImpl::GivenNucleotides`], so that they don't need to
+    /// fit in any storage.
+    View(NucleotidesView<'a>),
+}
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Dna<'a>(&'a str);
+pub type Dna<'a> = DnaImpl<'a, DEFAULT_MAX_NUCLEOTIDES>;
 
-#[derive(Clone, Copy)]
-pub struct Rna {
-    rna: [u8; MAX_NUM_RNA_NUCLEOTIDES],
-    len: usize,
+/// Payload of [`DnaImpl::Materialized`]: the first `len` bytes of `dna`. The fields are private,
+/// so that only [`RnaImpl::into_dna`] creates it. `unsafe` code in [`DnaImpl`] relies on that:
+/// `len` is within `M`, and the bytes are ASCII (hence UTF-8).
+///
+/// With `security` feature it's handled like [`RnaImpl`]: not [`Copy`], wiped on drop, and redacted
+/// in [`DnaImpl`]'s [`Debug`].
+#[cfg_attr(not(feature = "security"), derive(Copy))]
+#[derive(Clone)]
+pub struct MaterializedDna<const M: usize, L: LenType> {
+    dna: [u8; M],
+    len: L,
 }
 
-impl<'a> DnaTrait<'a, Rna> for Dna<'a> {
+#[cfg(feature = "security")]
+impl<const M: usize, L: LenType> Drop for MaterializedDna<M, L> {
+    fn drop(&mut self) {
+        utils::wipe(&mut self.dna);
+    }
+}
+
+/// With `security` feature this isn't [`Copy`]. Then it wipes its storage on drop, and [`Debug`]
+/// redacts the nucleotides. The same as in
+/// [03-ret_tok-rnd-modify_any-store-own-arr-const_overall-limit](../../03-ret_tok-rnd-modify_any-store-own-arr-const_overall-limit/src/lib.rs),
+/// and so is the length type `L`.
+///
+/// Every constructor (of [`DnaImpl`], too) fails to compile if `L` can't hold `M`:
+///
+/// ```
+/// use ret_tok_rnd_modify_any_store_own_arr_const_overall_limit_unsafe as dna;
+/// use utils::{DnaTrait, RnaTrait};
+/// let _ = dna::RnaImpl::<255, u8>::new("CGAU");
+/// let _ = dna::DnaImpl::<255, u8>::new("GCTA");
+/// ```
+///
+/// ```compile_fail,E0080
+/// use ret_tok_rnd_modify_any_store_own_arr_const_overall_limit_unsafe as dna;
+/// use utils::RnaTrait;
+/// let _ = dna::RnaImpl::<300, u8>::new("CGAU");
+/// ```
+///
+/// ```compile_fail,E0080
+/// use ret_tok_rnd_modify_any_store_own_arr_const_overall_limit_unsafe as dna;
+/// use utils::DnaTrait;
+/// let _ = dna::DnaImpl::<300, u8>::new("GCTA");
+/// ```
+#[cfg_attr(not(feature = "security"), derive(Copy))]
+#[derive(Clone)]
+pub struct RnaImpl<const M: usize = DEFAULT_MAX_NUCLEOTIDES, L: LenType = usize> {
+    rna: [u8; M],
+    len: L,
+}
+
+/// RNA with the default capacity (40 bytes).
+pub type Rna = RnaImpl<DEFAULT_MAX_NUCLEOTIDES>;
+
+impl<'a, const M: usize, L: LenType> DnaTrait<'a, RnaImpl<M, L>> for DnaImpl<'a, M, L> {
+    /// Fail to compile if `L` can't hold `M`, like every constructor of [`RnaImpl`] does.
     fn new(dna: &'a str) -> OurResult<Self> {
+        let () = LenFits::<L, M>::ASSERT;
         checks::check_dna(dna)?;
-        Ok(Self(dna))
+        Ok(Self::GivenNucleotides(dna))
     }
 
-    fn into_rna(&self) -> Rna {
-        Rna::new_from_iter(self.0.chars().map(utils::dna_to_rna)).expect("RNA")
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        let () = LenFits::<L, M>::ASSERT;
+        checks::check_dna_with(dna, Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    fn into_rna(&self) -> RnaImpl<M, L> {
+        self.try_into_rna().expect("RNA")
+    }
+
+    /// Return [`utils::NucleotideError::CapacityExceeded`] if the transcribed nucleotides don't
+    /// fit in `M` bytes.
+    fn try_into_rna(&self) -> OurResult<RnaImpl<M, L>> {
+        self.with_rna_chars(|rna_chars| RnaImpl::new_from_iter(rna_chars, Alphabet::Iupac))
     }
 }
 
-impl<'a> RnaTrait<'a> for Rna {
+utils::lazy_dna_impls!(DnaImpl<'a, M, L> [const M: usize, L: LenType]);
+
+impl<const M: usize, L: LenType> MaterializedNucleotides for MaterializedDna<M, L> {
+    const REDACT_DEBUG: bool = cfg!(feature = "security");
+
+    fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        // SAFETY: Only `RnaImpl::into_dna` creates `MaterializedDna`, so `len` is within `M` and
+        // the bytes are ASCII.
+        unsafe {
+            let u8_slice = slice::from_raw_parts(&self.dna as *const u8, self.len.to_usize());
+            str::from_utf8_unchecked(u8_slice)
+        }
+        .chars()
+    }
+
+    fn complement_in_place(&mut self, reverse: bool) {
+        let len = self.len.to_usize();
+        utils::complement_bytes(&mut self.dna[..len], utils::dna_complement, reverse);
+    }
+}
+
+impl<'a, const M: usize, L: LenType> RnaTrait<'a> for RnaImpl<M, L> {
     /// Create a new [`Rna`] instance with given RNA nucleotides. If `rna` is valid, return  
-    /// [`Some(Rna)`](Some<Rna>) containing the new instance. On error return [`Err`] with a 0-based
-    /// index of the first incorrect character.
+    /// [`Some(Rna)`](Some<Rna>) containing the new instance. On error return [`Err`] with a
+    /// [`NucleotideError`](utils::NucleotideError) locating the first incorrect character.
     fn new(rna: &'a str) -> OurResult<Self> {
-        Self::new_from_iter(rna.chars())
+        Self::new_from_iter(rna.chars(), Alphabet::Strict)
+    }
+
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        Self::new_from_iter(rna.chars(), Alphabet::Iupac)
     }
 }
 
-impl Rna {
-    /// We purge any extra leftover data.
-    fn set_from_iter_impl(&mut self, rna_iter: impl Iterator<Item = char>) -> OurResult<()> {
-        let previous_len = self.len;
+impl<const M: usize, L: LenType> RnaImpl<M, L> {
+    /// Transactional: we transcribe `rna_iter` into a staging buffer, validate it, and only then
+    /// commit it to `self`. Hence `self` never holds invalid bytes - which [`Rna::as_str`] relies
+    /// on. On error `self` keeps its previous nucleotides.
+    fn set_from_iter_impl(
+        &mut self,
+        rna_iter: impl Iterator<Item = char>,
+        alphabet: Alphabet,
+    ) -> OurResult<()> {
+        let mut staged = Self::default();
+        let result = utils::try_char_iter_to_bytes(&mut staged.rna, rna_iter).and_then(|len| {
+            staged.len = L::from_usize(len);
+            // Here we must not use staged.as_str() yet, The following call to str::from_utf8()
+            // verifies that the bytes are a valid UTF-8 slice. Only then as_str() is safe.
+            let slice = str::from_utf8(&staged.rna[..len])
+                .expect("UTF-8 encoded string of RNA nucleotides");
+            checks::check_rna_str_with(slice, alphabet)
+        });
+        if result.is_ok() {
+            // Unused bytes of `staged` are zero. So this purges our leftover data, too.
+            self.rna = staged.rna;
+            self.len = staged.len;
+        }
+        // Wipe the staging copy. Volatile writes can't be removed as dead stores.
+        utils::wipe(&mut staged.rna);
+        result
+    }
+    fn new_from_iter(rna_iter: impl Iterator<Item = char>, alphabet: Alphabet) -> OurResult<Self> {
+        let mut result = Self::default();
+        result.set_from_iter_impl(rna_iter, alphabet)?;
+        Ok(result)
+    }
 
-        self.len = utils::char_iter_to_bytes(&mut self.rna, rna_iter);
-        for i in self.len..previous_len {
-            self.rna[i] = u8::default();
+    /// A copy of `self`, whether [`Rna`] is [`Copy`] or not.
+    fn duplicate(&self) -> Self {
+        Self {
+            rna: self.rna,
+            len: self.len,
         }
+    }
 
-        // Here we must not use self.as_str() yet, The following call to str::from_utf8() verifies
-        // that the bytes are a valid UTF-8 slice. Only then self.as_str() is safe.
-        let slice =
-            str::from_utf8(&self.rna[..self.len]).expect("UTF-8 encoded string of RNA nucleotides");
-        checks::check_rna_str(slice)?;
-        Ok(())
+    /// Constant-time equality. Compare the whole storage of both (up to the larger capacity), not
+    /// only the used part, and don't stop at the first difference. Hence the time depends on `M`
+    /// and `R` only, not on the nucleotides (or on where they differ). Unused bytes are always
+    /// zero, so they don't affect the result. With `constant_time_eq` feature [`PartialEq`] uses
+    /// this.
+    pub fn ct_eq<const R: usize, RL: LenType>(&self, other: &RnaImpl<R, RL>) -> bool {
+        self.ct_eq_probed(other, || ())
     }
-    fn new_from_iter(rna_iter: impl Iterator<Item = char>) -> OurResult<Self> {
-        let mut result = Rna::default();
-        result.set_from_iter_impl(rna_iter)?;
-        Ok(result)
+
+    /// [`RnaImpl::ct_eq`], calling `probe` once per byte it reads and once when it compares the
+    /// lengths. For tests only (see `test_harness::api_tests_ct_eq`).
+    #[doc(hidden)]
+    pub fn ct_eq_probed<const R: usize, RL: LenType>(
+        &self,
+        other: &RnaImpl<R, RL>,
+        probe: impl Fn(),
+    ) -> bool {
+        let probe = &probe;
+        let bytes_equal = utils::ct_eq(
+            self.rna.iter().copied().inspect(|_| probe()),
+            other.rna.iter().copied().inspect(|_| probe()),
+        );
+        // Not `&&`, so that a mismatch in the bytes doesn't skip this.
+        bytes_equal & {
+            probe();
+            self.len.to_usize() == other.len.to_usize()
+        }
     }
 
     fn as_str(&self) -> &str {
         unsafe {
-            let u8_slice = slice::from_raw_parts(&self.rna as *const u8, self.len);
+            let u8_slice = slice::from_raw_parts(&self.rna as *const u8, self.len.to_usize());
             str::from_utf8_unchecked(u8_slice)
         }
     }
 }
 
-impl<'a> RnaTraitMut<'a> for Rna {
+impl<'a, const M: usize, L: LenType> RnaTraitMut<'a> for RnaImpl<M, L> {
     fn set_from_iter(&mut self, iter: &mut dyn Iterator<Item = char>) -> OurResult<()> {
-        self.set_from_iter_impl(iter)
+        self.set_from_iter_impl(iter, Alphabet::Strict)
     }
 }
 
-impl<'a> RnaTraitMutLeakStorage<'a> for Rna {}
+impl<'a, const M: usize, L: LenType> RnaTraitMutLeakStorage<'a> for RnaImpl<M, L> {}
 
-impl Default for Rna {
+impl<'a, const M: usize, L: LenType> RnaToDnaTrait<'a, DnaImpl<'a, M, L>> for RnaImpl<M, L> {
+    /// Create a [`DnaImpl::Materialized`] instance with the same capacity `M`. It stores the
+    /// transcribed nucleotides in an array (like `self` does).
+    fn into_dna(&self) -> DnaImpl<'a, M, L> {
+        let mut dna = [0; M];
+        for (dna_nucl, &rna_nucl) in dna.iter_mut().zip(&self.rna[..self.len.to_usize()]) {
+            // RNA nucleotides are ASCII, hence one byte each.
+            *dna_nucl = utils::rna_to_dna(rna_nucl as char) as u8;
+        }
+        DnaImpl::Materialized(MaterializedDna { dna, len: self.len })
+    }
+}
+
+impl<const M: usize, L: LenType> Default for RnaImpl<M, L> {
+    /// Fail to compile if `L` can't hold `M`.
     fn default() -> Self {
+        let () = LenFits::<L, M>::ASSERT;
         Self {
-            rna: [0; MAX_NUM_RNA_NUCLEOTIDES],
-            len: 0,
+            rna: [0; M],
+            len: L::default(),
         }
     }
 }
 
-impl PartialEq for Rna {
-    fn eq(&self, other: &Self) -> bool {
+/// Copy to a different capacity. Return [`NucleotideError::CapacityExceeded`] if `rna`'s
+/// nucleotides don't fit in `M` bytes.
+impl<const M: usize, L: LenType, const R: usize, RL: LenType> TryFrom<&RnaImpl<R, RL>>
+    for RnaImpl<M, L>
+{
+    type Error = NucleotideError;
+
+    fn try_from(rna: &RnaImpl<R, RL>) -> OurResult<Self> {
+        let len = rna.len.to_usize();
+        if len > M {
+            return Err(NucleotideError::CapacityExceeded {
+                capacity: M,
+                required: len,
+            });
+        }
+        let mut result = Self::default();
+        result.rna[..len].copy_from_slice(&rna.rna[..len]);
+        result.len = L::from_usize(len);
+        Ok(result)
+    }
+}
+
+impl<const M: usize, L: LenType, const R: usize, RL: LenType> PartialEq<RnaImpl<R, RL>>
+    for RnaImpl<M, L>
+{
+    #[cfg(not(feature = "constant_time_eq"))]
+    fn eq(&self, other: &RnaImpl<R, RL>) -> bool {
         self.as_str() == other.as_str()
     }
+
+    #[cfg(feature = "constant_time_eq")]
+    fn eq(&self, other: &RnaImpl<R, RL>) -> bool {
+        self.ct_eq(other)
+    }
+}
+impl<const M: usize, L: LenType> Eq for RnaImpl<M, L> {}
+
+impl<'a, const M: usize, L: LenType, const R: usize, RL: LenType> PartialEq<DnaImpl<'_, R, RL>>
+    for DnaImpl<'a, M, L>
+{
+    fn eq(&self, other: &DnaImpl<'_, R, RL>) -> bool {
+        self.with_chars(|self_chars| other.with_chars(|other_chars| self_chars.eq(other_chars)))
+    }
+}
+impl<'a, const M: usize, L: LenType> Eq for DnaImpl<'a, M, L> {}
+
+impl<const M: usize, L: LenType> Display for RnaImpl<M, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.as_str())
+    }
 }
-impl Eq for Rna {}
 
-impl Debug for Rna {
+impl<const M: usize, L: LenType> Debug for RnaImpl<M, L> {
+    #[cfg(not(feature = "security"))]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "Rna(\"{}\")", self.as_str())
+        write!(f, "Rna(\"{self}\")")
+    }
+
+    #[cfg(feature = "security")]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Rna(<redacted>)")
+    }
+}
+
+#[cfg(feature = "security")]
+impl<const M: usize, L: LenType> Drop for RnaImpl<M, L> {
+    fn drop(&mut self) {
+        utils::wipe(&mut self.rna);
+    }
+}
+
+impl<const M: usize, L: LenType> ComplementTrait for RnaImpl<M, L> {
+    fn complement(&self) -> Self {
+        let mut result = self.duplicate();
+        result.complement_in_place();
+        result
+    }
+
+    fn reverse_complement(&self) -> Self {
+        let mut result = self.duplicate();
+        result.reverse_complement_in_place();
+        result
+    }
+}
+
+impl<const M: usize, L: LenType> ComplementMutTrait for RnaImpl<M, L> {
+    fn complement_in_place(&mut self) {
+        let len = self.len.to_usize();
+        utils::complement_bytes(&mut self.rna[..len], utils::rna_complement, false);
+    }
+
+    fn reverse_complement_in_place(&mut self) {
+        let len = self.len.to_usize();
+        utils::complement_bytes(&mut self.rna[..len], utils::rna_complement, true);
     }
 }
@@ -0,0 +1,29 @@
+#![no_std]
+
+#[allow(clippy::unsafe_removed_from_name)]
+use ret_tok_rnd_modify_any_store_own_arr_const_overall_limit_unsafe as dna;
+
+use test_harness::api_tests_mut::Tests;
+use utils::{NucleotideError, RnaTrait, RnaTraitMut};
+
+struct T {}
+impl Tests for T {
+    type Dna<'a> = dna::Dna<'a>;
+    type Rna<'a> = dna::Rna;
+}
+
+test_harness::api_tests!(mutation: T);
+
+/// Too many nucleotides leave the previous ones intact, too.
+#[test]
+fn test_capacity_exceeded_keeps_previous_value() {
+    let mut rna = dna::Rna::new("CGAU").unwrap();
+    assert_eq!(
+        rna.set_from_iter(&mut core::iter::repeat_n('U', 41)),
+        Err(NucleotideError::CapacityExceeded {
+            capacity: 40,
+            required: 41
+        })
+    );
+    assert_eq!(rna, dna::Rna::new("CGAU").unwrap());
+}
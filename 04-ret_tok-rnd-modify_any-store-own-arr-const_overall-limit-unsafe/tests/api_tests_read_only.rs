@@ -1,17 +1,51 @@
 #![no_std]
-#![feature(generic_associated_types)]
 
+#[allow(clippy::unsafe_removed_from_name)]
 use ret_tok_rnd_modify_any_store_own_arr_const_overall_limit_unsafe as dna;
 
+use core::marker::PhantomData;
+use test_harness::api_tests_cross_capacity::Tests as CrossCapacityTests;
 use test_harness::api_tests_read_only::Tests;
+use test_harness::differential::Tests as DifferentialTests;
+use utils::LenType;
 
-struct T {}
-impl Tests for T {
+/// The default capacity, or capacity `M` with length type `L`.
+struct T<const M: usize = 40, L = usize>(PhantomData<L>);
+impl<const M: usize, L: LenType> Tests for T<M, L> {
+    type Dna<'a> = dna::DnaImpl<'a, M, L>;
+    type Rna<'a> = dna::RnaImpl<M, L>;
+    const REDACTED_DEBUG: bool = cfg!(feature = "security");
+}
+
+impl<const M: usize, L: LenType> DifferentialTests for T<M, L> {
+    const MAX_LEN: usize = M;
+}
+
+impl CrossCapacityTests for T {
     type Dna<'a> = dna::Dna<'a>;
     type Rna<'a> = dna::Rna;
+    type SmallDna<'a> = dna::DnaImpl<'a, 4>;
+    type SmallRna<'a> = dna::RnaImpl<4>;
+}
+
+test_harness::api_tests!(read_only: T);
+test_harness::api_tests!(capacity: T);
+test_harness::api_tests!(cross_capacity: T);
+
+mod capacity_4 {
+    use super::*;
+    test_harness::api_tests!(capacity: T<4>);
 }
 
-#[test]
-fn all_tests() {
-    T::all_tests();
+mod capacity_64 {
+    use super::*;
+    test_harness::api_tests!(capacity: T<64>);
 }
+
+/// The most a `u8` length holds.
+mod capacity_255_u8 {
+    use super::*;
+    test_harness::api_tests!(capacity: T<255, u8>);
+}
+
+test_harness::api_tests!(differential: T);
@@ -1,17 +1,64 @@
 //! no_std heapless (bare metal/embedded-friendly)
 #![no_std]
 
+use core::fmt::{self, Display, Formatter};
+use core::str;
 //#[cfg(test)]
 //use test_harness;
-use utils::{checks, DnaTrait, OurResult, RnaTrait};
+use utils::checks::Alphabet;
+use utils::{
+    checks, ComplementMutTrait, ComplementTrait, DnaTrait, MaterializedNucleotides,
+    NucleotideError, NucleotidesView, OurResult, RnaToDnaTrait, RnaTrait,
+};
+
+/// Create [`Dna`] from a string literal, inferring `N` from its length. Return
+/// [`OurResult`]`<`[`Dna`]`>`.
+#[macro_export]
+macro_rules! dna {
+    ($dna:literal) => {
+        <$crate::Dna<'_, { $dna.len() }> as $crate::__private::DnaTrait<_>>::new($dna)
+    };
+}
+
+/// Create [`Rna`] from a string literal, inferring `N` from its length. Return
+/// [`OurResult`]`<`[`Rna`]`>`. (A literal with a non-ASCII character fails as
+/// [`NucleotideError::InvalidNucleotide`], even though its length is in bytes.)
+#[macro_export]
+macro_rules! rna {
+    ($rna:literal) => {
+        <$crate::Rna<{ $rna.len() }> as $crate::__private::RnaTrait>::new($rna)
+    };
+}
+
+/// Used by macros [`dna`] and [`rna`], so that their callers don't need to import these traits (or
+/// depend on `utils`). Not a part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use utils::{DnaTrait, RnaTrait};
+}
 
 /// Fixed length.
 ///
-/// `const N` parameter does not affect storage of this type. It's used only to infer respective
-/// ['Rna`] size when calling [`Dna::into_rna`].
+/// `const N` parameter does not affect storage of this type (other than of [`Dna::Materialized`]
+/// variant). It's used to infer respective ['Rna`] size when calling [`Dna::into_rna`]. It's the
+/// exact length of all variants - [`Dna::new`] rejects any other length.
 ///
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Dna<'a, const N: usize>(&'a str);
+#[derive(Clone, Copy)]
+pub enum Dna<'a, const N: usize> {
+    /// Represented by given DNA nucleotides. Returned by [`Dna::new`].
+    GivenNucleotides(&'a str),
+    /// DNA nucleotides materialized (reverse transcribed) from [`Rna`] by [`Rna::into_dna`].
+    Materialized(MaterializedDna<N>),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed. Returned
+    /// by [`ComplementTrait`] methods of [`Dna::GivenNucleotides`], so that they don't need to fit
+    /// in any storage.
+    View(NucleotidesView<'a>),
+}
+
+/// Payload of [`Dna::Materialized`]: exactly `N` ASCII DNA nucleotides. Its field is private, so
+/// only [`Rna::into_dna`] creates it.
+#[derive(Clone, Copy)]
+pub struct MaterializedDna<const N: usize>([u8; N]);
 
 /// RNA (RNA nucleotide sequence).
 ///
@@ -30,46 +77,156 @@ pub struct Dna<'a, const N: usize>(&'a str);
 /// which does compare instances of types parameterized even with different const generic `M`.
 /// However, there `M` is not the actual length, but the maximum length.
 ///
-/// This is not Unicode-friendly. For that we'd need to implement [`PartialEq`] ourselves, and
-/// Unicode-proof [`Rna::new_from_iter`].
+/// It stores one byte per nucleotide, so it accepts ASCII nucleotides only. [`Rna::new_from_iter`]
+/// rejects anything else before narrowing it to a byte.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Rna<const N: usize>([u8; N]);
 
 impl<'a, const N: usize> DnaTrait<'a, Rna<N>> for Dna<'a, N> {
     /// Create a new [`Dna`] instance with given DNA nucleotides. If `dna` is valid, return  
-    /// [`Some(Dna)`](Some<Dna>) containing the new instance. On error return [`Err`] with a 0-based
-    /// index of the first incorrect character.
+    /// [`Some(Dna)`](Some<Dna>) containing the new instance. On error return [`Err`] with a
+    /// [`NucleotideError`](utils::NucleotideError) locating the first incorrect character. If all
+    /// are valid, but there are not exactly `N` of them, return
+    /// [`NucleotideError::LengthMismatch`] (like [`Rna::new`] does).
     fn new(dna: &'a str) -> OurResult<Self> {
         checks::check_dna(dna)?;
-        Ok(Self(dna))
+        Self::given_nucleotides(dna)
+    }
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, Alphabet::Iupac)?;
+        Self::given_nucleotides(dna)
     }
 
     /// Create an [`Rna`] instance, based on `self`. The returned instance contains the translated
     /// nucleotides. (The result doesn't depend on the original [`Dna`] instance's lifetime). TODO
     /// add similar doc to `ok_heap_string`.
     fn into_rna(&self) -> Rna<N> {
-        Rna::new_from_iter(self.0.chars().map(utils::dna_to_rna)).expect("RNA sequence")
+        self.try_into_rna().expect("RNA sequence of N nucleotides")
+    }
+
+    /// Never fails: `self` has exactly `N` nucleotides, and so does the returned [`Rna`].
+    fn try_into_rna(&self) -> OurResult<Rna<N>> {
+        self.with_rna_chars(|rna_chars| Rna::new_from_iter(rna_chars, Alphabet::Iupac))
+    }
+}
+
+impl<'a, const N: usize> Dna<'a, N> {
+    /// Wrap `dna` (already checked, hence ASCII) if it has exactly `N` nucleotides. Otherwise
+    /// return [`NucleotideError::LengthMismatch`].
+    fn given_nucleotides(dna: &'a str) -> OurResult<Self> {
+        if dna.len() != N {
+            return Err(NucleotideError::LengthMismatch {
+                expected: N,
+                actual: dna.len(),
+            });
+        }
+        Ok(Self::GivenNucleotides(dna))
+    }
+}
+
+utils::lazy_dna_impls!(Dna<'a, N> [const N: usize]);
+
+impl<const N: usize> MaterializedNucleotides for MaterializedDna<N> {
+    fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        str::from_utf8(&self.0)
+            .expect("UTF-8 encoded string of DNA nucleotides")
+            .chars()
+    }
+
+    fn complement_in_place(&mut self, reverse: bool) {
+        utils::complement_bytes(&mut self.0, utils::dna_complement, reverse);
+    }
+}
+
+/// Unlike [`Rna`], we compare [`Dna`] regardless of its variant.
+impl<'a, const N: usize> PartialEq for Dna<'a, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.with_chars(|self_chars| other.with_chars(|other_chars| self_chars.eq(other_chars)))
+    }
+}
+impl<'a, const N: usize> Eq for Dna<'a, N> {}
+
+impl<const N: usize> Display for Rna<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(str::from_utf8(&self.0).expect("UTF-8 encoded string of RNA nucleotides"))
     }
 }
 
 impl<'a, const N: usize> RnaTrait<'a> for Rna<N> {
     fn new(rna: &str) -> OurResult<Self> {
-        Self::new_from_iter(rna.chars())
+        Self::new_from_iter(rna.chars(), Alphabet::Strict)
+    }
+
+    fn new_iupac(rna: &str) -> OurResult<Self> {
+        Self::new_from_iter(rna.chars(), Alphabet::Iupac)
     }
 }
 
 impl<const N: usize> Rna<N> {
-    fn new_from_iter(mut rna_iter: impl Iterator<Item = char>) -> OurResult<Self> {
-        //let mut result = Self(core::array::from_fn(|_| Default::default()));
-        // Can't `result.0.copy_from_slice(rna)` - because `result.0` is `&[char]`.
-        let result = Self(core::array::from_fn(|_| {
-            rna_iter.next().expect("nucleotide") as u8
-        }));
-        assert!(
-            rna_iter.next().is_none(),
-            "Not enough space, or too long RNA source."
-        );
-        checks::check_rna_char_iter(result.0.iter().map(|&b| b as char))?;
+    /// Check each nucleotide against `alphabet` before narrowing it to a byte. (Otherwise `as u8`
+    /// would truncate a non-ASCII `char` - possibly into a valid nucleotide.) Then require exactly
+    /// `N` nucleotides, or return [`NucleotideError::LengthMismatch`]. An invalid nucleotide takes
+    /// precedence, even if it's beyond `N`.
+    fn new_from_iter(rna_iter: impl Iterator<Item = char>, alphabet: Alphabet) -> OurResult<Self> {
+        let expected_alphabet = alphabet.rna_nucleotides();
+        let mut result = Self([0; N]);
+        let mut len = 0usize;
+        for (char_index, nucl) in rna_iter.enumerate() {
+            if !expected_alphabet.contains(nucl) {
+                return Err(NucleotideError::InvalidNucleotide {
+                    char_index,
+                    // All previous nucleotides are ASCII.
+                    byte_offset: char_index,
+                    found: nucl,
+                    expected_alphabet,
+                });
+            }
+            if let Some(byte) = result.0.get_mut(char_index) {
+                *byte = nucl as u8;
+            }
+            len += 1;
+        }
+        if len != N {
+            return Err(NucleotideError::LengthMismatch {
+                expected: N,
+                actual: len,
+            });
+        }
         Ok(result)
     }
 }
+
+impl<'a, const N: usize> RnaToDnaTrait<'a, Dna<'a, N>> for Rna<N> {
+    /// Create a [`Dna::Materialized`] instance of the same (exact) length.
+    fn into_dna(&self) -> Dna<'a, N> {
+        Dna::Materialized(MaterializedDna(
+            self.0
+                .map(|rna_nucl| utils::rna_to_dna(rna_nucl as char) as u8),
+        ))
+    }
+}
+
+impl<const N: usize> ComplementTrait for Rna<N> {
+    fn complement(&self) -> Self {
+        let mut result = *self;
+        result.complement_in_place();
+        result
+    }
+
+    fn reverse_complement(&self) -> Self {
+        let mut result = *self;
+        result.reverse_complement_in_place();
+        result
+    }
+}
+
+impl<const N: usize> ComplementMutTrait for Rna<N> {
+    fn complement_in_place(&mut self) {
+        utils::complement_bytes(&mut self.0, utils::rna_complement, false);
+    }
+
+    fn reverse_complement_in_place(&mut self) {
+        utils::complement_bytes(&mut self.0, utils::rna_complement, true);
+    }
+}
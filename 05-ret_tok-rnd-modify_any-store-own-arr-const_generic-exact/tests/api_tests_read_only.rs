@@ -1,12 +1,19 @@
 //! Can't use the generic tests from ['utils::api_tests_read_only`], because this implementation
-//! makes `Rna` compatible only for the same const generic parameter `N`.
+//! makes `Rna` compatible only for the same const generic parameter `N`. For the same reason the
+//! differential tests here generate nucleotides of exactly `N` characters - see
+//! [`differential_tests_of_len`].
 use ret_tok_rnd_modify_any_store_own_arr_const_generic_exact as dna;
-use utils::{DnaTrait, RnaTrait};
+use test_harness::differential::{
+    random_nucleotides_of_len, random_variation, reference_check, reference_transcription, Prng,
+    DNA_ALPHABET, RNA_ALPHABET,
+};
+use utils::{
+    ComplementMutTrait, ComplementTrait, DnaTrait, NucleotideError, RnaToDnaTrait, RnaTrait,
+};
 
 #[test]
 fn test_valid_dna_input() {
-    // const generic parameter N doesn't matter here
-    assert!(<dna::Dna<0>>::new("GCTA").is_ok());
+    assert!(<dna::Dna<4>>::new("GCTA").is_ok());
 }
 
 #[test]
@@ -17,21 +24,24 @@ fn test_valid_rna_input() {
 #[test]
 fn test_invalid_dna_input() {
     // Invalid character
-    assert_eq!(<dna::Dna<0>>::new("X").err(), Some(0));
+    assert_eq!(<dna::Dna<0>>::new("X").err().map(|e| e.index()), Some(0));
     // Valid nucleotide, but invalid in context
-    assert_eq!(<dna::Dna<0>>::new("U").err(), Some(0));
+    assert_eq!(<dna::Dna<0>>::new("U").err().map(|e| e.index()), Some(0));
     // Longer string with contained errors
-    assert_eq!(<dna::Dna<0>>::new("ACGTUXXCTTAA").err(), Some(4));
+    assert_eq!(
+        <dna::Dna<0>>::new("ACGTUXXCTTAA").err().map(|e| e.index()),
+        Some(4)
+    );
 }
 
 #[test]
 fn test_invalid_rna_input() {
     // Invalid character
-    assert_eq!(<dna::Rna<1>>::new("X").unwrap_err(), 0);
+    assert_eq!(<dna::Rna<1>>::new("X").unwrap_err().index(), 0);
     // Valid nucleotide, but invalid in context
-    assert_eq!(<dna::Rna<1>>::new("T").unwrap_err(), 0);
+    assert_eq!(<dna::Rna<1>>::new("T").unwrap_err().index(), 0);
     // Longer string with contained errors
-    assert_eq!(<dna::Rna<12>>::new("ACGUTTXCUUAA").unwrap_err(), 4);
+    assert_eq!(<dna::Rna<12>>::new("ACGUTTXCUUAA").unwrap_err().index(), 4);
 }
 
 #[test]
@@ -94,4 +104,226 @@ fn test_transcribes_all_dna_to_rna() {
     )
 }
 
-// @TODO test that if const N is different to size of the slice, then panic
+#[test]
+fn test_reverse_transcribes_all_rna_to_dna() {
+    assert_eq!(
+        dna::Dna::new("ACGTGGTCTTAA").unwrap(),
+        <dna::Rna<12>>::new("UGCACCAGAAUU").unwrap().into_dna()
+    )
+}
+
+#[test]
+fn test_round_trip() {
+    let dna = <dna::Dna<12>>::new("ACGTGGTCTTAA").unwrap();
+    assert_eq!(dna, dna.into_rna().into_dna());
+}
+
+#[test]
+fn test_complement() {
+    assert_eq!(
+        <dna::Dna<6>>::new("ACGTTA").unwrap().complement(),
+        dna::Dna::new("TGCAAT").unwrap()
+    );
+    assert_eq!(
+        <dna::Rna<6>>::new("ACGUUA").unwrap().complement(),
+        dna::Rna::new("UGCAAU").unwrap()
+    );
+    assert_eq!(
+        <dna::Dna<8>>::new("AACGTTTG").unwrap().reverse_complement(),
+        dna::Dna::new("CAAACGTT").unwrap()
+    );
+    assert_eq!(
+        <dna::Rna<8>>::new("AACGUUUG").unwrap().reverse_complement(),
+        dna::Rna::new("CAAACGUU").unwrap()
+    );
+}
+
+#[test]
+fn test_complement_in_place() {
+    let mut rna = <dna::Rna<8>>::new("AACGUUUG").unwrap();
+    rna.reverse_complement_in_place();
+    assert_eq!(rna, dna::Rna::new("CAAACGUU").unwrap());
+    rna.complement_in_place();
+    assert_eq!(rna, dna::Rna::new("GUUUGCAA").unwrap());
+
+    let mut dna = rna.into_dna();
+    dna.reverse_complement_in_place();
+    assert_eq!(dna, rna.reverse_complement().into_dna());
+
+    let mut given_dna = <dna::Dna<8>>::new("AACGTTTG").unwrap();
+    given_dna.reverse_complement_in_place();
+    assert_eq!(given_dna, dna::Dna::new("CAAACGTT").unwrap());
+}
+
+#[test]
+fn test_iupac() {
+    assert!(<dna::Dna<0>>::new("ARYN").is_err());
+    let rna = <dna::Rna<4>>::new_iupac("UYRN").unwrap();
+    assert_eq!(<dna::Dna<4>>::new_iupac("ARYN").unwrap().into_rna(), rna);
+    assert_eq!(
+        rna.reverse_complement(),
+        dna::Rna::new_iupac("NYRA").unwrap()
+    );
+    assert_eq!(<dna::Rna<1>>::new_iupac("X").unwrap_err().index(), 0);
+}
+
+#[test]
+fn test_length_mismatch() {
+    assert_eq!(
+        <dna::Rna<4>>::new("CGA"),
+        Err(NucleotideError::LengthMismatch {
+            expected: 4,
+            actual: 3
+        })
+    );
+    assert_eq!(
+        <dna::Rna<2>>::new("CGA"),
+        Err(NucleotideError::LengthMismatch {
+            expected: 2,
+            actual: 3
+        })
+    );
+    // An invalid nucleotide takes precedence, even beyond `N`.
+    assert_eq!(<dna::Rna<2>>::new("CGX").unwrap_err().index(), 2);
+
+    assert!(dna::Dna::<3>::new("GCT").unwrap().try_into_rna().is_ok());
+}
+
+/// `Dna` requires exactly `N` nucleotides, too. So its transcription can't fail (or panic).
+#[test]
+fn test_dna_length_mismatch() {
+    assert_eq!(
+        <dna::Dna<4>>::new("GCT"),
+        Err(NucleotideError::LengthMismatch {
+            expected: 4,
+            actual: 3
+        })
+    );
+    assert_eq!(
+        <dna::Dna<2>>::new_iupac("GCN"),
+        Err(NucleotideError::LengthMismatch {
+            expected: 2,
+            actual: 3
+        })
+    );
+    // An invalid nucleotide takes precedence, as for `Rna`.
+    assert_eq!(<dna::Dna<2>>::new("GCX").unwrap_err().index(), 2);
+    assert_eq!(
+        <dna::Dna<3>>::new("GCT").unwrap().into_rna(),
+        dna::Rna::new("CGA").unwrap()
+    );
+}
+
+/// Non-ASCII characters are rejected, rather than truncated. ('Ł' is U+0141, which `as u8` would
+/// truncate to 'A'.)
+#[test]
+fn test_non_ascii_rejected() {
+    assert_eq!(
+        <dna::Rna<1>>::new("Ł"),
+        Err(NucleotideError::InvalidNucleotide {
+            char_index: 0,
+            byte_offset: 0,
+            found: 'Ł',
+            expected_alphabet: "CGAU",
+        })
+    );
+    assert_eq!(
+        <dna::Rna<4>>::new("CGŁ"),
+        Err(NucleotideError::InvalidNucleotide {
+            char_index: 2,
+            byte_offset: 2,
+            found: 'Ł',
+            expected_alphabet: "CGAU",
+        })
+    );
+    assert_eq!(<dna::Rna<3>>::new_iupac("CŁN").unwrap_err().index(), 1);
+}
+
+#[test]
+fn test_macros_infer_n() {
+    let rna: dna::Rna<4> = dna::rna!("CGAU").unwrap();
+    assert_eq!(rna, dna::Rna::new("CGAU").unwrap());
+    let dna: dna::Dna<4> = dna::dna!("GCTA").unwrap();
+    assert_eq!(dna.into_rna(), rna);
+    assert_eq!(dna::rna!("CGT").unwrap_err().index(), 2);
+    assert_eq!(dna::dna!("GCU").unwrap_err().index(), 2);
+    assert_eq!(dna::rna!("CGŁ").unwrap_err().index(), 2);
+}
+
+/// The harness's differential tests, adapted to the exact length `N`: both `Dna` and `Rna` have
+/// exactly `N` nucleotides, and equality is tested with variations of the same length only.
+fn differential_tests_of_len<const N: usize>(prng: &mut Prng) {
+    for _ in 0..500 {
+        let dna = random_nucleotides_of_len(prng, DNA_ALPHABET, N);
+        let expected = reference_check(&dna, DNA_ALPHABET);
+        let actual = <dna::Dna<N>>::new(&dna);
+        assert_eq!(
+            actual.as_ref().err(),
+            expected.as_ref().err(),
+            "DNA {dna:?}"
+        );
+        if let Ok(actual) = actual {
+            assert_eq!(format!("{actual:?}"), format!("Dna(\"{dna}\")"));
+            let rna = actual.into_rna();
+            assert_eq!(
+                rna.to_string(),
+                reference_transcription(&dna),
+                "DNA {dna:?}"
+            );
+            assert_eq!(rna.into_dna(), actual, "DNA {dna:?}");
+        }
+
+        let rna = random_nucleotides_of_len(prng, RNA_ALPHABET, N);
+        let expected = reference_check(&rna, RNA_ALPHABET);
+        let actual = <dna::Rna<N>>::new(&rna);
+        assert_eq!(
+            actual.as_ref().err(),
+            expected.as_ref().err(),
+            "RNA {rna:?}"
+        );
+        if let Ok(actual) = actual {
+            assert_eq!(actual.to_string(), rna, "RNA {rna:?}");
+        }
+
+        if reference_check(&dna, DNA_ALPHABET).is_err() {
+            continue;
+        }
+        let other = random_variation(prng, &dna, DNA_ALPHABET);
+        if other.len() != N {
+            continue;
+        }
+        let (left, right) = (<dna::Dna<N>>::new(&dna), <dna::Dna<N>>::new(&other));
+        let (left, right) = (left.expect("DNA"), right.expect("DNA"));
+        assert_eq!(left == right, dna == other, "{dna:?} vs {other:?}");
+        assert_eq!(
+            left.into_rna() == right.into_rna(),
+            dna == other,
+            "{dna:?} vs {other:?}"
+        );
+    }
+}
+
+#[test]
+fn differential_tests_of_len_0() {
+    differential_tests_of_len::<0>(&mut Prng::new(0x5EED));
+}
+
+#[test]
+fn differential_tests_of_len_1() {
+    differential_tests_of_len::<1>(&mut Prng::new(0x5EED));
+}
+
+#[test]
+fn differential_tests_of_len_4() {
+    differential_tests_of_len::<4>(&mut Prng::new(0x5EED));
+}
+
+#[test]
+fn differential_tests_of_len_12() {
+    differential_tests_of_len::<12>(&mut Prng::new(0x5EED));
+}
+
+#[test]
+fn differential_tests_of_len_40() {
+    differential_tests_of_len::<40>(&mut Prng::new(0x5EED));
+}
@@ -0,0 +1,44 @@
+//! Security unit test. It checks the whole storage (see [`utils::storage::Rna::storage`]), not only
+//! the used part.
+use crate as dna;
+
+use test_harness::api_tests_mut::wipe_on_mut::Tests;
+use utils::api_tests_mut::{WithStorageLeaked, WithStorageLeakedCallBack};
+use utils::storage::Storage;
+use utils::{NucleotideError, RnaTrait, RnaTraitMut};
+
+struct T {}
+impl Tests for T {
+    type Dna<'a> = dna::Dna<'a>;
+    type Rna<'a> = dna::Rna;
+}
+
+pub(super) fn with_storage_leaked(
+    rna: &mut dna::Rna,
+    with_storage_leaked_call_back: WithStorageLeakedCallBack<bool>,
+) -> bool {
+    let bytes = rna.storage().bytes();
+    let mut bytes_iter = bytes.iter().cloned();
+    with_storage_leaked_call_back(&mut bytes_iter)
+}
+type _TWithStorageLeaked<'a> = WithStorageLeaked<'a, dna::Rna, bool>;
+const _CHECK_WITH_STORAGE_LEAKED_FUNCTION_SIGNATURE: _TWithStorageLeaked = &with_storage_leaked;
+
+test_harness::api_tests!(wipe_on_mut: T, with_storage_leaked);
+
+/// `M` limits [`RnaTraitMut::set_from_iter`]. On error the whole storage is zeroed.
+#[test]
+fn test_set_from_iter_respects_m() {
+    let mut rna = dna::RnaImpl::<4>::new("CGAU").unwrap();
+    rna.set_from_iter(&mut "UAGC".chars()).unwrap();
+    assert_eq!(rna, dna::Rna::new("UAGC").unwrap());
+    assert_eq!(
+        rna.set_from_iter(&mut "UAGCA".chars()),
+        Err(NucleotideError::CapacityExceeded {
+            capacity: 4,
+            required: 5
+        })
+    );
+    assert_eq!(rna, dna::Rna::new("").unwrap());
+    assert_eq!(rna.storage().bytes(), [0; 4]);
+}
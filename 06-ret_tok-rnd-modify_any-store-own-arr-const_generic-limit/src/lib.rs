@@ -1,9 +1,19 @@
 //! no_std heapless (bare metal/embedded-friendly)
 #![no_std]
 
-use core::fmt::{self, Debug, Formatter};
 use core::str;
-use utils::{checks, DnaTrait, OurResult, RnaTrait};
+use utils::checks::Alphabet;
+use utils::storage::{self, FixedCapacity, FromNucleotides, Storage};
+use utils::{
+    checks, DnaTrait, LenType, MaterializedNucleotides, NucleotidesView, OurResult, RnaToDnaTrait,
+};
+
+#[cfg(test)]
+mod api_tests_mut_wipe_on_clone;
+#[cfg(all(test, feature = "security"))]
+mod api_tests_mut_wipe_on_drop;
+#[cfg(test)]
+mod api_tests_mut_wipe_on_mut;
 
 const DEFAULT_MAX_NUCLEOTIDES: usize = 12;
 
@@ -12,107 +22,162 @@ const DEFAULT_MAX_NUCLEOTIDES: usize = 12;
 /// `const N` parameter does not affect storage of this type. It's used only to infer respective
 /// ['Rna`] size when calling [`Dna::into_rna`].
 ///
-/// We don't derive [`PartialEq`]. Why? Because we want to compare [`Dna`] types regardless of `M`.
-#[derive(Debug, Clone, Copy)]
-pub struct DnaImpl<'a, const M: usize = DEFAULT_MAX_NUCLEOTIDES>(&'a str);
+/// We don't derive [`PartialEq`]. Why? Because we want to compare [`Dna`] types regardless of `M`
+/// (and regardless of the variant).
+///
+/// With `security` feature it isn't [`Copy`]. See [`MaterializedDna`].
+#[cfg_attr(not(feature = "security"), derive(Copy))]
+#[derive(Clone)]
+pub enum DnaImpl<'a, const M: usize = DEFAULT_MAX_NUCLEOTIDES, L: LenType = usize> {
+    /// Represented by given DNA nucleotides. Returned by [`DnaImpl::new`].
+    GivenNucleotides(&'a str),
+    /// DNA nucleotides materialized (reverse transcribed) from [`RnaImpl`] by
+    /// [`RnaImpl::into_dna`].
+    Materialized(MaterializedDna<M, L>),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed. Returned
+    /// by [`utils::ComplementTrait`] methods of [`DnaImpl::GivenNucleotides`], so that they don't
+    /// need to fit in any storage.
+    View(NucleotidesView<'a>),
+}
 
 pub type Dna<'a> = DnaImpl<'a, DEFAULT_MAX_NUCLEOTIDES>;
 
-/// RNA (RNA nucleotide sequence).
+/// Payload of [`DnaImpl::Materialized`], created by [`RnaImpl::into_dna`] only (its fields are
+/// private). So `len` doesn't exceed `M`, and the first `len` bytes of `dna` are ASCII
+/// nucleotides.
 ///
-/// TODO:
-/// We don't derive [`PartialEq`] or [`Debug`] or [`Clone`] or [`Copy`] (neither Serde's
-/// `Serialize`, if we used it). See
+/// With `security` feature it isn't [`Copy`], it wipes itself on drop, and [`DnaImpl`]'s
+/// [`Debug`](core::fmt::Debug) redacts it - the same as [`ArrayStorage`].
+#[cfg_attr(not(feature = "security"), derive(Copy))]
+#[derive(Clone)]
+pub struct MaterializedDna<const M: usize, L: LenType> {
+    dna: [u8; M],
+    len: L,
+}
+
+#[cfg(feature = "security")]
+impl<const M: usize, L: LenType> Drop for MaterializedDna<M, L> {
+    fn drop(&mut self) {
+        utils::wipe(&mut self.dna);
+    }
+}
+
+/// [`RnaImpl`]'s storage: `M` bytes. With `security` feature it wipes itself on drop, and [`Rna`]'s
+/// [`Debug`] redacts the nucleotides. See
 /// [02_no_heap-array-const_limit-chars](../../02_no_heap-array-const_limit-chars/src/lib.rs) for
 /// notes on security.
 ///
-/// We don't derive [`PartialEq`] for the same reason as in [`DnaImpl`].
-pub struct RnaImpl<const M: usize = DEFAULT_MAX_NUCLEOTIDES> {
-    rna: [u8; M],
-    len: usize,
+/// Not transactional: [`utils::RnaTraitMut::set_from_iter`] stores in place. On error the whole
+/// storage is zeroed, and [`Rna`] is left empty.
+#[derive(Clone)]
+pub struct ArrayStorage<const M: usize>([u8; M]);
+
+impl<const M: usize> Storage for ArrayStorage<M> {
+    const CAPACITY: Option<usize> = Some(M);
+    const REDACT_DEBUG: bool = cfg!(feature = "security");
+    const CONSTANT_TIME_EQ: bool = cfg!(feature = "constant_time_eq");
+
+    fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl<'a, const M: usize> FromNucleotides<'a> for ArrayStorage<M> {
+    fn from_nucleotides(nucleotides: &'a str) -> OurResult<Self> {
+        <[u8; M]>::from_nucleotides(nucleotides).map(Self)
+    }
+}
+
+impl<const M: usize> FixedCapacity for ArrayStorage<M> {
+    type WithCapacity<const N: usize> = ArrayStorage<N>;
+}
+
+#[cfg(feature = "security")]
+impl<const M: usize> Drop for ArrayStorage<M> {
+    fn drop(&mut self) {
+        utils::wipe(&mut self.0);
+    }
 }
 
+/// RNA (RNA nucleotide sequence), stored in an array of `M` bytes. See [`ArrayStorage`].
+///
+/// The length type `L` must hold `M` (and `N` of [`RnaImpl::clone_max_size`]). Otherwise
+/// instantiating the constructors fails to compile. See [`Storage::CAPACITY`].
+pub type RnaImpl<const M: usize = DEFAULT_MAX_NUCLEOTIDES, L = usize> =
+    storage::Rna<ArrayStorage<M>, L>;
+
 pub type Rna = RnaImpl<DEFAULT_MAX_NUCLEOTIDES>;
 
-impl<'a, const M: usize> DnaTrait<'a, RnaImpl<M>> for DnaImpl<'a, M> {
+impl<'a, const M: usize, L: LenType> DnaTrait<'a, RnaImpl<M, L>> for DnaImpl<'a, M, L> {
     /// Create a new [`Dna`] instance with given DNA nucleotides. If `dna` is valid, return  
-    /// [`Some(Dna)`](Some<Dna>) containing the new instance. On error return [`Err`] with a 0-based
-    /// index of the first incorrect character.
+    /// [`Some(Dna)`](Some<Dna>) containing the new instance. On error return [`Err`] with a
+    /// [`NucleotideError`](utils::NucleotideError) locating the first incorrect character.
     fn new(dna: &'a str) -> OurResult<Self> {
         checks::check_dna(dna)?;
-        Ok(Self(dna))
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(dna))
     }
 
     /// Create an [`Rna`] instance, based on `self`. The returned instance contains the translated
     /// nucleotides. (The result doesn't depend on the original [`Dna`] instance's lifetime). TODO
     /// add similar doc to `ok_heap_string`.
-    fn into_rna(&self) -> RnaImpl<M> {
-        RnaImpl::new_from_iter(self.0.chars().map(utils::dna_to_rna)).expect("RNA sequence")
+    fn into_rna(&self) -> RnaImpl<M, L> {
+        self.try_into_rna().expect("RNA sequence")
     }
-}
 
-impl<'a, const M: usize> RnaTrait<'a> for RnaImpl<M> {
-    fn new(rna: &'a str) -> OurResult<Self> {
-        Self::new_from_iter(rna.chars())
+    /// Return [`utils::NucleotideError::CapacityExceeded`] if the transcribed nucleotides don't
+    /// fit in `M` bytes.
+    fn try_into_rna(&self) -> OurResult<RnaImpl<M, L>> {
+        self.with_rna_chars(|rna_chars| RnaImpl::new_from_iter_with(rna_chars, Alphabet::Iupac))
     }
 }
 
-impl<const M: usize> RnaImpl<M> {
-    pub fn new_from_iter(rna_chars_iter: impl Iterator<Item = char>) -> OurResult<Self> {
-        let mut len = 0usize;
-        let mut rna_bytes_iter = utils::char_iter_to_byte_iter(rna_chars_iter);
-        let rna = core::array::from_fn(|_| {
-            if let Some(b) = rna_bytes_iter.next() {
-                len += 1;
-                b
-            } else {
-                0 // extra slots - not used by current data
-            }
-        });
-        if rna_bytes_iter.next().is_some() {
-            // Extra bytes left.
-            return Err(len);
+impl<'a, const M: usize, L: LenType> RnaToDnaTrait<'a, DnaImpl<'a, M, L>> for RnaImpl<M, L> {
+    /// Create a [`DnaImpl::Materialized`] instance with the same maximum size `M`.
+    fn into_dna(&self) -> DnaImpl<'a, M, L> {
+        let mut dna = [u8::default(); M];
+        let rna = self.as_str().as_bytes();
+        for (dna_nucl, &rna_nucl) in dna.iter_mut().zip(rna) {
+            // RNA nucleotides are ASCII, hence one byte each.
+            *dna_nucl = utils::rna_to_dna(rna_nucl as char) as u8;
         }
-        let result = Self { rna, len };
-        checks::check_rna_str(result.as_str())?;
-        Ok(result)
-    }
-
-    fn as_str(&self) -> &str {
-        str::from_utf8(&self.rna[..self.len]).expect("UTF-8 encoded string of RNA nucleotides")
-    }
-
-    pub fn clone_max_size<const N: usize>(&self) -> RnaImpl<N> {
-        assert!(self.len <= N, "Calling clone_max_size on an instance with len={}, but the target maximum size is insufficient: {}.", self.len, N);
-        let mut rna = [u8::default(); N];
-        rna[..self.len].copy_from_slice(&self.rna[..self.len]);
-        RnaImpl { rna, len: self.len }
+        DnaImpl::Materialized(MaterializedDna {
+            dna,
+            len: L::from_usize(rna.len()),
+        })
     }
 }
 
-impl<const M: usize> Clone for RnaImpl<M> {
-    fn clone(&self) -> Self {
-        self.clone_max_size::<M>()
-    }
-}
+utils::lazy_dna_impls!(DnaImpl<'a, M, L> [const M: usize, L: LenType]);
+
+impl<const M: usize, L: LenType> MaterializedNucleotides for MaterializedDna<M, L> {
+    const REDACT_DEBUG: bool = cfg!(feature = "security");
 
-impl<'a, const L: usize, const R: usize> PartialEq<DnaImpl<'_, R>> for DnaImpl<'a, L> {
-    fn eq(&self, other: &DnaImpl<'_, R>) -> bool {
-        self.0 == other.0
+    fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        str::from_utf8(&self.dna[..self.len.to_usize()])
+            .expect("UTF-8 encoded string of DNA nucleotides")
+            .chars()
     }
-}
-impl<'a, const M: usize> Eq for DnaImpl<'a, M> {}
 
-impl<const L: usize, const R: usize> PartialEq<RnaImpl<R>> for RnaImpl<L> {
-    fn eq(&self, other: &RnaImpl<R>) -> bool {
-        self.as_str() == other.as_str()
+    fn complement_in_place(&mut self, reverse: bool) {
+        let len = self.len.to_usize();
+        utils::complement_bytes(&mut self.dna[..len], utils::dna_complement, reverse);
     }
 }
 
-impl<const M: usize> Eq for RnaImpl<M> {}
-
-impl<const M: usize> Debug for RnaImpl<M> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "Rna(\"{}\")", self.as_str())
+impl<'a, const M: usize, L: LenType, const R: usize, RL: LenType> PartialEq<DnaImpl<'_, R, RL>>
+    for DnaImpl<'a, M, L>
+{
+    fn eq(&self, other: &DnaImpl<'_, R, RL>) -> bool {
+        self.with_chars(|self_chars| other.with_chars(|other_chars| self_chars.eq(other_chars)))
     }
 }
+impl<'a, const M: usize, L: LenType> Eq for DnaImpl<'a, M, L> {}
@@ -0,0 +1,57 @@
+//! Security unit test. It checks the whole storage of [`dna::RnaStorage::MutableNucleotides`] (see
+//! [`utils::storage::Rna::storage`]), not only the used part.
+use crate as dna;
+
+use test_harness::api_tests_mut::wipe_on_mut::{RnaInStorageTests, RnaTests};
+use utils::api_tests_mut::WithStorageLeakedCallBack;
+use utils::storage::Storage;
+use utils::{DnaTraitWithStorage, NucleotideError, RnaTrait, RnaTraitMut};
+
+struct T {}
+/// Our [`dna::Dna`] transcribes into storage passed in, so it isn't a [`utils::DnaTrait`].
+impl RnaTests for T {
+    type Rna<'a> = dna::Rna<'a>;
+}
+
+/// [`dna::Rna::new`] has no storage to modify. So we transcribe into a storage instead.
+impl RnaInStorageTests for T {
+    fn cga_in(storage: &mut [u8; 3]) -> dna::Rna<'_> {
+        dna::Dna::new("GCT").unwrap().into_rna(storage)
+    }
+}
+
+fn with_storage_leaked(
+    rna: &mut dna::Rna,
+    with_storage_leaked_call_back: WithStorageLeakedCallBack<bool>,
+) -> bool {
+    let bytes = rna.storage().bytes();
+    let mut bytes_iter = bytes.iter().cloned();
+    with_storage_leaked_call_back(&mut bytes_iter)
+}
+
+test_harness::api_tests!(wipe_on_mut_in_storage: T, with_storage_leaked);
+
+#[test]
+fn test_set_from_iter_respects_storage_len() {
+    let mut storage = [0u8; 4];
+    let dna = dna::Dna::new("GCTA").unwrap();
+    let mut rna = dna.into_rna(&mut storage);
+    assert_eq!(
+        rna.set_from_iter(&mut "UAGCA".chars()),
+        Err(NucleotideError::CapacityExceeded {
+            capacity: 4,
+            required: 5
+        })
+    );
+    assert_eq!(rna, dna::Rna::new("").unwrap());
+    assert_eq!(storage, [0; 4]);
+
+    let mut given = dna::Rna::new("CG").unwrap();
+    assert_eq!(
+        given.set_from_iter(&mut "U".chars()),
+        Err(NucleotideError::CapacityExceeded {
+            capacity: 0,
+            required: 1
+        })
+    );
+}
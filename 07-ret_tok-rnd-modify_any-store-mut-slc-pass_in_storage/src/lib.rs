@@ -1,104 +1,99 @@
-//! This crate doesn't implement utils::{DnaTrait, RnaTrait}, because the function signature of
-//! [`Dna::into_rna`] here is different - it needs an extra parameter (storage slice).
+//! This crate doesn't implement utils::DnaTrait, because the function signature of
+//! [`Dna::into_rna`] here is different - it needs an extra parameter (storage slice). Instead, it
+//! implements [`DnaTraitWithStorage`] (and [`RnaToDnaTraitWithStorage`]). It does implement
+//! [`utils::RnaTrait`] and [`utils::RnaTraitMut`].
 #![no_std]
 
-use core::fmt::{self, Debug, Formatter};
 use core::str;
-use utils::{checks, OurResult};
+use utils::checks::{self, Alphabet};
+use utils::storage::{self, FromNucleotides, Storage};
+use utils::{DnaTraitWithStorage, OurResult, RnaToDnaTraitWithStorage};
+
+#[cfg(test)]
+mod api_tests_mut_wipe_on_mut;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Dna<'a>(&'a str);
 
-/// This can't derive, neither implement, [`Clone`]. Why? Because a mutable reference (`rna` field)
-/// can't be cloned.
+/// [`Rna`]'s storage. This can't derive, neither implement, [`Clone`]. Why? Because a mutable
+/// reference can't be cloned.
 /// -- TODO change that for the immutable version (once implemented)
 ///
 /// New to Rust? We can't just clone the referenced data and use a new reference, because any data
 /// in Rust has to be owned from exactly one place. However,  the goal of this implementation is not
 /// to own the data,  but to (mutably) refer to it instead.
-pub enum Rna<'a> {
+pub enum RnaStorage<'a> {
     GivenNucleotides(&'a str),
     /// The characters in the byte slice represent, or will represent, RNA.
-    MutableNucleotides {
-        /// The whole/available storage.
-        /// TODO implement mutable operations. Panic on the first invariant.
-        rna: &'a mut [u8],
-        /// Length of the valid subslice (used storage).
-        len: usize,
-    },
+    /// [`utils::RnaTraitMut::set_from_iter`] can use all of the slice.
+    MutableNucleotides(&'a mut [u8]),
 }
 
-impl<'a> Dna<'a> {
-    pub fn new(dna: &'a str) -> OurResult<Self> {
-        checks::check_dna(dna)?;
-        Ok(Self(dna))
+/// Only [`RnaStorage::MutableNucleotides`] can be modified: up to the length of its slice. So
+/// [`utils::RnaTraitMut::set_from_iter`] returns [`utils::NucleotideError::CapacityExceeded`]
+/// otherwise (with `capacity` zero for [`RnaStorage::GivenNucleotides`]).
+///
+/// Not transactional: we zero any unused bytes, so that they don't keep previous nucleotides. On
+/// error we zero the whole slice, and we leave [`Rna`] empty.
+impl<'a> Storage for RnaStorage<'a> {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Self::GivenNucleotides(rna) => rna.as_bytes(),
+            Self::MutableNucleotides(rna) => rna,
+        }
     }
 
-    pub fn into_rna<'s>(&self, storage: &'s mut [u8]) -> Rna
-    where
-        's: 'a,
-    {
-        Rna::new_from_iter_and_storage(self.0.chars().map(utils::dna_to_rna), storage).expect("RNA")
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        match self {
+            Self::GivenNucleotides(_) => &mut [],
+            Self::MutableNucleotides(rna) => rna,
+        }
     }
 }
 
-impl<'a> Rna<'a> {
-    pub fn new(rna: &'a str) -> OurResult<Self> {
-        checks::check_rna_str(rna)?;
-        Ok(Self::GivenNucleotides(rna))
+impl<'a> FromNucleotides<'a> for RnaStorage<'a> {
+    fn from_nucleotides(nucleotides: &'a str) -> OurResult<Self> {
+        Ok(Self::GivenNucleotides(nucleotides))
     }
+}
 
-    fn new_from_iter_and_storage<'s>(
-        rna_iter: impl Iterator<Item = char>,
-        storage: &'s mut [u8],
-    ) -> OurResult<Self>
-    where
-        's: 'a,
-    {
-        let len = utils::char_iter_to_bytes(storage, rna_iter);
-        let result = Self::MutableNucleotides { rna: storage, len };
-        checks::check_rna_str(result.as_str())?;
-        Ok(result)
-    }
+pub type Rna<'a> = storage::Rna<RnaStorage<'a>>;
 
-    fn as_str(&self) -> &str {
-        match self {
-            Self::GivenNucleotides(rna) => rna,
-            Self::MutableNucleotides { rna, len } => {
-                str::from_utf8(&rna[..*len]).expect("UTF-8 encoded string of RNA nucleotides")
-            }
-        }
+impl<'a> DnaTraitWithStorage<'a, Rna<'a>> for Dna<'a> {
+    fn new(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna(dna)?;
+        Ok(Self(dna))
     }
-}
 
-impl<'a> PartialEq for Rna<'a> {
-    fn eq(&self, other: &Self) -> bool {
-        self.as_str() == other.as_str()
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, Alphabet::Iupac)?;
+        Ok(Self(dna))
     }
-}
-/// Not necessary, but valid.
-impl<'a> Eq for Rna<'a> {}
 
-impl<'a> Debug for Rna<'a> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "Rna(\"{}\")", self.as_str())
+    fn into_rna(&self, storage: &'a mut [u8]) -> Rna<'a> {
+        // Transcribed from valid DNA of either alphabet. Alphabet::Iupac is a superset.
+        Rna::new_from_iter_in(
+            RnaStorage::MutableNucleotides(storage),
+            self.0.chars().map(utils::dna_to_rna),
+            Alphabet::Iupac,
+        )
+        .expect("RNA")
     }
 }
 
-impl<'l, 'r> PartialEq<&Rna<'r>> for Rna<'l> {
-    fn eq(&self, other: &&Rna<'r>) -> bool {
-        self.as_str() == other.as_str()
-    }
-}
-impl<'l, 'r> PartialEq<Rna<'r>> for &Rna<'l> {
-    fn eq(&self, other: &Rna<'r>) -> bool {
-        self.as_str() == other.as_str()
+impl<'a> RnaToDnaTraitWithStorage<'a, Dna<'a>> for Rna<'a> {
+    /// Return [`Dna`] referring to (the used part of) `storage`. Unlike [`Rna`], [`Dna`] never needs
+    /// to be modified, so it refers to `storage` as shared.
+    fn into_dna(&self, storage: &'a mut [u8]) -> Dna<'a> {
+        let len = utils::char_iter_to_bytes(storage, self.as_str().chars().map(utils::rna_to_dna));
+        Dna(str::from_utf8(&storage[..len]).expect("UTF-8 encoded string of DNA nucleotides"))
     }
 }
 
 #[cfg(test)]
 pub mod test {
     use super::{Dna, Rna};
+    use utils::{DnaTraitWithStorage, RnaTrait};
 
     /// Testing that equality is defined for references - because we can't share instances of this
     /// type in any other way.
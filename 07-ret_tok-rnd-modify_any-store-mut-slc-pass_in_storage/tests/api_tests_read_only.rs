@@ -1,81 +1,17 @@
-use ret_tok_rnd_modify_any_store_mut_slc_pass_in_storage as dna;
-
-#[test]
-fn test_valid_dna_input() {
-    assert!(dna::Dna::new("GCTA").is_ok());
-}
-
-#[test]
-fn test_valid_rna_input() {
-    assert!(dna::Rna::new("CGAU").is_ok());
-}
-
-#[test]
-fn test_invalid_dna_input() {
-    // Invalid character
-    assert_eq!(dna::Dna::new("X").err(), Some(0));
-    // Valid nucleotide, but invalid in context
-    assert_eq!(dna::Dna::new("U").err(), Some(0));
-    // Longer string with contained errors
-    assert_eq!(dna::Dna::new("ACGTUXXCTTAA").err(), Some(4));
-}
-
-#[test]
-fn test_invalid_rna_input() {
-    // Invalid character
-    assert_eq!(dna::Rna::new("X").unwrap_err(), 0);
-    // Valid nucleotide, but invalid in context
-    assert_eq!(dna::Rna::new("T").unwrap_err(), 0);
-    // Longer string with contained errors
-    assert_eq!(dna::Rna::new("ACGUTTXCUUAA").unwrap_err(), 4);
-}
-
-#[test]
-fn test_acid_equals_acid() {
-    assert_eq!(dna::Dna::new("CGA").unwrap(), dna::Dna::new("CGA").unwrap());
-    assert_ne!(dna::Dna::new("CGA").unwrap(), dna::Dna::new("AGC").unwrap());
-    assert_eq!(dna::Rna::new("CGA").unwrap(), dna::Rna::new("CGA").unwrap());
-    assert_ne!(dna::Rna::new("CGA").unwrap(), dna::Rna::new("AGC").unwrap());
-}
+#![no_std]
 
-#[test]
-fn test_transcribes_cytosine_guanine() {
-    assert_eq!(
-        dna::Rna::new("G").unwrap(),
-        dna::Dna::new("C").unwrap().into_rna(&mut [0u8; 1])
-    );
-}
+use ret_tok_rnd_modify_any_store_mut_slc_pass_in_storage as dna;
+use test_harness::api_tests_read_only::with_storage::Tests;
+use test_harness::differential::with_storage::Tests as DifferentialTests;
 
-#[test]
-fn test_transcribes_guanine_cytosine() {
-    assert_eq!(
-        dna::Rna::new("C").unwrap(),
-        dna::Dna::new("G").unwrap().into_rna(&mut [0u8; 2])
-    );
+struct T {}
+impl Tests for T {
+    type Dna<'a> = dna::Dna<'a>;
+    type Rna<'a> = dna::Rna<'a>;
 }
 
-#[test]
-fn test_transcribes_adenine_uracil() {
-    assert_eq!(
-        dna::Rna::new("U").unwrap(),
-        dna::Dna::new("A").unwrap().into_rna(&mut [0u8; 4])
-    );
-}
+impl DifferentialTests for T {}
 
-#[test]
-fn test_transcribes_thymine_to_adenine() {
-    assert_eq!(
-        dna::Rna::new("A").unwrap(),
-        dna::Dna::new("T").unwrap().into_rna(&mut [0u8; 4])
-    );
-}
+test_harness::api_tests!(read_only_with_storage: T);
 
-#[test]
-fn test_transcribes_all_dna_to_rna() {
-    assert_eq!(
-        dna::Rna::new("UGCACCAGAAUU").unwrap(),
-        dna::Dna::new("ACGTGGTCTTAA")
-            .unwrap()
-            .into_rna(&mut [0u8; 12])
-    )
-}
+test_harness::api_tests!(differential_with_storage: T);
@@ -1,53 +1,108 @@
-//! This crate doesn't implement utils::{DnaTrait, RnaTrait}, because the function signature of
-//! [`Dna::into_rna`] here is different - it needs an extra parameter (storage slice).
+//! This crate doesn't implement utils::DnaTrait, because transcription here is different - it needs
+//! an extra parameter (storage slice). See macros [`into_rna`] and [`into_dna`]. Through them it
+//! implements [`DnaTraitWithStorage`] (and [`RnaToDnaTraitWithStorage`]), so that it can run the
+//! shared tests.
 #![no_std]
 
+use core::fmt::{self, Display, Formatter};
 use core::str;
-use utils::{self, checks, OurResult};
+use utils::checks::{self, Alphabet};
+use utils::{self, DnaTraitWithStorage, OurResult, RnaToDnaTraitWithStorage, RnaTrait};
 
 #[macro_export]
 macro_rules! into_rna {
     ($dna:expr, $storage:expr) => {
         // We generate a block {...}, and the last expression is the result Rna instance.
         {
-            // @TODO vairable hygiene  - 2x
-            //
             // NOT using the following, because then the result would be tied to its lifetime:
             //
             // let tmp_storage = $storage;
             //
             // TODO change to: dna.prepare_sto..($storage)
-            let len = Dna::prepare_storage_from_dna($dna, &mut $storage);
-            Rna::from_prepared_storage(&$storage, len)
+            let len = $crate::Dna::prepare_storage_from_dna($dna, &mut $storage);
+            $crate::Rna::from_prepared_storage(&$storage, len)
         }
     };
 }
 
+/// The reverse of [`into_rna`]: reverse transcribe `$rna` into `$storage`, and return [`Dna`].
+#[macro_export]
+macro_rules! into_dna {
+    ($rna:expr, $storage:expr) => {{
+        let len = $crate::Rna::prepare_storage_from_rna($rna, &mut $storage);
+        $crate::Dna::from_prepared_storage(&$storage, len)
+    }};
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Dna<'a>(&'a str);
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Rna<'a>(&'a str);
 
-impl<'a> Dna<'a> {
-    pub fn new(dna: &'a str) -> OurResult<Self> {
+impl<'a> DnaTraitWithStorage<'a, Rna<'a>> for Dna<'a> {
+    fn new(dna: &'a str) -> OurResult<Self> {
         checks::check_dna(dna)?;
         Ok(Self(dna))
     }
 
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, Alphabet::Iupac)?;
+        Ok(Self(dna))
+    }
+
+    fn into_rna(&self, storage: &'a mut [u8]) -> Rna<'a> {
+        into_rna!(self, *storage)
+    }
+}
+
+impl<'a> Dna<'a> {
     /// TODO doc.
     /// Return the UTF-8 length.
-    pub fn prepare_storage_from_dna<'s>(&self, storage: &'s mut [u8]) -> usize {
+    pub fn prepare_storage_from_dna(&self, storage: &mut [u8]) -> usize {
         utils::char_iter_to_bytes(storage, self.0.chars().map(utils::dna_to_rna))
     }
+
+    /// The reverse of [`Rna::from_prepared_storage`]. It takes an immutable storage that was
+    /// previously prepared by [`Rna::prepare_storage_from_rna`].
+    pub fn from_prepared_storage<'s>(prepared_storage: &'s [u8], len: usize) -> Self
+    where
+        's: 'a,
+    {
+        let result = Self(
+            str::from_utf8(&prepared_storage[..len])
+                .expect("UTF-8 encoded string of DNA nucleotides"),
+        );
+        // Prepared from valid RNA of either alphabet. Alphabet::Iupac is a superset.
+        checks::check_dna_with(result.0, Alphabet::Iupac).expect("DNA string");
+        result
+    }
 }
 
-impl<'a> Rna<'a> {
-    pub fn new(rna: &'a str) -> OurResult<Self> {
+impl<'a> RnaTrait<'a> for Rna<'a> {
+    fn new(rna: &'a str) -> OurResult<Self> {
         checks::check_rna_str(rna)?;
         Ok(Self(rna))
     }
 
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str_with(rna, Alphabet::Iupac)?;
+        Ok(Self(rna))
+    }
+}
+
+impl<'a> RnaToDnaTraitWithStorage<'a, Dna<'a>> for Rna<'a> {
+    fn into_dna(&self, storage: &'a mut [u8]) -> Dna<'a> {
+        into_dna!(self, *storage)
+    }
+}
+
+impl<'a> Rna<'a> {
+    /// The reverse of [`Dna::prepare_storage_from_dna`]. Return the UTF-8 length.
+    pub fn prepare_storage_from_rna(&self, storage: &mut [u8]) -> usize {
+        utils::char_iter_to_bytes(storage, self.0.chars().map(utils::rna_to_dna))
+    }
+
     /// This takes an immutable storage that was previously prepared by
     /// [`Dna::prepare_storage_from_dna`]. This separation allows us to use shared slice (instead of
     /// a mutable slice) here.
@@ -59,8 +114,8 @@ impl<'a> Rna<'a> {
             str::from_utf8(&prepared_storage[..len])
                 .expect("UTF-8 encoded string of RNA nucleotides"),
         );
-        // This would not work for Unicode in general.
-        checks::check_rna_str(result.as_str()).expect("RNA string");
+        // This would not work for Unicode in general. Alphabet::Iupac: see Dna::from_prepared_storage.
+        checks::check_rna_str_with(result.as_str(), Alphabet::Iupac).expect("RNA string");
         result
     }
 
@@ -69,6 +124,12 @@ impl<'a> Rna<'a> {
     }
 }
 
+impl<'a> Display for Rna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
 impl<'l, 'r> PartialEq<&Rna<'r>> for Rna<'l> {
     fn eq(&self, other: &&Rna<'r>) -> bool {
         self.as_str() == other.as_str()
@@ -83,7 +144,8 @@ impl<'l, 'r> PartialEq<Rna<'r>> for &Rna<'l> {
 #[cfg(test)]
 pub mod test {
     extern crate alloc;
-    use super::{into_rna, Dna, Rna};
+    use super::{Dna, Rna};
+    use utils::{DnaTraitWithStorage, RnaTrait};
 
     /// Testing that equality is defined for references - because we can't share instances of this
     /// type in any other way.
@@ -1,54 +1,24 @@
+#![no_std]
+
 use dna::into_rna;
 use ret_tok_rnd_modify_ini_store_mix_slc_pass_in_storage_macro as dna;
-// @TODO
-use ret_tok_rnd_modify_ini_store_mix_slc_pass_in_storage_macro::{Dna, Rna};
+use test_harness::api_tests_read_only::with_storage::Tests;
+use test_harness::differential::with_storage::Tests as DifferentialTests;
+use utils::{DnaTraitWithStorage, RnaTrait};
 
-#[test]
-fn test_valid_dna_input() {
-    assert!(dna::Dna::new("GCTA").is_ok());
+struct T {}
+impl Tests for T {
+    type Dna<'a> = dna::Dna<'a>;
+    type Rna<'a> = dna::Rna<'a>;
 }
 
-#[test]
-fn test_valid_rna_input() {
-    assert!(dna::Rna::new("CGAU").is_ok());
-}
+// The implementation of DnaTraitWithStorage::into_rna (and of RnaToDnaTraitWithStorage::into_dna)
+// uses the macros, so this tests them, too.
+test_harness::api_tests!(read_only_with_storage: T);
 
-#[test]
-fn test_invalid_dna_input() {
-    // Invalid character
-    assert_eq!(dna::Dna::new("X").err(), Some(0));
-    // Valid nucleotide, but invalid in context
-    assert_eq!(dna::Dna::new("U").err(), Some(0));
-    // Longer string with contained errors
-    assert_eq!(dna::Dna::new("ACGTUXXCTTAA").err(), Some(4));
-}
+impl DifferentialTests for T {}
 
-#[test]
-fn test_invalid_rna_input() {
-    // Invalid character
-    assert_eq!(dna::Rna::new("X").unwrap_err(), 0);
-    // Valid nucleotide, but invalid in context
-    assert_eq!(dna::Rna::new("T").unwrap_err(), 0);
-    // Longer string with contained errors
-    assert_eq!(dna::Rna::new("ACGUTTXCUUAA").unwrap_err(), 4);
-}
-
-#[test]
-fn test_acid_equals_acid() {
-    assert_eq!(dna::Dna::new("CGA").unwrap(), dna::Dna::new("CGA").unwrap());
-    assert_ne!(dna::Dna::new("CGA").unwrap(), dna::Dna::new("AGC").unwrap());
-    assert_eq!(dna::Rna::new("CGA").unwrap(), dna::Rna::new("CGA").unwrap());
-    assert_ne!(dna::Rna::new("CGA").unwrap(), dna::Rna::new("AGC").unwrap());
-}
-
-#[test]
-fn test_transcribes_cytosine_guanine() {
-    let mut storage = [0u8; 1];
-    assert_eq!(
-        dna::Rna::new("G").unwrap(),
-        into_rna!(&dna::Dna::new("C").unwrap(), storage)
-    );
-}
+test_harness::api_tests!(differential_with_storage: T);
 
 #[test]
 fn test_transcribes_cytosine_guanine_storage_can_be_shared() {
@@ -71,44 +41,8 @@ fn test_transcribes_cytosine_guanine_storage_can_be_shared_expanded() {
     // later _ even though `Rna::from_prepared_storage` uses the given slice as shared only.
     //
     // let rna = Rna::from_prepared_storage(&mut storage, len);
-    let rna = Rna::from_prepared_storage(&storage, len);
+    let rna = dna::Rna::from_prepared_storage(&storage, len);
 
     assert_eq!(storage.len(), 1);
     assert_eq!(dna::Rna::new("G").unwrap(), rna);
 }
-
-#[test]
-fn test_transcribes_guanine_cytosine() {
-    let mut storage = [0u8; 1];
-    assert_eq!(
-        dna::Rna::new("C").unwrap(),
-        into_rna!(&dna::Dna::new("G").unwrap(), storage)
-    );
-}
-
-#[test]
-fn test_transcribes_adenine_uracil() {
-    let mut storage = [0u8; 4];
-    assert_eq!(
-        dna::Rna::new("U").unwrap(),
-        into_rna!(&dna::Dna::new("A").unwrap(), storage)
-    );
-}
-
-#[test]
-fn test_transcribes_thymine_to_adenine() {
-    let mut storage = [0u8; 1];
-    assert_eq!(
-        dna::Rna::new("A").unwrap(),
-        into_rna!(&dna::Dna::new("T").unwrap(), storage)
-    );
-}
-
-#[test]
-fn test_transcribes_all_dna_to_rna() {
-    let mut storage = [0u8; 12];
-    assert_eq!(
-        dna::Rna::new("UGCACCAGAAUU").unwrap(),
-        into_rna!(&dna::Dna::new("ACGTGGTCTTAA").unwrap(), storage)
-    )
-}
@@ -3,14 +3,26 @@
 extern crate alloc;
 
 use alloc::boxed::Box;
-use core::fmt::{self, Debug, Formatter};
-use utils::{checks, DnaTrait, OurResult, RnaTrait};
+use core::fmt::{self, Debug, Display, Formatter};
+use utils::{
+    checks, ComplementTrait, DnaTrait, NucleicAcid, NucleotidesView, OurResult, RnaToDnaTrait,
+    RnaTrait,
+};
 
 /// DNA (DNA nucleotide sequence).
 ///
 /// Implementing [`Eq`] is not necessary for our purpose, but valid.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Dna<'a>(&'a str);
+#[derive(Clone, Copy)]
+pub enum Dna<'a> {
+    /// Represented by given DNA nucleotides. Returned by [`Dna::new`].
+    GivenNucleotides(&'a str),
+    /// Represented by respective RNA nucleotides, but *not* transformed. The reverse of
+    /// [`Rna::DnaBased`]. See [`Dna::iter`].
+    RnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
+}
 
 /// RNA (RNA nucleotide sequence).
 #[derive(Clone, Copy)]
@@ -21,30 +33,125 @@ pub enum Rna<'a> {
     /// type generate RNA nucleotides on the fly by iterating when the consumer calls
     /// [`PartialEq::eq`] or [`Debug::fmt`] on `&self`. See [`Rna::iter`].
     DnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
 }
 
 impl<'a> DnaTrait<'a, Rna<'a>> for Dna<'a> {
     fn new(dna: &'a str) -> OurResult<Self> {
         checks::check_dna(dna)?;
-        Ok(Self(dna))
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(dna))
     }
 
     /// Create a [DNA-based variant of `Rna`](Rna::GivenNucleotides) instance, based on `self`. No
-    /// transformation/iteration is done yet - see [`Rna::DnaBased`].
+    /// transformation/iteration is done yet - see [`Rna::DnaBased`]. An [RNA-based
+    /// `Dna`](Dna::RnaBased) gives back its original [`Rna::GivenNucleotides`].
     fn into_rna(&self) -> Rna<'a> {
-        match self {
-            Dna(dna) => Rna::DnaBased(dna),
+        match *self {
+            Dna::GivenNucleotides(dna) => Rna::DnaBased(dna),
+            Dna::RnaBased(rna) => Rna::GivenNucleotides(rna),
+            Dna::View(view) => Rna::View(view.complement()),
+        }
+    }
+}
+
+impl<'a> Dna<'a> {
+    /// Create an [`Iterator`] over `self`'s DNA nucleotides (chars). The reverse of [`Rna::iter`].
+    fn iter(&self) -> Box<dyn Iterator<Item = char> + 'a> {
+        match *self {
+            Dna::GivenNucleotides(dna) => Box::new(dna.chars()),
+
+            Dna::RnaBased(rna) => Box::new(rna.chars().map(utils::rna_to_dna)),
+
+            Dna::View(view) => Box::new(view.chars(NucleicAcid::Dna)),
         }
     }
 }
 
 impl<'a> RnaTrait<'a> for Rna<'a> {
-    /// Create a new instance with given RNA nucleotides. On error return [`Err`] with a 0-based
-    /// index of the first incorrect character.
+    /// Create a new instance with given RNA nucleotides. On error return [`Err`] with a
+    /// [`NucleotideError`](utils::NucleotideError) locating the first incorrect character.
     fn new(rna: &'a str) -> OurResult<Self> {
         checks::check_rna_str(rna)?;
         Ok(Self::GivenNucleotides(rna))
     }
+
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str_with(rna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(rna))
+    }
+}
+
+impl<'a> RnaToDnaTrait<'a, Dna<'a>> for Rna<'a> {
+    /// Create an [RNA-based variant of `Dna`](Dna::RnaBased) instance, based on `self`. No
+    /// transformation/iteration is done - the reverse of [`Dna::into_rna`].
+    fn into_dna(&self) -> Dna<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => Dna::RnaBased(rna),
+            Rna::DnaBased(dna) => Dna::GivenNucleotides(dna),
+            Rna::View(view) => Dna::View(view.complement()),
+        }
+    }
+}
+
+impl<'a> Dna<'a> {
+    /// `self` as a [`NucleotidesView`] (of DNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Dna::GivenNucleotides(dna) => {
+                NucleotidesView::new(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::RnaBased(rna) => NucleotidesView::new(rna, NucleicAcid::Rna)
+                .expect("RNA nucleotides")
+                .complement(),
+            Dna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Dna<'a> {
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Dna::View(self.as_view().complement())
+    }
+
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Dna::View(self.as_view().reverse_complement())
+    }
+}
+
+impl<'a> Rna<'a> {
+    /// `self` as a [`NucleotidesView`] (of RNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => {
+                NucleotidesView::new(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::DnaBased(dna) => NucleotidesView::new(dna, NucleicAcid::Dna)
+                .expect("DNA nucleotides")
+                .complement(),
+            Rna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Rna<'a> {
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Rna::View(self.as_view().complement())
+    }
+
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Rna::View(self.as_view().reverse_complement())
+    }
 }
 
 impl<'a> Rna<'a> {
@@ -59,6 +166,8 @@ impl<'a> Rna<'a> {
             Rna::GivenNucleotides(rna) => Box::new(rna.chars()),
 
             Rna::DnaBased(dna) => Box::new(dna.chars().map(utils::dna_to_rna)),
+
+            Rna::View(view) => Box::new(view.chars(NucleicAcid::Rna)),
         }
     }
 }
@@ -70,9 +179,30 @@ impl<'a> PartialEq for Rna<'a> {
 }
 impl<'a> Eq for Rna<'a> {}
 
+impl<'a> Display for Rna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        self.iter().try_for_each(|c| write!(f, "{c}"))?;
+        Ok(())
+    }
+}
+
 impl<'a> Debug for Rna<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "Rna(\"")?;
+        write!(f, "Rna(\"{self}\")")
+    }
+}
+
+impl<'a> PartialEq for Dna<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+impl<'a> Eq for Dna<'a> {}
+
+/// Honoring default derived format of a newtype-based implementation.
+impl<'a> Debug for Dna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Dna(\"")?;
         self.iter().try_for_each(|c| write!(f, "{c}"))?;
         write!(f, "\")")
     }
@@ -1,9 +1,10 @@
 #![no_std]
-#![feature(generic_associated_types)]
 
 use ret_tok_seq_non_modify_trans_shr_ref_sta_iter_enum as dna;
 
 use test_harness::api_tests_read_only::Tests;
+use test_harness::differential::Tests as DifferentialTests;
+use utils::{DnaTrait, RnaTrait};
 
 struct T {}
 impl Tests for T {
@@ -11,7 +12,24 @@ impl Tests for T {
     type Rna<'a> = dna::Rna<'a>;
 }
 
+impl DifferentialTests for T {}
+
+test_harness::api_tests!(read_only: T);
+
+test_harness::api_tests!(differential: T);
+
+/// Bytes from a sensor get stored as they are, without a copy.
 #[test]
-fn all_tests() {
-    T::all_tests();
+fn test_new_from_bytes_refers_to_given_bytes() {
+    let dna_data: &[u8] = b"GATTACA";
+    let dna::Dna::GivenBytes(dna) = dna::Dna::new_from_bytes(dna_data).unwrap() else {
+        panic!("Dna::GivenBytes expected");
+    };
+    assert_eq!(dna.as_ptr(), dna_data.as_ptr());
+
+    let rna_data: &[u8] = b"CUAAUGU";
+    let dna::Rna::GivenBytes(rna) = dna::Rna::new_from_bytes(rna_data).unwrap() else {
+        panic!("Rna::GivenBytes expected");
+    };
+    assert_eq!(rna.as_ptr(), rna_data.as_ptr());
 }
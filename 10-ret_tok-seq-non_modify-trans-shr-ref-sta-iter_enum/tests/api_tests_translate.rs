@@ -0,0 +1,12 @@
+#![no_std]
+
+use ret_tok_seq_non_modify_trans_shr_ref_sta_iter_enum as dna;
+use test_harness::api_tests_translate::Tests;
+
+struct T {}
+impl Tests for T {
+    type Dna<'a> = dna::Dna<'a>;
+    type Rna<'a> = dna::Rna<'a>;
+}
+
+test_harness::api_tests!(translate: T);
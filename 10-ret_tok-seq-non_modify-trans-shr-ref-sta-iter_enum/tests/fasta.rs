@@ -0,0 +1,57 @@
+use ret_tok_seq_non_modify_trans_shr_ref_sta_iter_enum as dna;
+use utils::fasta::{FastaError, FastaReader, FastaWriter};
+use utils::{checks, DnaTrait, RnaTrait};
+
+const INPUT: &[u8] = b">seq1 multi-line\nACGTGG\nTCTTAA\n>seq2\r\nGCTA\r\n";
+
+/// Multi-line sequences become lazy [`dna::Dna::View`]s, without copying.
+#[test]
+fn test_read_dna() {
+    let mut records = FastaReader::new(INPUT);
+    let record = records.next().unwrap().unwrap();
+    assert_eq!((record.id, record.description), ("seq1", "multi-line"));
+    let dna = dna::Dna::View(record.dna().unwrap());
+    assert_eq!(dna, dna::Dna::new("ACGTGGTCTTAA").unwrap());
+    assert_eq!(dna.into_rna(), dna::Rna::new("UGCACCAGAAUU").unwrap());
+
+    let record = records.next().unwrap().unwrap();
+    assert_eq!(
+        dna::Dna::View(record.dna().unwrap()),
+        dna::Dna::new("GCTA").unwrap()
+    );
+    assert!(records.next().is_none());
+}
+
+#[test]
+fn test_invalid_dna() {
+    let record = FastaReader::new(b">seq\nACGT\nACUT\n")
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        record.dna(),
+        Err(FastaError::InvalidNucleotide {
+            record: 0,
+            line: 2,
+            column: 2,
+            found: 'U',
+            expected_alphabet: checks::DNA_NUCLEOTIDES,
+        })
+    );
+}
+
+#[test]
+fn test_write_rna() {
+    let mut writer = FastaWriter::new(String::new(), 5);
+    for record in FastaReader::new(INPUT) {
+        let record = record.unwrap();
+        let rna = dna::Dna::View(record.dna().unwrap()).into_rna();
+        writer
+            .write_record(record.id, record.description, &rna)
+            .unwrap();
+    }
+    assert_eq!(
+        writer.into_inner(),
+        ">seq1 multi-line\nUGCAC\nCAGAA\nUU\n>seq2\nCGAU\n"
+    );
+}
@@ -1,61 +1,257 @@
 //! no_std heapless (bare metal/embedded-friendly)
 #![no_std]
 
-use core::fmt::{self, Debug, Formatter};
-use utils::{checks, DnaTrait, OurResult, RnaTrait};
+use core::fmt::{self, Debug, Display, Formatter};
+use utils::protein::{Protein, Translation};
+use utils::{
+    checks, ComplementTrait, DnaTrait, NucleicAcid, NucleotidesView, OurResult, RnaToDnaTrait,
+    RnaTrait, TranslateTrait,
+};
 
 /// DNA (DNA nucleotide sequence).  
 /// Implementing [`Eq`] is not necessary, but valid.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Dna<'a>(&'a str);
+#[derive(Clone, Copy)]
+pub enum Dna<'a> {
+    /// Represented by given DNA nucleotides. Returned by [`Dna::new`].
+    GivenNucleotides(&'a str),
+    /// Represented by given DNA nucleotides as ASCII bytes, *not* converted to `&str`. Returned by
+    /// [`Dna::new_from_bytes`].
+    GivenBytes(&'a [u8]),
+    /// Represented by respective RNA nucleotides, but *not* transformed. The reverse of
+    /// [`Rna::DnaBased`]. See [`Dna::iter`].
+    RnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
+}
 
 #[derive(Clone, Copy)]
 pub enum Rna<'a> {
     /// Represented by given RNA nucleotides. Returned by [`Rna::new`].
     GivenNucleotides(&'a str),
+    /// Represented by given RNA nucleotides as ASCII bytes. Returned by [`Rna::new_from_bytes`].
+    GivenBytes(&'a [u8]),
     /// Represented by respective DNA nucleotides, but *not* transformed. Instead, methods of this
     /// type generate RNA nucleotides on the fly by iterating when the consumer calls
     /// [`PartialEq::eq`] or [`Debug::fmt`] on `&self`. See [`Rna::iter`].
     DnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
 }
 
 impl<'a> DnaTrait<'a, Rna<'a>> for Dna<'a> {
     /// Create a new [`Dna`] instance with given DNA nucleotides. If `dna` is valid, return  
-    /// [`Some(Dna)`](Some<Dna>) containing the new instance. On error return [`Err`] with a 0-based
-    /// index of the first incorrect character.
+    /// [`Some(Dna)`](Some<Dna>) containing the new instance. On error return [`Err`] with a
+    /// [`NucleotideError`](utils::NucleotideError) locating the first incorrect character.
     fn new(dna: &'a str) -> OurResult<Self> {
         checks::check_dna(dna)?;
-        Ok(Self(dna))
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    /// Create a [`Dna::GivenBytes`] instance. The error index is the same as for [`Dna::new`],
+    /// since (valid) nucleotides are ASCII.
+    fn new_from_bytes(dna: &'a [u8]) -> OurResult<Self> {
+        checks::check_dna_bytes(dna)?;
+        Ok(Self::GivenBytes(dna))
     }
 
     /// Create a [DNA-based variant of `Rna`](Rna::GivenNucleotides) instance, based on `self`. No
-    /// transformation/iteration is done yet - see [`Rna::DnaBased`].
+    /// transformation/iteration is done yet - see [`Rna::DnaBased`]. An [RNA-based
+    /// `Dna`](Dna::RnaBased) gives back its original [`Rna::GivenNucleotides`].
     fn into_rna(&self) -> Rna<'a> {
-        match self {
-            Dna(dna) => Rna::DnaBased(dna),
+        match *self {
+            Dna::GivenNucleotides(dna) => Rna::DnaBased(dna),
+            Dna::RnaBased(rna) => Rna::GivenNucleotides(rna),
+            Dna::GivenBytes(_) | Dna::View(_) => Rna::View(self.as_view().complement()),
+        }
+    }
+}
+
+impl<'a> Dna<'a> {
+    /// Get the stored nucleotides (DNA for [Dna::GivenNucleotides], or RNA for [Dna::RnaBased]).
+    /// Use together with [`Dna::is_rna_based`]. Empty for [Dna::GivenBytes] and [Dna::View] - see
+    /// [`Dna::given_bytes`] and [`Dna::viewed_nucleotides`].
+    fn stored_nucleotides(&self) -> &'a str {
+        match *self {
+            Self::GivenNucleotides(dna) => dna,
+            Self::RnaBased(rna) => rna,
+            Self::GivenBytes(_) | Self::View(_) => "",
+        }
+    }
+
+    /// Iterate over nucleotides of [Dna::GivenBytes], or over nothing for other variants.
+    fn given_bytes(&self) -> impl Iterator<Item = char> + 'a {
+        match *self {
+            Self::GivenBytes(dna) => dna,
+            _ => &[],
+        }
+        .iter()
+        .map(|&b| char::from(b))
+    }
+
+    /// Iterate over nucleotides of [Dna::View], or over nothing for other variants.
+    fn viewed_nucleotides(&self) -> impl Iterator<Item = char> + 'a {
+        match *self {
+            Self::View(view) => Some(view.chars(NucleicAcid::Dna)),
+            _ => None,
         }
+        .into_iter()
+        .flatten()
+    }
+
+    fn is_rna_based(&self) -> bool {
+        matches!(*self, Self::RnaBased(_))
+    }
+
+    /// Create an [`Iterator`] over `self`'s DNA nucleotides (chars). The reverse of [`Rna::iter`].
+    fn iter(&self) -> impl Iterator<Item = char> + '_ {
+        self.stored_nucleotides()
+            .chars()
+            .map(|c| {
+                if self.is_rna_based() {
+                    utils::rna_to_dna(c)
+                } else {
+                    c
+                }
+            })
+            .chain(self.given_bytes())
+            .chain(self.viewed_nucleotides())
     }
 }
 
 impl<'a> RnaTrait<'a> for Rna<'a> {
     /// Create a new [`Rna`] instance with given RNA nucleotides -[`Rna::GivenNucleotides`] variant.
     /// If `rna` is valid, return  
-    /// [`Some(Rna)`](Some<Rna>) containing the new instance. On error return [`Err`] with a 0-based
-    /// index of the first incorrect character.
+    /// [`Some(Rna)`](Some<Rna>) containing the new instance. On error return [`Err`] with a
+    /// [`NucleotideError`](utils::NucleotideError) locating the first incorrect character.
     fn new(rna: &'a str) -> OurResult<Self> {
         checks::check_rna_str(rna)?;
         Ok(Self::GivenNucleotides(rna))
     }
+
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str_with(rna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(rna))
+    }
+
+    /// Create an [`Rna::GivenBytes`] instance. See [`Dna::new_from_bytes`].
+    fn new_from_bytes(rna: &'a [u8]) -> OurResult<Self> {
+        checks::check_rna_bytes(rna)?;
+        Ok(Self::GivenBytes(rna))
+    }
+}
+
+impl<'a> RnaToDnaTrait<'a, Dna<'a>> for Rna<'a> {
+    /// Create an [RNA-based variant of `Dna`](Dna::RnaBased) instance, based on `self`. No
+    /// transformation/iteration is done - the reverse of [`Dna::into_rna`].
+    fn into_dna(&self) -> Dna<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => Dna::RnaBased(rna),
+            Rna::DnaBased(dna) => Dna::GivenNucleotides(dna),
+            Rna::GivenBytes(_) | Rna::View(_) => Dna::View(self.as_view().complement()),
+        }
+    }
+}
+
+impl<'a> Dna<'a> {
+    /// `self` as a [`NucleotidesView`] (of DNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Dna::GivenNucleotides(dna) => {
+                NucleotidesView::new(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::GivenBytes(dna) => {
+                NucleotidesView::new_from_bytes(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::RnaBased(rna) => NucleotidesView::new(rna, NucleicAcid::Rna)
+                .expect("RNA nucleotides")
+                .complement(),
+            Dna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Dna<'a> {
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Dna::View(self.as_view().complement())
+    }
+
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Dna::View(self.as_view().reverse_complement())
+    }
+}
+
+impl<'a> Rna<'a> {
+    /// `self` as a [`NucleotidesView`] (of RNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => {
+                NucleotidesView::new(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::GivenBytes(rna) => {
+                NucleotidesView::new_from_bytes(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::DnaBased(dna) => NucleotidesView::new(dna, NucleicAcid::Dna)
+                .expect("DNA nucleotides")
+                .complement(),
+            Rna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Rna<'a> {
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Rna::View(self.as_view().complement())
+    }
+
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Rna::View(self.as_view().reverse_complement())
+    }
 }
 
 impl<'a> Rna<'a> {
     /// Get the stored nucleotides (RNA for[Rna::GivenNucleotides], or DNA for [Rna::DnaBased]). Use
-    /// together with [`Rna::is_dna_based`].
+    /// together with [`Rna::is_dna_based`]. Empty for [Rna::GivenBytes] and [Rna::View] - see
+    /// [`Rna::given_bytes`] and [`Rna::viewed_nucleotides`].
     fn stored_nucleotides(&self) -> &'a str {
         match *self {
             Self::GivenNucleotides(rna) => rna,
             Self::DnaBased(dna) => dna,
+            Self::GivenBytes(_) | Self::View(_) => "",
+        }
+    }
+
+    /// Iterate over nucleotides of [Rna::GivenBytes], or over nothing for other variants. Like
+    /// [`Rna::viewed_nucleotides`], an empty slice is an empty iterator of the same type.
+    fn given_bytes(&self) -> impl Iterator<Item = char> + 'a {
+        match *self {
+            Self::GivenBytes(rna) => rna,
+            _ => &[],
         }
+        .iter()
+        .map(|&b| char::from(b))
+    }
+
+    /// Iterate over nucleotides of [Rna::View], or over nothing for other variants. Unlike
+    /// [`Rna::stored_nucleotides`], [Rna::View] can't be represented by a `&str`. But an empty
+    /// [`Option`] is an empty iterator of the same type, so [`Rna::iter`] can chain both.
+    fn viewed_nucleotides(&self) -> impl Iterator<Item = char> + 'a {
+        match *self {
+            Self::View(view) => Some(view.chars(NucleicAcid::Rna)),
+            _ => None,
+        }
+        .into_iter()
+        .flatten()
     }
 
     fn is_dna_based(&self) -> bool {
@@ -71,13 +267,27 @@ impl<'a> Rna<'a> {
     /// `_` which indicates _lifetime elision_. Thanks to
     /// https://robinmoussu.gitlab.io/blog/post/2021-03-25_rust_iterators_tips_and_tricks.
     fn iter(&self) -> impl Iterator<Item = char> + '_ {
-        self.stored_nucleotides().chars().map(|c| {
-            if self.is_dna_based() {
-                utils::dna_to_rna(c)
-            } else {
-                c
-            }
-        })
+        self.stored_nucleotides()
+            .chars()
+            .map(|c| {
+                if self.is_dna_based() {
+                    utils::dna_to_rna(c)
+                } else {
+                    c
+                }
+            })
+            .chain(self.given_bytes())
+            .chain(self.viewed_nucleotides())
+    }
+}
+
+impl<'a> TranslateTrait for Rna<'a> {
+    fn translate_into<B: AsRef<[u8]> + AsMut<[u8]>>(
+        &self,
+        buffer: B,
+        translation: Translation,
+    ) -> OurResult<Protein<B>> {
+        Protein::new_from_rna(buffer, self.iter(), translation)
     }
 }
 
@@ -88,9 +298,29 @@ impl<'a> PartialEq for Rna<'a> {
 }
 impl<'a> Eq for Rna<'a> {}
 
+impl<'a> Display for Rna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        self.iter().try_for_each(|c| write!(f, "{c}"))?;
+        Ok(())
+    }
+}
+
 impl<'a> Debug for Rna<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "Rna(\"")?;
+        write!(f, "Rna(\"{self}\")")
+    }
+}
+
+impl<'a> PartialEq for Dna<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+impl<'a> Eq for Dna<'a> {}
+
+impl<'a> Debug for Dna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Dna(\"")?;
         self.iter().try_for_each(|c| write!(f, "{c}"))?;
         write!(f, "\")")
     }
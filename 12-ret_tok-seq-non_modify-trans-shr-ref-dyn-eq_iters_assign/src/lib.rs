@@ -1,27 +1,60 @@
 //! no_std heapless (bare metal/embedded-friendly) implementation
 #![no_std]
 
-use core::fmt::{self, Debug, Formatter};
-use utils::{checks, DnaTrait, OurResult, RnaTrait};
+use core::fmt::{self, Debug, Display, Formatter};
+use utils::protein::{Protein, Translation};
+use utils::{
+    checks, ComplementTrait, DnaTrait, NucleicAcid, NucleotidesView, OurResult, RnaToDnaTrait,
+    RnaTrait, TranslateTrait,
+};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Dna<'a>(&'a str);
+#[derive(Clone, Copy)]
+pub enum Dna<'a> {
+    GivenNucleotides(&'a str),
+    /// Represented by given DNA nucleotides as ASCII bytes, *not* converted to `&str`. Returned by
+    /// [`Dna::new_from_bytes`].
+    GivenBytes(&'a [u8]),
+    // Original RNA nucleotides, reverse transcribed on the fly. See `RnaToDnaTrait for Rna`.
+    RnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
+}
 
 #[derive(Clone, Copy)]
 pub enum Rna<'a> {
     GivenNucleotides(&'a str),
+    /// Represented by given RNA nucleotides as ASCII bytes. Returned by [`Rna::new_from_bytes`].
+    GivenBytes(&'a [u8]),
     DnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
 }
 
 impl<'a> DnaTrait<'a, Rna<'a>> for Dna<'a> {
     fn new(dna: &'a str) -> OurResult<Self> {
         checks::check_dna(dna)?;
-        Ok(Self(dna))
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    /// Create a [`Dna::GivenBytes`] instance. The error index is the same as for [`Dna::new`],
+    /// since (valid) nucleotides are ASCII.
+    fn new_from_bytes(dna: &'a [u8]) -> OurResult<Self> {
+        checks::check_dna_bytes(dna)?;
+        Ok(Self::GivenBytes(dna))
     }
 
     fn into_rna(&self) -> Rna<'a> {
-        match self {
-            Dna(dna) => Rna::DnaBased(dna),
+        match *self {
+            Dna::GivenNucleotides(dna) => Rna::DnaBased(dna),
+            Dna::RnaBased(rna) => Rna::GivenNucleotides(rna),
+            Dna::GivenBytes(_) | Dna::View(_) => Rna::View(self.as_view().complement()),
         }
     }
 }
@@ -31,6 +64,117 @@ impl<'a> RnaTrait<'a> for Rna<'a> {
         checks::check_rna_str(rna)?;
         Ok(Self::GivenNucleotides(rna))
     }
+
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str_with(rna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(rna))
+    }
+
+    /// Create an [`Rna::GivenBytes`] instance. See [`Dna::new_from_bytes`].
+    fn new_from_bytes(rna: &'a [u8]) -> OurResult<Self> {
+        checks::check_rna_bytes(rna)?;
+        Ok(Self::GivenBytes(rna))
+    }
+}
+
+impl<'a> RnaToDnaTrait<'a, Dna<'a>> for Rna<'a> {
+    fn into_dna(&self) -> Dna<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => Dna::RnaBased(rna),
+            Rna::DnaBased(dna) => Dna::GivenNucleotides(dna),
+            Rna::GivenBytes(_) | Rna::View(_) => Dna::View(self.as_view().complement()),
+        }
+    }
+}
+
+impl<'a> Dna<'a> {
+    /// `self` as a [`NucleotidesView`] (of DNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Dna::GivenNucleotides(dna) => {
+                NucleotidesView::new(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::GivenBytes(dna) => {
+                NucleotidesView::new_from_bytes(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::RnaBased(rna) => NucleotidesView::new(rna, NucleicAcid::Rna)
+                .expect("RNA nucleotides")
+                .complement(),
+            Dna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Dna<'a> {
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Dna::View(self.as_view().complement())
+    }
+
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Dna::View(self.as_view().reverse_complement())
+    }
+}
+
+impl<'a> Rna<'a> {
+    /// `self` as a [`NucleotidesView`] (of RNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => {
+                NucleotidesView::new(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::GivenBytes(rna) => {
+                NucleotidesView::new_from_bytes(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::DnaBased(dna) => NucleotidesView::new(dna, NucleicAcid::Dna)
+                .expect("DNA nucleotides")
+                .complement(),
+            Rna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Rna<'a> {
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Rna::View(self.as_view().complement())
+    }
+
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Rna::View(self.as_view().reverse_complement())
+    }
+}
+
+impl<'a> TranslateTrait for Rna<'a> {
+    fn translate_into<B: AsRef<[u8]> + AsMut<[u8]>>(
+        &self,
+        buffer: B,
+        translation: Translation,
+    ) -> OurResult<Protein<B>> {
+        // The same `&mut dyn` technique as in `PartialEq for Rna`.
+        let (mut rna_chars, mut bytes_chars, mut dna_chars_mapped, mut view_chars);
+        let chars: &mut dyn Iterator<Item = char> = match self {
+            Self::GivenNucleotides(rna) => {
+                rna_chars = rna.chars();
+                &mut rna_chars
+            }
+            Self::GivenBytes(rna) => {
+                bytes_chars = rna.iter().map(|&b| char::from(b));
+                &mut bytes_chars
+            }
+            Self::DnaBased(dna) => {
+                dna_chars_mapped = dna.chars().map(utils::dna_to_rna);
+                &mut dna_chars_mapped
+            }
+            Self::View(view) => {
+                view_chars = view.chars(NucleicAcid::Rna);
+                &mut view_chars
+            }
+        };
+        Protein::new_from_rna(buffer, chars, translation)
+    }
 }
 
 impl<'a> PartialEq for Rna<'a> {
@@ -41,31 +185,55 @@ impl<'a> PartialEq for Rna<'a> {
         // store them separately. But we can store a reference to either, cast as `&dyn
         // Iterator<...>`, and store that `&dyn` in the same variable. We do so for both `self`
         // and `other`.
-        let (mut self_rna_chars, mut self_dna_chars_mapped);
-        let (mut other_rna_chars, mut other_dna_chars_mapped);
-        let self_chars: &mut dyn Iterator<Item = char>;
-        let other_chars: &mut dyn Iterator<Item = char>;
+        let (
+            mut self_rna_chars,
+            mut self_bytes_chars,
+            mut self_dna_chars_mapped,
+            mut self_view_chars,
+        );
+        let (
+            mut other_rna_chars,
+            mut other_bytes_chars,
+            mut other_dna_chars_mapped,
+            mut other_view_chars,
+        );
 
         // @TODO factor to a method
-        self_chars = match self {
+        let self_chars: &mut dyn Iterator<Item = char> = match self {
             Self::GivenNucleotides(rna) => {
                 self_rna_chars = rna.chars();
                 &mut self_rna_chars
             }
+            Self::GivenBytes(rna) => {
+                self_bytes_chars = rna.iter().map(|&b| char::from(b));
+                &mut self_bytes_chars
+            }
             Self::DnaBased(dna) => {
                 self_dna_chars_mapped = dna.chars().map(utils::dna_to_rna);
                 &mut self_dna_chars_mapped
             }
+            Self::View(view) => {
+                self_view_chars = view.chars(NucleicAcid::Rna);
+                &mut self_view_chars
+            }
         };
-        other_chars = match other {
+        let other_chars: &mut dyn Iterator<Item = char> = match other {
             Self::GivenNucleotides(rna) => {
                 other_rna_chars = rna.chars();
                 &mut other_rna_chars
             }
+            Self::GivenBytes(rna) => {
+                other_bytes_chars = rna.iter().map(|&b| char::from(b));
+                &mut other_bytes_chars
+            }
             Self::DnaBased(dna) => {
                 other_dna_chars_mapped = dna.chars().map(utils::dna_to_rna);
                 &mut other_dna_chars_mapped
             }
+            Self::View(view) => {
+                other_view_chars = view.chars(NucleicAcid::Rna);
+                &mut other_view_chars
+            }
         };
         // This &dyn call adds a dynamic dispatch overhead (once for the left side: `self`, and
         // multiple times for the right side: `other`), but the code may be clearer than in
@@ -76,13 +244,16 @@ impl<'a> PartialEq for Rna<'a> {
 }
 impl<'a> Eq for Rna<'a> {}
 
-impl<'a> Debug for Rna<'a> {
+impl<'a> Display for Rna<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "Rna(\"")?;
         match self {
             Rna::GivenNucleotides(rna) => {
                 write!(f, "{rna}")?;
             }
+            Rna::GivenBytes(rna) => {
+                rna.iter()
+                    .try_for_each(|&b| write!(f, "{}", char::from(b)))?;
+            }
             Rna::DnaBased(dna) => {
                 // Compared to ../../no_std-no_heap-slices-iterator (TODO update <--) here we don't
                 // have self.iter(). So we map dna to rna here:
@@ -90,6 +261,98 @@ impl<'a> Debug for Rna<'a> {
                     .map(utils::dna_to_rna)
                     .try_for_each(|c| write!(f, "{c}"))?;
             }
+            Rna::View(view) => {
+                view.chars(NucleicAcid::Rna)
+                    .try_for_each(|c| write!(f, "{c}"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Debug for Rna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Rna(\"{self}\")")
+    }
+}
+
+impl<'a> PartialEq for Dna<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        // The same technique as in `PartialEq for Rna`, but mapping in reverse.
+        let (
+            mut self_dna_chars,
+            mut self_bytes_chars,
+            mut self_rna_chars_mapped,
+            mut self_view_chars,
+        );
+        let (
+            mut other_dna_chars,
+            mut other_bytes_chars,
+            mut other_rna_chars_mapped,
+            mut other_view_chars,
+        );
+
+        let self_chars: &mut dyn Iterator<Item = char> = match self {
+            Self::GivenNucleotides(dna) => {
+                self_dna_chars = dna.chars();
+                &mut self_dna_chars
+            }
+            Self::GivenBytes(dna) => {
+                self_bytes_chars = dna.iter().map(|&b| char::from(b));
+                &mut self_bytes_chars
+            }
+            Self::RnaBased(rna) => {
+                self_rna_chars_mapped = rna.chars().map(utils::rna_to_dna);
+                &mut self_rna_chars_mapped
+            }
+            Self::View(view) => {
+                self_view_chars = view.chars(NucleicAcid::Dna);
+                &mut self_view_chars
+            }
+        };
+        let other_chars: &mut dyn Iterator<Item = char> = match other {
+            Self::GivenNucleotides(dna) => {
+                other_dna_chars = dna.chars();
+                &mut other_dna_chars
+            }
+            Self::GivenBytes(dna) => {
+                other_bytes_chars = dna.iter().map(|&b| char::from(b));
+                &mut other_bytes_chars
+            }
+            Self::RnaBased(rna) => {
+                other_rna_chars_mapped = rna.chars().map(utils::rna_to_dna);
+                &mut other_rna_chars_mapped
+            }
+            Self::View(view) => {
+                other_view_chars = view.chars(NucleicAcid::Dna);
+                &mut other_view_chars
+            }
+        };
+        self_chars.eq(other_chars)
+    }
+}
+impl<'a> Eq for Dna<'a> {}
+
+impl<'a> Debug for Dna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Dna(\"")?;
+        match self {
+            Dna::GivenNucleotides(dna) => {
+                write!(f, "{dna}")?;
+            }
+            Dna::GivenBytes(dna) => {
+                dna.iter()
+                    .try_for_each(|&b| write!(f, "{}", char::from(b)))?;
+            }
+            Dna::RnaBased(rna) => {
+                rna.chars()
+                    .map(utils::rna_to_dna)
+                    .try_for_each(|c| write!(f, "{c}"))?;
+            }
+            Dna::View(view) => {
+                view.chars(NucleicAcid::Dna)
+                    .try_for_each(|c| write!(f, "{c}"))?;
+            }
         }
         write!(f, "\")")
     }
@@ -1,9 +1,9 @@
 #![no_std]
-#![feature(generic_associated_types)]
 
 use ret_tok_seq_non_modify_trans_shr_ref_dyn_eq_iters_assign as dna;
 
 use test_harness::api_tests_read_only::Tests;
+use test_harness::differential::Tests as DifferentialTests;
 
 struct T {}
 impl Tests for T {
@@ -11,7 +11,8 @@ impl Tests for T {
     type Rna<'a> = dna::Rna<'a>;
 }
 
-#[test]
-fn all_tests() {
-    T::all_tests();
-}
+impl DifferentialTests for T {}
+
+test_harness::api_tests!(read_only: T);
+
+test_harness::api_tests!(differential: T);
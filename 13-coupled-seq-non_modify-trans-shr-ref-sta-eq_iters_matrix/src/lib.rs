@@ -1,37 +1,173 @@
 #![no_std]
 
-use core::fmt::{self, Debug, Formatter};
-use utils::{checks, DnaTrait, OurResult, RnaTrait};
+use core::fmt::{self, Debug, Display, Formatter};
+use utils::protein::{Protein, Translation};
+use utils::{
+    checks, ComplementTrait, DnaTrait, NucleicAcid, NucleotidesView, OurResult, RnaToDnaTrait,
+    RnaTrait, TranslateTrait,
+};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Dna<'a>(&'a str);
+#[derive(Clone, Copy)]
+pub enum Dna<'a> {
+    GivenNucleotides(&'a str),
+    /// Represented by given DNA nucleotides as ASCII bytes, *not* converted to `&str`. Returned by
+    /// [`Dna::new_from_bytes`].
+    GivenBytes(&'a [u8]),
+    // Original RNA nucleotides, reverse transcribed on the fly. See `RnaToDnaTrait for Rna`.
+    RnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
+}
 
 #[derive(Clone, Copy)]
 pub enum Rna<'a> {
     GivenNucleotides(&'a str),
+    /// Represented by given RNA nucleotides as ASCII bytes. Returned by [`Rna::new_from_bytes`].
+    GivenBytes(&'a [u8]),
     DnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
 }
 
 impl<'a> DnaTrait<'a, Rna<'a>> for Dna<'a> {
-    /** On error return Err with a 0-based index of the first incorrect character. */
+    /** On error return Err with a NucleotideError locating the first incorrect character. */
     fn new(dna: &'a str) -> OurResult<Self> {
         checks::check_dna(dna)?;
-        Ok(Self(dna))
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    /// Create a [`Dna::GivenBytes`] instance. The error index is the same as for [`Dna::new`],
+    /// since (valid) nucleotides are ASCII.
+    fn new_from_bytes(dna: &'a [u8]) -> OurResult<Self> {
+        checks::check_dna_bytes(dna)?;
+        Ok(Self::GivenBytes(dna))
     }
 
     fn into_rna(&self) -> Rna<'a> {
-        match self {
-            Dna(dna) => Rna::DnaBased(dna),
+        match *self {
+            Dna::GivenNucleotides(dna) => Rna::DnaBased(dna),
+            Dna::RnaBased(rna) => Rna::GivenNucleotides(rna),
+            Dna::GivenBytes(_) | Dna::View(_) => Rna::View(self.as_view().complement()),
         }
     }
 }
 
 impl<'a> RnaTrait<'a> for Rna<'a> {
-    /** On error return Err with a 0-based index of the first incorrect character. */
+    /** On error return Err with a NucleotideError locating the first incorrect character. */
     fn new(rna: &'a str) -> OurResult<Self> {
         checks::check_rna_str(rna)?;
         Ok(Self::GivenNucleotides(rna))
     }
+
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str_with(rna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(rna))
+    }
+
+    /// Create an [`Rna::GivenBytes`] instance. See [`Dna::new_from_bytes`].
+    fn new_from_bytes(rna: &'a [u8]) -> OurResult<Self> {
+        checks::check_rna_bytes(rna)?;
+        Ok(Self::GivenBytes(rna))
+    }
+}
+
+impl<'a> RnaToDnaTrait<'a, Dna<'a>> for Rna<'a> {
+    fn into_dna(&self) -> Dna<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => Dna::RnaBased(rna),
+            Rna::DnaBased(dna) => Dna::GivenNucleotides(dna),
+            Rna::GivenBytes(_) | Rna::View(_) => Dna::View(self.as_view().complement()),
+        }
+    }
+}
+
+impl<'a> Dna<'a> {
+    /// `self` as a [`NucleotidesView`] (of DNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Dna::GivenNucleotides(dna) => {
+                NucleotidesView::new(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::GivenBytes(dna) => {
+                NucleotidesView::new_from_bytes(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::RnaBased(rna) => NucleotidesView::new(rna, NucleicAcid::Rna)
+                .expect("RNA nucleotides")
+                .complement(),
+            Dna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Dna<'a> {
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Dna::View(self.as_view().complement())
+    }
+
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Dna::View(self.as_view().reverse_complement())
+    }
+}
+
+impl<'a> Rna<'a> {
+    /// `self` as a [`NucleotidesView`] (of RNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => {
+                NucleotidesView::new(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::GivenBytes(rna) => {
+                NucleotidesView::new_from_bytes(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::DnaBased(dna) => NucleotidesView::new(dna, NucleicAcid::Dna)
+                .expect("DNA nucleotides")
+                .complement(),
+            Rna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Rna<'a> {
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Rna::View(self.as_view().complement())
+    }
+
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Rna::View(self.as_view().reverse_complement())
+    }
+}
+
+impl<'a> TranslateTrait for Rna<'a> {
+    fn translate_into<B: AsRef<[u8]> + AsMut<[u8]>>(
+        &self,
+        buffer: B,
+        translation: Translation,
+    ) -> OurResult<Protein<B>> {
+        // Only one side here, so no matrix: each branch has its own iterator type.
+        match self {
+            Self::GivenNucleotides(rna) => Protein::new_from_rna(buffer, rna.chars(), translation),
+            Self::GivenBytes(rna) => {
+                Protein::new_from_rna(buffer, rna.iter().map(|&b| char::from(b)), translation)
+            }
+            Self::DnaBased(dna) => {
+                Protein::new_from_rna(buffer, dna.chars().map(utils::dna_to_rna), translation)
+            }
+            Self::View(view) => {
+                Protein::new_from_rna(buffer, view.chars(NucleicAcid::Rna), translation)
+            }
+        }
+    }
 }
 
 impl<'a> PartialEq for Rna<'a> {
@@ -55,18 +191,60 @@ impl<'a> PartialEq for Rna<'a> {
                 // No need to map both of them - their DNA must be the same, too
                 self_dna.chars().eq(other_dna.chars())
             }
+            (Self::GivenNucleotides(self_rna), Self::View(other_view)) => {
+                self_rna.chars().eq(other_view.chars(NucleicAcid::Rna))
+            }
+            (Self::DnaBased(self_dna), Self::View(other_view)) => self_dna
+                .chars()
+                .map(utils::dna_to_rna)
+                .eq(other_view.chars(NucleicAcid::Rna)),
+            (Self::View(self_view), Self::GivenNucleotides(other_rna)) => {
+                self_view.chars(NucleicAcid::Rna).eq(other_rna.chars())
+            }
+            (Self::View(self_view), Self::DnaBased(other_dna)) => self_view
+                .chars(NucleicAcid::Rna)
+                .eq(other_dna.chars().map(utils::dna_to_rna)),
+            (Self::View(self_view), Self::View(other_view)) => self_view
+                .chars(NucleicAcid::Rna)
+                .eq(other_view.chars(NucleicAcid::Rna)),
+            // Both are ASCII, so no need to iterate over chars.
+            (Self::GivenBytes(self_rna), Self::GivenNucleotides(other_rna)) => {
+                *self_rna == other_rna.as_bytes()
+            }
+            (Self::GivenBytes(self_rna), Self::GivenBytes(other_rna)) => self_rna == other_rna,
+            (Self::GivenBytes(self_rna), Self::DnaBased(other_dna)) => self_rna
+                .iter()
+                .map(|&b| char::from(b))
+                .eq(other_dna.chars().map(utils::dna_to_rna)),
+            (Self::GivenBytes(self_rna), Self::View(other_view)) => self_rna
+                .iter()
+                .map(|&b| char::from(b))
+                .eq(other_view.chars(NucleicAcid::Rna)),
+            (Self::GivenNucleotides(self_rna), Self::GivenBytes(other_rna)) => {
+                self_rna.as_bytes() == *other_rna
+            }
+            (Self::DnaBased(self_dna), Self::GivenBytes(other_rna)) => self_dna
+                .chars()
+                .map(utils::dna_to_rna)
+                .eq(other_rna.iter().map(|&b| char::from(b))),
+            (Self::View(self_view), Self::GivenBytes(other_rna)) => self_view
+                .chars(NucleicAcid::Rna)
+                .eq(other_rna.iter().map(|&b| char::from(b))),
         }
     }
 }
 impl<'a> Eq for Rna<'a> {}
 
-impl<'a> Debug for Rna<'a> {
+impl<'a> Display for Rna<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "Rna(\"")?;
         match self {
             Rna::GivenNucleotides(rna) => {
                 write!(f, "{rna}")?;
             }
+            Rna::GivenBytes(rna) => {
+                rna.iter()
+                    .try_for_each(|&b| write!(f, "{}", char::from(b)))?;
+            }
             Rna::DnaBased(dna) => {
                 // Compared to ../../no_std-no_heap-slices-iterator here we
                 // don't have self.iter(). So we map dna to rna here:
@@ -74,6 +252,102 @@ impl<'a> Debug for Rna<'a> {
                     .map(utils::dna_to_rna)
                     .try_for_each(|c| write!(f, "{c}"))?;
             }
+            Rna::View(view) => {
+                view.chars(NucleicAcid::Rna)
+                    .try_for_each(|c| write!(f, "{c}"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Debug for Rna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Rna(\"{self}\")")
+    }
+}
+
+impl<'a> PartialEq for Dna<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self, other) {
+            (Self::GivenNucleotides(self_dna), Self::GivenNucleotides(other_dna)) => {
+                self_dna.chars().eq(other_dna.chars())
+            }
+            (Self::GivenNucleotides(self_dna), Self::RnaBased(other_rna)) => self_dna
+                .chars()
+                .eq(other_rna.chars().map(utils::rna_to_dna)),
+            (Self::RnaBased(self_rna), Self::GivenNucleotides(other_dna)) => self_rna
+                .chars()
+                .map(utils::rna_to_dna)
+                .eq(other_dna.chars()),
+            (Self::RnaBased(self_rna), Self::RnaBased(other_rna)) => {
+                // No need to map both of them - their RNA must be the same, too
+                self_rna.chars().eq(other_rna.chars())
+            }
+            (Self::GivenNucleotides(self_dna), Self::View(other_view)) => {
+                self_dna.chars().eq(other_view.chars(NucleicAcid::Dna))
+            }
+            (Self::RnaBased(self_rna), Self::View(other_view)) => self_rna
+                .chars()
+                .map(utils::rna_to_dna)
+                .eq(other_view.chars(NucleicAcid::Dna)),
+            (Self::View(self_view), Self::GivenNucleotides(other_dna)) => {
+                self_view.chars(NucleicAcid::Dna).eq(other_dna.chars())
+            }
+            (Self::View(self_view), Self::RnaBased(other_rna)) => self_view
+                .chars(NucleicAcid::Dna)
+                .eq(other_rna.chars().map(utils::rna_to_dna)),
+            (Self::View(self_view), Self::View(other_view)) => self_view
+                .chars(NucleicAcid::Dna)
+                .eq(other_view.chars(NucleicAcid::Dna)),
+            // Both are ASCII, so no need to iterate over chars.
+            (Self::GivenBytes(self_dna), Self::GivenNucleotides(other_dna)) => {
+                *self_dna == other_dna.as_bytes()
+            }
+            (Self::GivenBytes(self_dna), Self::GivenBytes(other_dna)) => self_dna == other_dna,
+            (Self::GivenBytes(self_dna), Self::RnaBased(other_rna)) => self_dna
+                .iter()
+                .map(|&b| char::from(b))
+                .eq(other_rna.chars().map(utils::rna_to_dna)),
+            (Self::GivenBytes(self_dna), Self::View(other_view)) => self_dna
+                .iter()
+                .map(|&b| char::from(b))
+                .eq(other_view.chars(NucleicAcid::Dna)),
+            (Self::GivenNucleotides(self_dna), Self::GivenBytes(other_dna)) => {
+                self_dna.as_bytes() == *other_dna
+            }
+            (Self::RnaBased(self_rna), Self::GivenBytes(other_dna)) => self_rna
+                .chars()
+                .map(utils::rna_to_dna)
+                .eq(other_dna.iter().map(|&b| char::from(b))),
+            (Self::View(self_view), Self::GivenBytes(other_dna)) => self_view
+                .chars(NucleicAcid::Dna)
+                .eq(other_dna.iter().map(|&b| char::from(b))),
+        }
+    }
+}
+impl<'a> Eq for Dna<'a> {}
+
+impl<'a> Debug for Dna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Dna(\"")?;
+        match self {
+            Dna::GivenNucleotides(dna) => {
+                write!(f, "{dna}")?;
+            }
+            Dna::GivenBytes(dna) => {
+                dna.iter()
+                    .try_for_each(|&b| write!(f, "{}", char::from(b)))?;
+            }
+            Dna::RnaBased(rna) => {
+                rna.chars()
+                    .map(utils::rna_to_dna)
+                    .try_for_each(|c| write!(f, "{c}"))?;
+            }
+            Dna::View(view) => {
+                view.chars(NucleicAcid::Dna)
+                    .try_for_each(|c| write!(f, "{c}"))?;
+            }
         }
         write!(f, "\")")
     }
@@ -1,30 +1,63 @@
 //! no_std heapless (bare metal/embedded-friendly) implementation
 #![no_std]
 
-use core::fmt::{self, Debug, Formatter};
-use utils::{checks, DnaTrait, OurResult, RnaTrait};
+use core::fmt::{self, Debug, Display, Formatter};
+use utils::protein::{Protein, Translation};
+use utils::{
+    checks, ComplementTrait, DnaTrait, NucleicAcid, NucleotidesView, OurResult, RnaToDnaTrait,
+    RnaTrait, TranslateTrait,
+};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Dna<'a>(&'a str);
+#[derive(Clone, Copy)]
+pub enum Dna<'a> {
+    GivenNucleotides(&'a str),
+    /// Represented by given DNA nucleotides as ASCII bytes, *not* converted to `&str`. Returned by
+    /// [`Dna::new_from_bytes`].
+    GivenBytes(&'a [u8]),
+    // Original RNA nucleotides, reverse transcribed on the fly. See `RnaToDnaTrait for Rna`.
+    RnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
+}
 
 #[derive(Clone, Copy)]
 pub enum Rna<'a> {
     GivenNucleotides(&'a str), // RNA nucleotides
+    /// Represented by given RNA nucleotides as ASCII bytes. Returned by [`Rna::new_from_bytes`].
+    GivenBytes(&'a [u8]),
     // Original DNA nucleotides, but *not* transformed. Instead, it will
     // generate RNA nucleotides on the fly by iterating when the consumer calls
     // `PartialEq::eq(...)` on `self`.
     DnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
 }
 
 impl<'a> DnaTrait<'a, Rna<'a>> for Dna<'a> {
     fn new(dna: &'a str) -> OurResult<Self> {
         checks::check_dna(dna)?;
-        Ok(Self(dna))
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    /// Create a [`Dna::GivenBytes`] instance. The error index is the same as for [`Dna::new`],
+    /// since (valid) nucleotides are ASCII.
+    fn new_from_bytes(dna: &'a [u8]) -> OurResult<Self> {
+        checks::check_dna_bytes(dna)?;
+        Ok(Self::GivenBytes(dna))
     }
 
     fn into_rna(&self) -> Rna<'a> {
-        match self {
-            Dna(dna) => Rna::DnaBased(dna),
+        match *self {
+            Dna::GivenNucleotides(dna) => Rna::DnaBased(dna),
+            Dna::RnaBased(rna) => Rna::GivenNucleotides(rna),
+            Dna::GivenBytes(_) | Dna::View(_) => Rna::View(self.as_view().complement()),
         }
     }
 }
@@ -34,6 +67,87 @@ impl<'a> RnaTrait<'a> for Rna<'a> {
         checks::check_rna_str(rna)?;
         Ok(Self::GivenNucleotides(rna))
     }
+
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str_with(rna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(rna))
+    }
+
+    /// Create an [`Rna::GivenBytes`] instance. See [`Dna::new_from_bytes`].
+    fn new_from_bytes(rna: &'a [u8]) -> OurResult<Self> {
+        checks::check_rna_bytes(rna)?;
+        Ok(Self::GivenBytes(rna))
+    }
+}
+
+impl<'a> RnaToDnaTrait<'a, Dna<'a>> for Rna<'a> {
+    fn into_dna(&self) -> Dna<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => Dna::RnaBased(rna),
+            Rna::DnaBased(dna) => Dna::GivenNucleotides(dna),
+            Rna::GivenBytes(_) | Rna::View(_) => Dna::View(self.as_view().complement()),
+        }
+    }
+}
+
+impl<'a> Dna<'a> {
+    /// `self` as a [`NucleotidesView`] (of DNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Dna::GivenNucleotides(dna) => {
+                NucleotidesView::new(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::GivenBytes(dna) => {
+                NucleotidesView::new_from_bytes(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::RnaBased(rna) => NucleotidesView::new(rna, NucleicAcid::Rna)
+                .expect("RNA nucleotides")
+                .complement(),
+            Dna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Dna<'a> {
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Dna::View(self.as_view().complement())
+    }
+
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Dna::View(self.as_view().reverse_complement())
+    }
+}
+
+impl<'a> Rna<'a> {
+    /// `self` as a [`NucleotidesView`] (of RNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => {
+                NucleotidesView::new(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::GivenBytes(rna) => {
+                NucleotidesView::new_from_bytes(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::DnaBased(dna) => NucleotidesView::new(dna, NucleicAcid::Dna)
+                .expect("DNA nucleotides")
+                .complement(),
+            Rna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Rna<'a> {
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Rna::View(self.as_view().complement())
+    }
+
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Rna::View(self.as_view().reverse_complement())
+    }
 }
 
 impl<'a> Rna<'a> {
@@ -43,7 +157,30 @@ impl<'a> Rna<'a> {
     {
         match self {
             Rna::GivenNucleotides(rna) => rna.chars().eq(other_rna_chars),
+            Rna::GivenBytes(rna) => rna.iter().map(|&b| char::from(b)).eq(other_rna_chars),
             Rna::DnaBased(dna) => dna.chars().map(utils::dna_to_rna).eq(other_rna_chars),
+            Rna::View(view) => view.chars(NucleicAcid::Rna).eq(other_rna_chars),
+        }
+    }
+}
+
+impl<'a> TranslateTrait for Rna<'a> {
+    fn translate_into<B: AsRef<[u8]> + AsMut<[u8]>>(
+        &self,
+        buffer: B,
+        translation: Translation,
+    ) -> OurResult<Protein<B>> {
+        match self {
+            Self::GivenNucleotides(rna) => Protein::new_from_rna(buffer, rna.chars(), translation),
+            Self::GivenBytes(rna) => {
+                Protein::new_from_rna(buffer, rna.iter().map(|&b| char::from(b)), translation)
+            }
+            Self::DnaBased(dna) => {
+                Protein::new_from_rna(buffer, dna.chars().map(utils::dna_to_rna), translation)
+            }
+            Self::View(view) => {
+                Protein::new_from_rna(buffer, view.chars(NucleicAcid::Rna), translation)
+            }
         }
     }
 }
@@ -52,19 +189,24 @@ impl<'a> PartialEq for Rna<'a> {
     fn eq(&self, other: &Self) -> bool {
         match self {
             Self::GivenNucleotides(rna) => other.eq_iterate_other(rna.chars()),
+            Self::GivenBytes(rna) => other.eq_iterate_other(rna.iter().map(|&b| char::from(b))),
             Self::DnaBased(dna) => other.eq_iterate_other(dna.chars().map(utils::dna_to_rna)),
+            Self::View(view) => other.eq_iterate_other(view.chars(NucleicAcid::Rna)),
         }
     }
 }
 impl<'a> Eq for Rna<'a> {}
 
-impl<'a> Debug for Rna<'a> {
+impl<'a> Display for Rna<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "Rna(\"")?;
         match self {
             Rna::GivenNucleotides(rna) => {
                 write!(f, "{rna}")?;
             }
+            Rna::GivenBytes(rna) => {
+                rna.iter()
+                    .try_for_each(|&b| write!(f, "{}", char::from(b)))?;
+            }
             Rna::DnaBased(dna) => {
                 // Compared to ../../no_std-no_heap-slices-iterator (TODO fix <--) here we
                 // don't have self.iter(). So we map dna to rna here:
@@ -72,6 +214,67 @@ impl<'a> Debug for Rna<'a> {
                     .map(utils::dna_to_rna)
                     .try_for_each(|c| write!(f, "{c}"))?;
             }
+            Rna::View(view) => {
+                view.chars(NucleicAcid::Rna)
+                    .try_for_each(|c| write!(f, "{c}"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Debug for Rna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Rna(\"{self}\")")
+    }
+}
+
+impl<'a> Dna<'a> {
+    fn eq_iterate_other<I>(&self, other_dna_chars: I) -> bool
+    where
+        I: Iterator<Item = char>,
+    {
+        match self {
+            Dna::GivenNucleotides(dna) => dna.chars().eq(other_dna_chars),
+            Dna::GivenBytes(dna) => dna.iter().map(|&b| char::from(b)).eq(other_dna_chars),
+            Dna::RnaBased(rna) => rna.chars().map(utils::rna_to_dna).eq(other_dna_chars),
+            Dna::View(view) => view.chars(NucleicAcid::Dna).eq(other_dna_chars),
+        }
+    }
+}
+
+impl<'a> PartialEq for Dna<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            Self::GivenNucleotides(dna) => other.eq_iterate_other(dna.chars()),
+            Self::GivenBytes(dna) => other.eq_iterate_other(dna.iter().map(|&b| char::from(b))),
+            Self::RnaBased(rna) => other.eq_iterate_other(rna.chars().map(utils::rna_to_dna)),
+            Self::View(view) => other.eq_iterate_other(view.chars(NucleicAcid::Dna)),
+        }
+    }
+}
+impl<'a> Eq for Dna<'a> {}
+
+impl<'a> Debug for Dna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Dna(\"")?;
+        match self {
+            Dna::GivenNucleotides(dna) => {
+                write!(f, "{dna}")?;
+            }
+            Dna::GivenBytes(dna) => {
+                dna.iter()
+                    .try_for_each(|&b| write!(f, "{}", char::from(b)))?;
+            }
+            Dna::RnaBased(rna) => {
+                rna.chars()
+                    .map(utils::rna_to_dna)
+                    .try_for_each(|c| write!(f, "{c}"))?;
+            }
+            Dna::View(view) => {
+                view.chars(NucleicAcid::Dna)
+                    .try_for_each(|c| write!(f, "{c}"))?;
+            }
         }
         write!(f, "\")")
     }
@@ -1,8 +1,8 @@
 #![no_std]
-#![feature(generic_associated_types)]
 
 use coupled_seq_non_modify_trans_shr_ref_sta_eq_iter_to_specialized as dna;
 use test_harness::api_tests_read_only::Tests;
+use test_harness::differential::Tests as DifferentialTests;
 
 struct T {}
 impl Tests for T {
@@ -10,7 +10,8 @@ impl Tests for T {
     type Rna<'a> = dna::Rna<'a>;
 }
 
-#[test]
-fn all_tests() {
-    T::all_tests();
-}
+impl DifferentialTests for T {}
+
+test_harness::api_tests!(read_only: T);
+
+test_harness::api_tests!(differential: T);
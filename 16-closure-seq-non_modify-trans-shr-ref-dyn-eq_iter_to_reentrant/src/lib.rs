@@ -1,26 +1,59 @@
 #![no_std]
 
-use core::fmt::{self, Debug, Formatter};
-use utils::{checks, DnaTrait, OurResult, RnaTrait};
+use core::fmt::{self, Debug, Display, Formatter};
+use utils::protein::{Protein, Translation};
+use utils::{
+    checks, ComplementTrait, DnaTrait, NucleicAcid, NucleotidesView, OurResult, RnaToDnaTrait,
+    RnaTrait, TranslateTrait,
+};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Dna<'a>(&'a str);
+#[derive(Clone, Copy)]
+pub enum Dna<'a> {
+    GivenNucleotides(&'a str),
+    /// Represented by given DNA nucleotides as ASCII bytes, *not* converted to `&str`. Returned by
+    /// [`Dna::new_from_bytes`].
+    GivenBytes(&'a [u8]),
+    // Original RNA nucleotides, reverse transcribed on the fly. See `RnaToDnaTrait for Rna`.
+    RnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
+}
 
 #[derive(Clone, Copy)]
 pub enum Rna<'a> {
     GivenNucleotides(&'a str),
+    /// Represented by given RNA nucleotides as ASCII bytes. Returned by [`Rna::new_from_bytes`].
+    GivenBytes(&'a [u8]),
     DnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
 }
 
 impl<'a> DnaTrait<'a, Rna<'a>> for Dna<'a> {
     fn new(dna: &'a str) -> OurResult<Self> {
         checks::check_dna(dna)?;
-        Ok(Self(dna))
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    /// Create a [`Dna::GivenBytes`] instance. The error index is the same as for [`Dna::new`],
+    /// since (valid) nucleotides are ASCII.
+    fn new_from_bytes(dna: &'a [u8]) -> OurResult<Self> {
+        checks::check_dna_bytes(dna)?;
+        Ok(Self::GivenBytes(dna))
     }
 
     fn into_rna(&self) -> Rna<'a> {
-        match self {
-            Dna(dna) => Rna::DnaBased(dna),
+        match *self {
+            Dna::GivenNucleotides(dna) => Rna::DnaBased(dna),
+            Dna::RnaBased(rna) => Rna::GivenNucleotides(rna),
+            Dna::GivenBytes(_) | Dna::View(_) => Rna::View(self.as_view().complement()),
         }
     }
 }
@@ -30,12 +63,93 @@ impl<'a> RnaTrait<'a> for Rna<'a> {
         checks::check_rna_str(rna)?;
         Ok(Self::GivenNucleotides(rna))
     }
+
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str_with(rna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(rna))
+    }
+
+    /// Create an [`Rna::GivenBytes`] instance. See [`Dna::new_from_bytes`].
+    fn new_from_bytes(rna: &'a [u8]) -> OurResult<Self> {
+        checks::check_rna_bytes(rna)?;
+        Ok(Self::GivenBytes(rna))
+    }
+}
+
+impl<'a> RnaToDnaTrait<'a, Dna<'a>> for Rna<'a> {
+    fn into_dna(&self) -> Dna<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => Dna::RnaBased(rna),
+            Rna::DnaBased(dna) => Dna::GivenNucleotides(dna),
+            Rna::GivenBytes(_) | Rna::View(_) => Dna::View(self.as_view().complement()),
+        }
+    }
+}
+
+impl<'a> Dna<'a> {
+    /// `self` as a [`NucleotidesView`] (of DNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Dna::GivenNucleotides(dna) => {
+                NucleotidesView::new(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::GivenBytes(dna) => {
+                NucleotidesView::new_from_bytes(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::RnaBased(rna) => NucleotidesView::new(rna, NucleicAcid::Rna)
+                .expect("RNA nucleotides")
+                .complement(),
+            Dna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Dna<'a> {
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Dna::View(self.as_view().complement())
+    }
+
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Dna::View(self.as_view().reverse_complement())
+    }
+}
+
+impl<'a> Rna<'a> {
+    /// `self` as a [`NucleotidesView`] (of RNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => {
+                NucleotidesView::new(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::GivenBytes(rna) => {
+                NucleotidesView::new_from_bytes(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::DnaBased(dna) => NucleotidesView::new(dna, NucleicAcid::Dna)
+                .expect("DNA nucleotides")
+                .complement(),
+            Rna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Rna<'a> {
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Rna::View(self.as_view().complement())
+    }
+
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Rna::View(self.as_view().reverse_complement())
+    }
 }
 
 impl<'a> Rna<'a> {
     /// - Generic param P - type of the parameter to pass to the closure.
     /// - Generic param P - result type from the closure, to be returned from this
-    /// `with_chars_universal`.
+    ///   `with_chars_universal`.
     /// - Generic param C - closure to call, with a (dynamic) iterator over chars from `self`, and
     ///   with the given `param`.
     /// - Return: Result of the call to `closure`.
@@ -45,20 +159,34 @@ impl<'a> Rna<'a> {
     {
         match self {
             Rna::GivenNucleotides(rna) => closure(&mut rna.chars(), param),
+            Rna::GivenBytes(rna) => closure(&mut rna.iter().map(|&b| char::from(b)), param),
             Rna::DnaBased(dna) => closure(&mut dna.chars().map(utils::dna_to_rna), param),
+            Rna::View(view) => closure(&mut view.chars(NucleicAcid::Rna), param),
         }
     }
 }
 
+impl<'a> TranslateTrait for Rna<'a> {
+    fn translate_into<B: AsRef<[u8]> + AsMut<[u8]>>(
+        &self,
+        buffer: B,
+        translation: Translation,
+    ) -> OurResult<Protein<B>> {
+        self.with_chars_reentrant((buffer, translation), |rna_chars, (buffer, translation)| {
+            Protein::new_from_rna(buffer, rna_chars, translation)
+        })
+    }
+}
+
 impl<'a> PartialEq for Rna<'a> {
     fn eq(&self, other: &Self) -> bool {
         self.with_chars_reentrant(
             other,
-            |self_chars/*: &mut dyn Iterator<Item = char>*/, other/*: &Self*/| {
+            |self_chars /*: &mut dyn Iterator<Item = char>*/, other /*: &Self*/| {
                 other.with_chars_reentrant(
                     self_chars,
-                    |other_chars/*: &mut dyn Iterator<Item = char>*/,
-                     self_chars/*: &mut dyn Iterator<Item = char>*/| {
+                    |other_chars, /*: &mut dyn Iterator<Item = char>*/
+                     self_chars   /*: &mut dyn Iterator<Item = char>*/| {
                         other_chars.eq(self_chars)
                     },
                 )
@@ -68,13 +196,16 @@ impl<'a> PartialEq for Rna<'a> {
 }
 impl<'a> Eq for Rna<'a> {}
 
-impl<'a> Debug for Rna<'a> {
+impl<'a> Display for Rna<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "Rna(\"")?;
         match self {
             Rna::GivenNucleotides(rna) => {
                 write!(f, "{rna}")?;
             }
+            Rna::GivenBytes(rna) => {
+                rna.iter()
+                    .try_for_each(|&b| write!(f, "{}", char::from(b)))?;
+            }
             Rna::DnaBased(dna) => {
                 // Compared to ../../no_std-no_heap-slices-iterator (TODO <-- fix name) here we
                 // don't have self.iter(). So we map dna to rna here:
@@ -82,6 +213,67 @@ impl<'a> Debug for Rna<'a> {
                     .map(utils::dna_to_rna)
                     .try_for_each(|c| write!(f, "{c}"))?;
             }
+            Rna::View(view) => {
+                view.chars(NucleicAcid::Rna)
+                    .try_for_each(|c| write!(f, "{c}"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Debug for Rna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Rna(\"{self}\")")
+    }
+}
+
+impl<'a> Dna<'a> {
+    /// The same as [`Rna::with_chars_reentrant`], but over `self`'s DNA nucleotides.
+    fn with_chars_reentrant<P, C, R>(&self, param: P, closure: C) -> R
+    where
+        C: Fn(&mut dyn Iterator<Item = char>, P) -> R,
+    {
+        match self {
+            Dna::GivenNucleotides(dna) => closure(&mut dna.chars(), param),
+            Dna::GivenBytes(dna) => closure(&mut dna.iter().map(|&b| char::from(b)), param),
+            Dna::RnaBased(rna) => closure(&mut rna.chars().map(utils::rna_to_dna), param),
+            Dna::View(view) => closure(&mut view.chars(NucleicAcid::Dna), param),
+        }
+    }
+}
+
+impl<'a> PartialEq for Dna<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.with_chars_reentrant(other, |self_chars, other| {
+            other.with_chars_reentrant(self_chars, |other_chars, self_chars| {
+                other_chars.eq(self_chars)
+            })
+        })
+    }
+}
+impl<'a> Eq for Dna<'a> {}
+
+impl<'a> Debug for Dna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Dna(\"")?;
+        match self {
+            Dna::GivenNucleotides(dna) => {
+                write!(f, "{dna}")?;
+            }
+            Dna::GivenBytes(dna) => {
+                dna.iter()
+                    .try_for_each(|&b| write!(f, "{}", char::from(b)))?;
+            }
+            Dna::RnaBased(rna) => {
+                rna.chars()
+                    .map(utils::rna_to_dna)
+                    .try_for_each(|c| write!(f, "{c}"))?;
+            }
+            Dna::View(view) => {
+                view.chars(NucleicAcid::Dna)
+                    .try_for_each(|c| write!(f, "{c}"))?;
+            }
         }
         write!(f, "\")")
     }
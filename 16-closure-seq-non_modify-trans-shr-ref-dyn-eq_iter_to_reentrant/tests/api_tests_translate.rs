@@ -0,0 +1,12 @@
+#![no_std]
+
+use closure_seq_non_modify_trans_shr_ref_dyn_eq_iter_to_reentrant as dna;
+use test_harness::api_tests_translate::Tests;
+
+struct T {}
+impl Tests for T {
+    type Dna<'a> = dna::Dna<'a>;
+    type Rna<'a> = dna::Rna<'a>;
+}
+
+test_harness::api_tests!(translate: T);
@@ -0,0 +1,387 @@
+//! no_std heapless. [`Dna::with_rna`] transcribes onto the stack, without a storage of a fixed
+//! size: it recurses once per [`CHUNK_LEN`] nucleotides, and each stack frame holds one chunk. The
+//! innermost frame passes all chunks (as an [`Rna`]) to the given closure. Only the number of
+//! frames is limited - see [`DEFAULT_MAX_CHUNKS`].
+#![no_std]
+
+use core::fmt::{self, Debug, Display, Formatter};
+use core::iter::{self, Peekable};
+use utils::{
+    checks, ComplementTrait, DnaTrait, NucleicAcid, NucleotideError, NucleotidesView, OurResult,
+    RnaToDnaTrait, RnaTrait,
+};
+
+/// Number of nucleotides held by one chunk (one stack frame of [`Dna::with_rna`]).
+pub const CHUNK_LEN: usize = 16;
+
+/// Maximum number of chunks (stack frames) of [`Dna::with_rna`]: up to 1024 nucleotides. Longer
+/// sequences fail, rather than overflow the stack. See [`Dna::with_rna_max`] for another limit.
+pub const DEFAULT_MAX_CHUNKS: usize = 64;
+
+/// Number of stack frames that [`Dna::with_rna`] recurses for, when transcribing
+/// `num_nucleotides`. At least one, even for empty DNA.
+pub const fn recursion_depth(num_nucleotides: usize) -> usize {
+    if num_nucleotides == 0 {
+        1
+    } else {
+        num_nucleotides.div_ceil(CHUNK_LEN)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Dna<'a> {
+    GivenNucleotides(&'a str),
+    // Original RNA nucleotides, reverse transcribed on the fly. See `RnaToDnaTrait for Rna`.
+    RnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
+    /// Reverse transcribed (on the fly) from [`Rna::Stack`].
+    Stack(StackView<'a>),
+}
+
+#[derive(Clone, Copy)]
+pub enum Rna<'a> {
+    GivenNucleotides(&'a str),
+    /// Returned by [`Dna::into_rna`]. Transcribed on the fly.
+    DnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
+    /// RNA nucleotides transcribed onto the stack. Passed to the closure of [`Dna::with_rna`].
+    Stack(StackView<'a>),
+}
+
+/// RNA nucleotides in one stack frame of [`Dna::with_rna`], and a link to the chunk in the
+/// previous (outer) frame. All chunks but the last one are full.
+struct Chunk<'a> {
+    rna: [u8; CHUNK_LEN],
+    /// Number of nucleotides in this chunk.
+    len: usize,
+    /// Number of previous chunks.
+    index: usize,
+    previous: Option<&'a Chunk<'a>>,
+}
+
+impl<'a> Chunk<'a> {
+    fn nucleotides(&self) -> &[u8] {
+        &self.rna[..self.len]
+    }
+}
+
+/// A (lazy) complement, reverse complement, or reverse of chunks on the stack - like
+/// [`NucleotidesView`], but over the chunks rather than a `&str`.
+#[derive(Clone, Copy)]
+pub struct StackView<'a> {
+    /// All chunks, the outermost (first) one first. The chunks link only to their previous ones,
+    /// so the innermost frame collects them here, for iterating in one pass in either direction.
+    chunks: &'a [&'a Chunk<'a>],
+    complemented: bool,
+    reversed: bool,
+}
+
+impl<'a> StackView<'a> {
+    fn new(chunks: &'a [&'a Chunk<'a>]) -> Self {
+        Self {
+            chunks,
+            complemented: false,
+            reversed: false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn complement(self) -> Self {
+        Self {
+            complemented: !self.complemented,
+            ..self
+        }
+    }
+
+    fn reverse_complement(self) -> Self {
+        Self {
+            complemented: !self.complemented,
+            reversed: !self.reversed,
+            ..self
+        }
+    }
+
+    /// Iterate over the viewed (RNA) nucleotides, as nucleotides of `nucleic_acid`.
+    fn chars(&self, nucleic_acid: NucleicAcid) -> impl Iterator<Item = char> + 'a {
+        let map: fn(char) -> char = match (nucleic_acid, self.complemented) {
+            (NucleicAcid::Rna, false) => |c| c,
+            (NucleicAcid::Rna, true) => utils::rna_complement,
+            (NucleicAcid::Dna, true) => utils::rna_to_dna,
+            (NucleicAcid::Dna, false) => |c| utils::rna_to_dna(utils::rna_complement(c)),
+        };
+        let mut nucleotides = self.chunks.iter().flat_map(|chunk| chunk.nucleotides());
+        let reversed = self.reversed;
+        iter::from_fn(move || {
+            if reversed {
+                nucleotides.next_back()
+            } else {
+                nucleotides.next()
+            }
+        })
+        .map(move |&nucl| map(nucl as char))
+    }
+}
+
+impl<'a> DnaTrait<'a, Rna<'a>> for Dna<'a> {
+    fn new(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna(dna)?;
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    /// Lazy - it doesn't transcribe. See [`Dna::with_rna`] for transcription onto the stack.
+    fn into_rna(&self) -> Rna<'a> {
+        match *self {
+            Dna::GivenNucleotides(dna) => Rna::DnaBased(dna),
+            Dna::RnaBased(rna) => Rna::GivenNucleotides(rna),
+            Dna::View(view) => Rna::View(view.complement()),
+            Dna::Stack(view) => Rna::Stack(view.complement()),
+        }
+    }
+}
+
+impl<'a> RnaTrait<'a> for Rna<'a> {
+    fn new(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str(rna)?;
+        Ok(Self::GivenNucleotides(rna))
+    }
+
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str_with(rna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(rna))
+    }
+}
+
+impl<'a> RnaToDnaTrait<'a, Dna<'a>> for Rna<'a> {
+    fn into_dna(&self) -> Dna<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => Dna::RnaBased(rna),
+            Rna::DnaBased(dna) => Dna::GivenNucleotides(dna),
+            Rna::View(view) => Dna::View(view.complement()),
+            Rna::Stack(view) => Dna::Stack(view.complement()),
+        }
+    }
+}
+
+impl<'a> Dna<'a> {
+    /// Transcribe `self` onto the stack, and call `f` with the result. Return what `f` returns.
+    ///
+    /// The deeper the recursion, the more stack it needs: see [`recursion_depth`]. Return
+    /// [`NucleotideError::CapacityExceeded`] (without calling `f`) if that's more than
+    /// [`DEFAULT_MAX_CHUNKS`].
+    pub fn with_rna<R>(&self, f: impl FnOnce(&Rna<'_>) -> R) -> OurResult<R> {
+        self.with_rna_max::<DEFAULT_MAX_CHUNKS, R>(f)
+    }
+
+    /// Like [`Dna::with_rna`], but limited to `MAX_CHUNKS` (at least one) chunks. The innermost
+    /// frame holds `MAX_CHUNKS` references (to collect the chunks).
+    pub fn with_rna_max<const MAX_CHUNKS: usize, R>(
+        &self,
+        f: impl FnOnce(&Rna<'_>) -> R,
+    ) -> OurResult<R> {
+        const { assert!(MAX_CHUNKS > 0, "MAX_CHUNKS must be at least 1") };
+        let len = self.with_chars(|dna_chars| dna_chars.count());
+        if recursion_depth(len) > MAX_CHUNKS {
+            return Err(NucleotideError::CapacityExceeded {
+                capacity: MAX_CHUNKS * CHUNK_LEN,
+                required: len,
+            });
+        }
+        Ok(self.with_chars(|dna_chars| {
+            transcribe_chunk::<MAX_CHUNKS, _, _, _>(
+                &mut dna_chars.map(utils::dna_to_rna).peekable(),
+                None,
+                f,
+            )
+        }))
+    }
+
+    /// Call `f` with an iterator over `self`'s DNA nucleotides. Return what `f` returns.
+    fn with_chars<R>(&self, f: impl FnOnce(&mut dyn Iterator<Item = char>) -> R) -> R {
+        match self {
+            Dna::GivenNucleotides(dna) => f(&mut dna.chars()),
+            Dna::RnaBased(rna) => f(&mut rna.chars().map(utils::rna_to_dna)),
+            Dna::View(view) => f(&mut view.chars(NucleicAcid::Dna)),
+            Dna::Stack(view) => f(&mut view.chars(NucleicAcid::Dna)),
+        }
+    }
+
+    fn complement_or_reverse(&self, reverse: bool) -> Self {
+        let view = match *self {
+            Dna::GivenNucleotides(dna) => {
+                NucleotidesView::new(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::RnaBased(rna) => NucleotidesView::new(rna, NucleicAcid::Rna)
+                .expect("RNA nucleotides")
+                .complement(),
+            Dna::View(view) => view,
+            Dna::Stack(view) => {
+                return Dna::Stack(if reverse {
+                    view.reverse_complement()
+                } else {
+                    view.complement()
+                })
+            }
+        };
+        Dna::View(if reverse {
+            view.reverse_complement()
+        } else {
+            view.complement()
+        })
+    }
+}
+
+/// Fill one [`Chunk`] (in this stack frame) from `rna_chars`. Then either recurse for the rest of
+/// `rna_chars`, or (if none are left) call `f` with all the chunks. The caller has checked that
+/// there are at most `MAX_CHUNKS` of them.
+fn transcribe_chunk<const MAX_CHUNKS: usize, I, F, R>(
+    rna_chars: &mut Peekable<I>,
+    previous: Option<&Chunk<'_>>,
+    f: F,
+) -> R
+where
+    I: Iterator<Item = char>,
+    F: FnOnce(&Rna<'_>) -> R,
+{
+    let mut chunk = Chunk {
+        rna: [0; CHUNK_LEN],
+        len: 0,
+        index: previous.map_or(0, |previous| previous.index + 1),
+        previous,
+    };
+    // `zip` takes from the chunk first. So it leaves the rest of `rna_chars` untouched.
+    for (slot, c) in chunk.rna.iter_mut().zip(rna_chars.by_ref()) {
+        // Valid nucleotides are ASCII.
+        *slot = c as u8;
+        chunk.len += 1;
+    }
+    if rna_chars.peek().is_some() {
+        transcribe_chunk::<MAX_CHUNKS, _, _, _>(rna_chars, Some(&chunk), f)
+    } else {
+        let mut chunks = [&chunk; MAX_CHUNKS];
+        let mut next = chunk.previous;
+        while let Some(previous) = next {
+            chunks[previous.index] = previous;
+            next = previous.previous;
+        }
+        f(&Rna::Stack(StackView::new(&chunks[..=chunk.index])))
+    }
+}
+
+impl<'a> ComplementTrait for Dna<'a> {
+    /// Create a [`Dna::View`] (or [`Dna::Stack`]) instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        self.complement_or_reverse(false)
+    }
+
+    /// Create a [`Dna::View`] (or [`Dna::Stack`]) instance. No nucleotides are complemented (or
+    /// reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        self.complement_or_reverse(true)
+    }
+}
+
+impl<'a> Rna<'a> {
+    /// Call `f` with an iterator over `self`'s RNA nucleotides. Return what `f` returns.
+    fn with_chars<R>(&self, f: impl FnOnce(&mut dyn Iterator<Item = char>) -> R) -> R {
+        match self {
+            Rna::GivenNucleotides(rna) => f(&mut rna.chars()),
+            Rna::DnaBased(dna) => f(&mut dna.chars().map(utils::dna_to_rna)),
+            Rna::View(view) => f(&mut view.chars(NucleicAcid::Rna)),
+            Rna::Stack(view) => f(&mut view.chars(NucleicAcid::Rna)),
+        }
+    }
+
+    fn complement_or_reverse(&self, reverse: bool) -> Self {
+        let view = match *self {
+            Rna::GivenNucleotides(rna) => {
+                NucleotidesView::new(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::DnaBased(dna) => NucleotidesView::new(dna, NucleicAcid::Dna)
+                .expect("DNA nucleotides")
+                .complement(),
+            Rna::View(view) => view,
+            Rna::Stack(view) => {
+                return Rna::Stack(if reverse {
+                    view.reverse_complement()
+                } else {
+                    view.complement()
+                })
+            }
+        };
+        Rna::View(if reverse {
+            view.reverse_complement()
+        } else {
+            view.complement()
+        })
+    }
+}
+
+impl<'a> ComplementTrait for Rna<'a> {
+    /// Create an [`Rna::View`] (or [`Rna::Stack`]) instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        self.complement_or_reverse(false)
+    }
+
+    /// Create an [`Rna::View`] (or [`Rna::Stack`]) instance. No nucleotides are complemented (or
+    /// reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        self.complement_or_reverse(true)
+    }
+}
+
+impl<'a> PartialEq for Rna<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.with_chars(|self_chars| other.with_chars(|other_chars| self_chars.eq(other_chars)))
+    }
+}
+impl<'a> Eq for Rna<'a> {}
+
+fn write_chars(f: &mut Formatter<'_>, chars: &mut dyn Iterator<Item = char>) -> fmt::Result {
+    for c in chars {
+        write!(f, "{c}")?;
+    }
+    Ok(())
+}
+
+impl<'a> Display for Rna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        self.with_chars(|rna_chars| write_chars(f, rna_chars))
+    }
+}
+
+impl<'a> Debug for Rna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Rna(\"{self}\")")
+    }
+}
+
+impl<'a> PartialEq for Dna<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.with_chars(|self_chars| other.with_chars(|other_chars| self_chars.eq(other_chars)))
+    }
+}
+impl<'a> Eq for Dna<'a> {}
+
+impl<'a> Debug for Dna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Dna(\"")?;
+        self.with_chars(|dna_chars| write_chars(f, dna_chars))?;
+        write!(f, "\")")
+    }
+}
@@ -0,0 +1,18 @@
+#![no_std]
+
+use closure_rnd_modify_any_stack_shr_arr_recurse_linear as dna;
+
+use test_harness::api_tests_read_only::Tests;
+use test_harness::differential::Tests as DifferentialTests;
+
+struct T {}
+impl Tests for T {
+    type Dna<'a> = dna::Dna<'a>;
+    type Rna<'a> = dna::Rna<'a>;
+}
+
+impl DifferentialTests for T {}
+
+test_harness::api_tests!(read_only: T);
+
+test_harness::api_tests!(differential: T);
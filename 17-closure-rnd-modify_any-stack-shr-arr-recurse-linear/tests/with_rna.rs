@@ -0,0 +1,89 @@
+//! [`dna::Dna::with_rna`] across [`dna::CHUNK_LEN`] boundaries.
+use closure_rnd_modify_any_stack_shr_arr_recurse_linear as dna;
+use dna::{CHUNK_LEN, DEFAULT_MAX_CHUNKS};
+use utils::{ComplementTrait, DnaTrait, NucleotideError, OurResult, RnaToDnaTrait, RnaTrait};
+
+const DNA: &str = "ACGTGGTCTTAAGCTAACGTGGTCTTAAGCTAACGTGGTCTTAAGCTA";
+
+#[test]
+fn test_with_rna() -> OurResult<()> {
+    for len in [0, 1, CHUNK_LEN - 1, CHUNK_LEN, CHUNK_LEN + 1, DNA.len()] {
+        let dna = dna::Dna::new(&DNA[..len])?;
+        dna.with_rna(|rna| {
+            assert!(matches!(rna, dna::Rna::Stack(_)));
+            assert_eq!(*rna, dna.into_rna());
+            assert_eq!(rna.into_dna(), dna);
+            assert_eq!(rna.to_string(), dna.into_rna().to_string());
+        })?;
+    }
+    assert_eq!(
+        dna::Dna::new("GCTA")?.with_rna(|rna| rna.to_string())?,
+        "CGAU"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_with_rna_complement() -> OurResult<()> {
+    let dna = dna::Dna::new(DNA)?;
+    dna.with_rna(|rna| {
+        assert_eq!(rna.complement(), dna.into_rna().complement());
+        assert_eq!(
+            rna.reverse_complement(),
+            dna.into_rna().reverse_complement()
+        );
+        assert_eq!(
+            rna.reverse_complement().into_dna(),
+            dna.reverse_complement()
+        );
+        assert_eq!(rna.complement().complement(), *rna);
+    })?;
+    // Transcribing a complemented (lazy) DNA.
+    dna.reverse_complement().with_rna(|rna| {
+        assert_eq!(*rna, dna.into_rna().reverse_complement());
+    })?;
+    Ok(())
+}
+
+#[test]
+fn test_with_rna_debug() -> OurResult<()> {
+    let dna = dna::Dna::new_iupac("AARN")?;
+    let debug = dna.with_rna(|rna| format!("{rna:?}"))?;
+    assert_eq!(debug, "Rna(\"UUYN\")");
+    assert_eq!(dna::Rna::new_iupac("UUYN")?, dna.into_rna());
+    Ok(())
+}
+
+#[test]
+fn test_with_rna_max_chunks() -> OurResult<()> {
+    let max_len = DEFAULT_MAX_CHUNKS * CHUNK_LEN;
+    let longest = "A".repeat(max_len);
+    let num_u = dna::Dna::new(&longest)?.with_rna(|rna| rna.to_string().matches('U').count())?;
+    assert_eq!(num_u, max_len);
+
+    let too_long = "A".repeat(max_len + 1);
+    let mut called = false;
+    assert_eq!(
+        dna::Dna::new(&too_long)?.with_rna(|_| called = true),
+        Err(NucleotideError::CapacityExceeded {
+            capacity: max_len,
+            required: max_len + 1
+        })
+    );
+    assert!(!called);
+
+    let dna = dna::Dna::new(&DNA[..2 * CHUNK_LEN])?;
+    assert!(dna.with_rna_max::<2, _>(|rna| *rna == dna.into_rna())?);
+    assert!(dna::Dna::new(&DNA[..2 * CHUNK_LEN + 1])?
+        .with_rna_max::<2, _>(|_| ())
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_recursion_depth() {
+    assert_eq!(dna::recursion_depth(0), 1);
+    assert_eq!(dna::recursion_depth(CHUNK_LEN), 1);
+    assert_eq!(dna::recursion_depth(CHUNK_LEN + 1), 2);
+    assert_eq!(dna::recursion_depth(10 * CHUNK_LEN), 10);
+}
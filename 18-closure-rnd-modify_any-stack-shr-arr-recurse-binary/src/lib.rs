@@ -0,0 +1,298 @@
+//! no_std heapless. [`Dna::with_rna`] transcribes onto the stack, without any storage limit known
+//! in advance: it recurses with stack buffers of 1, 2, 4, 8... bytes, until one can hold all
+//! nucleotides. The frame with that buffer passes it (as an [`Rna`]) to the given closure.
+//!
+//! Compared to linear recursion (one frame per fixed-size chunk), the recursion depth is
+//! logarithmic, and the buffer is contiguous. But up to half of the buffer is unused. Only the
+//! frame that transcribes has a buffer - the outer frames only pass on the closure.
+#![no_std]
+
+use core::fmt::{self, Debug, Display, Formatter};
+use core::str;
+use utils::{
+    checks, ComplementTrait, DnaTrait, NucleicAcid, NucleotideError, NucleotidesView, OurResult,
+    RnaToDnaTrait, RnaTrait,
+};
+
+/// Maximum number of nucleotides of [`Dna::with_rna`]: a stack buffer of 1 KiB. Longer sequences
+/// fail, rather than overflow the stack. See [`Dna::with_rna_max`] for another limit.
+pub const DEFAULT_MAX_LEN: usize = 1 << 10;
+
+/// Length of the largest stack buffer that [`Dna::with_rna_max`] can use, hence the highest limit
+/// it accepts.
+pub const MAX_BUFFER_LEN: usize = 1 << 16;
+
+/// Number of stack frames that [`Dna::with_rna`] recurses for, when transcribing
+/// `num_nucleotides` (up to [`MAX_BUFFER_LEN`]). At least one, even for empty DNA.
+pub const fn recursion_depth(num_nucleotides: usize) -> usize {
+    if num_nucleotides <= 1 {
+        1
+    } else {
+        // The last buffer is the smallest power of two not less than `num_nucleotides`.
+        (num_nucleotides - 1).ilog2() as usize + 2
+    }
+}
+
+/// A stack buffer of one recursion level of [`Dna::with_rna`], and the type of the (twice as long)
+/// buffer of the next level.
+trait Buffer: AsMut<[u8]> + Sized {
+    type Doubled: Buffer;
+    const ZEROED: Self;
+}
+
+/// Implement [`Buffer`] for `[u8; $len]` for each given power of two, except for the last one
+/// ([`MAX_BUFFER_LEN`]). Then the compiler generates the recursion for a finite number of levels only.
+macro_rules! buffers {
+    ($($len:literal)+) => {
+        $(
+            impl Buffer for [u8; $len] {
+                type Doubled = [u8; $len * 2];
+                const ZEROED: Self = [0; $len];
+            }
+        )+
+    };
+}
+buffers!(1 2 4 8 16 32 64 128 256 512 1024 2048 4096 8192 16384 32768);
+
+/// The last level. [`Dna::with_rna_max`] never recurses beyond it.
+impl Buffer for [u8; MAX_BUFFER_LEN] {
+    type Doubled = Self;
+    const ZEROED: Self = [0; MAX_BUFFER_LEN];
+}
+
+#[derive(Clone, Copy)]
+pub enum Dna<'a> {
+    GivenNucleotides(&'a str),
+    // Original RNA nucleotides, reverse transcribed on the fly. See `RnaToDnaTrait for Rna`.
+    RnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
+}
+
+#[derive(Clone, Copy)]
+pub enum Rna<'a> {
+    /// Given to [`Rna::new`], or transcribed onto the stack by [`Dna::with_rna`].
+    GivenNucleotides(&'a str),
+    /// Returned by [`Dna::into_rna`]. Transcribed on the fly.
+    DnaBased(&'a str),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed either.
+    /// Returned by [`ComplementTrait`] methods.
+    View(NucleotidesView<'a>),
+}
+
+impl<'a> DnaTrait<'a, Rna<'a>> for Dna<'a> {
+    fn new(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna(dna)?;
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    /// Lazy - it doesn't transcribe. See [`Dna::with_rna`] for transcription onto the stack.
+    fn into_rna(&self) -> Rna<'a> {
+        match *self {
+            Dna::GivenNucleotides(dna) => Rna::DnaBased(dna),
+            Dna::RnaBased(rna) => Rna::GivenNucleotides(rna),
+            Dna::View(view) => Rna::View(view.complement()),
+        }
+    }
+}
+
+impl<'a> RnaTrait<'a> for Rna<'a> {
+    fn new(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str(rna)?;
+        Ok(Self::GivenNucleotides(rna))
+    }
+
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str_with(rna, checks::Alphabet::Iupac)?;
+        Ok(Self::GivenNucleotides(rna))
+    }
+}
+
+impl<'a> RnaToDnaTrait<'a, Dna<'a>> for Rna<'a> {
+    fn into_dna(&self) -> Dna<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => Dna::RnaBased(rna),
+            Rna::DnaBased(dna) => Dna::GivenNucleotides(dna),
+            Rna::View(view) => Dna::View(view.complement()),
+        }
+    }
+}
+
+impl<'a> Dna<'a> {
+    /// Transcribe `self` onto the stack, and call `f` with the result. Return what `f` returns.
+    ///
+    /// Return [`NucleotideError::CapacityExceeded`] (without calling `f`) if `self` is longer than
+    /// [`DEFAULT_MAX_LEN`].
+    pub fn with_rna<R>(&self, f: impl FnOnce(&Rna<'_>) -> R) -> OurResult<R> {
+        self.with_rna_max::<DEFAULT_MAX_LEN, R>(f)
+    }
+
+    /// Like [`Dna::with_rna`], but limited to `MAX_LEN` nucleotides, which must not exceed
+    /// [`MAX_BUFFER_LEN`]. The buffer is the smallest power of two not less than the length.
+    pub fn with_rna_max<const MAX_LEN: usize, R>(
+        &self,
+        f: impl FnOnce(&Rna<'_>) -> R,
+    ) -> OurResult<R> {
+        const {
+            assert!(
+                MAX_LEN <= MAX_BUFFER_LEN,
+                "MAX_LEN must not exceed MAX_BUFFER_LEN"
+            )
+        };
+        let len = self.with_chars(|dna_chars| dna_chars.count());
+        if len > MAX_LEN {
+            return Err(NucleotideError::CapacityExceeded {
+                capacity: MAX_LEN,
+                required: len,
+            });
+        }
+        Ok(self.transcribe_doubling::<[u8; 1], _, _>(len, 1, f))
+    }
+
+    /// Transcribe `self` into a stack buffer of type `B` if it fits. Otherwise recurse for a
+    /// buffer twice as long. `len` is the number of nucleotides of `self`, and `depth` is the
+    /// number of the frames so far (including this one).
+    fn transcribe_doubling<B, F, R>(&self, len: usize, depth: usize, f: F) -> R
+    where
+        B: Buffer,
+        F: FnOnce(&Rna<'_>) -> R,
+    {
+        if len <= size_of::<B>() {
+            debug_assert_eq!(depth, recursion_depth(len));
+            self.transcribe_into::<B, _>(f)
+        } else {
+            self.transcribe_doubling::<B::Doubled, _, _>(len, depth + 1, f)
+        }
+    }
+
+    /// Transcribe `self` into a stack buffer of type `B`, which is long enough. Not inlined, so
+    /// that the buffer takes space in this (the innermost) frame only, and not in each frame of
+    /// [`Dna::transcribe_doubling`].
+    #[inline(never)]
+    fn transcribe_into<B: Buffer, R>(&self, f: impl FnOnce(&Rna<'_>) -> R) -> R {
+        let mut buffer = B::ZEROED;
+        let buffer = buffer.as_mut();
+        let rna_len = self.with_chars(|dna_chars| {
+            utils::char_iter_to_bytes(buffer, dna_chars.map(utils::dna_to_rna))
+        });
+        let rna = str::from_utf8(&buffer[..rna_len]).expect("ASCII nucleotides");
+        f(&Rna::GivenNucleotides(rna))
+    }
+
+    /// Call `f` with an iterator over `self`'s DNA nucleotides. Return what `f` returns.
+    fn with_chars<R>(&self, f: impl FnOnce(&mut dyn Iterator<Item = char>) -> R) -> R {
+        match self {
+            Dna::GivenNucleotides(dna) => f(&mut dna.chars()),
+            Dna::RnaBased(rna) => f(&mut rna.chars().map(utils::rna_to_dna)),
+            Dna::View(view) => f(&mut view.chars(NucleicAcid::Dna)),
+        }
+    }
+
+    /// `self` as a [`NucleotidesView`] (of DNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Dna::GivenNucleotides(dna) => {
+                NucleotidesView::new(dna, NucleicAcid::Dna).expect("DNA nucleotides")
+            }
+            Dna::RnaBased(rna) => NucleotidesView::new(rna, NucleicAcid::Rna)
+                .expect("RNA nucleotides")
+                .complement(),
+            Dna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Dna<'a> {
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Dna::View(self.as_view().complement())
+    }
+
+    /// Create a [`Dna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Dna::View(self.as_view().reverse_complement())
+    }
+}
+
+impl<'a> Rna<'a> {
+    /// Call `f` with an iterator over `self`'s RNA nucleotides. Return what `f` returns.
+    fn with_chars<R>(&self, f: impl FnOnce(&mut dyn Iterator<Item = char>) -> R) -> R {
+        match self {
+            Rna::GivenNucleotides(rna) => f(&mut rna.chars()),
+            Rna::DnaBased(dna) => f(&mut dna.chars().map(utils::dna_to_rna)),
+            Rna::View(view) => f(&mut view.chars(NucleicAcid::Rna)),
+        }
+    }
+
+    /// `self` as a [`NucleotidesView`] (of RNA nucleotides), so that we can complement it.
+    fn as_view(&self) -> NucleotidesView<'a> {
+        match *self {
+            Rna::GivenNucleotides(rna) => {
+                NucleotidesView::new(rna, NucleicAcid::Rna).expect("RNA nucleotides")
+            }
+            Rna::DnaBased(dna) => NucleotidesView::new(dna, NucleicAcid::Dna)
+                .expect("DNA nucleotides")
+                .complement(),
+            Rna::View(view) => view,
+        }
+    }
+}
+
+impl<'a> ComplementTrait for Rna<'a> {
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented yet.
+    fn complement(&self) -> Self {
+        Rna::View(self.as_view().complement())
+    }
+
+    /// Create an [`Rna::View`] instance. No nucleotides are complemented (or reversed) yet.
+    fn reverse_complement(&self) -> Self {
+        Rna::View(self.as_view().reverse_complement())
+    }
+}
+
+impl<'a> PartialEq for Rna<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.with_chars(|self_chars| other.with_chars(|other_chars| self_chars.eq(other_chars)))
+    }
+}
+impl<'a> Eq for Rna<'a> {}
+
+fn write_chars(f: &mut Formatter<'_>, chars: &mut dyn Iterator<Item = char>) -> fmt::Result {
+    for c in chars {
+        write!(f, "{c}")?;
+    }
+    Ok(())
+}
+
+impl<'a> Display for Rna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        self.with_chars(|rna_chars| write_chars(f, rna_chars))
+    }
+}
+
+impl<'a> Debug for Rna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Rna(\"{self}\")")
+    }
+}
+
+impl<'a> PartialEq for Dna<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.with_chars(|self_chars| other.with_chars(|other_chars| self_chars.eq(other_chars)))
+    }
+}
+impl<'a> Eq for Dna<'a> {}
+
+impl<'a> Debug for Dna<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Dna(\"")?;
+        self.with_chars(|dna_chars| write_chars(f, dna_chars))?;
+        write!(f, "\")")
+    }
+}
@@ -0,0 +1,87 @@
+use closure_rnd_modify_any_stack_shr_arr_recurse_binary as dna;
+use closure_rnd_modify_any_stack_shr_arr_recurse_linear as linear;
+use utils::{ComplementTrait, DnaTrait, NucleotideError, OurResult, RnaToDnaTrait, RnaTrait};
+
+const DNA: &str = "ACGTGGTCTTAAGCTAACGTGGTCTTAAGCTAACGTGGTCTTAAGCTA";
+
+#[test]
+fn test_with_rna() -> OurResult<()> {
+    for len in [0, 1, 2, 3, 4, 5, 31, 32, 33, DNA.len()] {
+        let dna = dna::Dna::new(&DNA[..len])?;
+        dna.with_rna(|rna| {
+            assert_eq!(*rna, dna.into_rna());
+            assert_eq!(rna.into_dna(), dna);
+            assert_eq!(
+                rna.reverse_complement(),
+                dna.into_rna().reverse_complement()
+            );
+        })?;
+    }
+    let dna = dna::Dna::new("GCTA")?;
+    assert_eq!(dna.with_rna(|rna| format!("{rna:?}"))?, "Rna(\"CGAU\")");
+    // Transcribing a complemented (lazy) DNA.
+    dna.reverse_complement().with_rna(|rna| {
+        assert_eq!(*rna, dna::Rna::new("AUCG").unwrap());
+    })?;
+    Ok(())
+}
+
+#[test]
+fn test_with_rna_max_len() -> OurResult<()> {
+    let longest = "A".repeat(dna::DEFAULT_MAX_LEN);
+    let num_u = dna::Dna::new(&longest)?.with_rna(|rna| rna.to_string().matches('U').count())?;
+    assert_eq!(num_u, dna::DEFAULT_MAX_LEN);
+
+    let too_long = "A".repeat(dna::DEFAULT_MAX_LEN + 1);
+    let mut called = false;
+    assert_eq!(
+        dna::Dna::new(&too_long)?.with_rna(|_| called = true),
+        Err(NucleotideError::CapacityExceeded {
+            capacity: dna::DEFAULT_MAX_LEN,
+            required: dna::DEFAULT_MAX_LEN + 1
+        })
+    );
+    assert!(!called);
+    Ok(())
+}
+
+#[test]
+fn test_with_rna_max() -> OurResult<()> {
+    let dna = dna::Dna::new(&DNA[..5])?;
+    assert_eq!(dna.with_rna_max::<5, _>(|rna| rna.to_string())?, "UGCAC");
+    assert_eq!(
+        dna.with_rna_max::<4, _>(|_| ()),
+        Err(NucleotideError::CapacityExceeded {
+            capacity: 4,
+            required: 5
+        })
+    );
+
+    let longest = "A".repeat(dna::MAX_BUFFER_LEN);
+    let num_u = dna::Dna::new(&longest)?
+        .with_rna_max::<{ dna::MAX_BUFFER_LEN }, _>(|rna| rna.to_string().matches('U').count())?;
+    assert_eq!(num_u, dna::MAX_BUFFER_LEN);
+    Ok(())
+}
+
+#[test]
+fn test_recursion_depth() {
+    assert_eq!(dna::recursion_depth(0), 1);
+    assert_eq!(dna::recursion_depth(1), 1);
+    assert_eq!(dna::recursion_depth(2), 2);
+    assert_eq!(dna::recursion_depth(5), 4);
+    assert_eq!(dna::recursion_depth(dna::MAX_BUFFER_LEN), 17);
+}
+
+/// Linear recursion is shallower for short sequences only.
+#[test]
+fn test_recursion_depth_against_linear() {
+    for len in 0..=linear::CHUNK_LEN {
+        assert!(dna::recursion_depth(len) >= linear::recursion_depth(len));
+    }
+    for len in (10 * linear::CHUNK_LEN..=dna::MAX_BUFFER_LEN).step_by(7) {
+        assert!(dna::recursion_depth(len) < linear::recursion_depth(len));
+    }
+    assert_eq!(dna::recursion_depth(1000), 11);
+    assert_eq!(linear::recursion_depth(1000), 63);
+}
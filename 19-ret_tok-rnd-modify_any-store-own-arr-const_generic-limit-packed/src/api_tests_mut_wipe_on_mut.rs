@@ -0,0 +1,53 @@
+//! Security unit test. This can't be an integration test (under ../tests/), because it needs
+//! private access to [`dna::RnaImpl::packed`]. We unpack all slots (including unused ones), so
+//! that the shared test sees one byte per nucleotide. Zero bits (wiped, or `C`) show as a zero
+//! byte: with four nucleotides in two bits there's no separate code for an empty slot, so a `C`
+//! left behind is indistinguishable from a wiped slot (and it tells nothing).
+use crate as dna;
+
+use test_harness::api_tests_mut::wipe_on_mut::Tests;
+use utils::api_tests_mut::{WithStorageLeaked, WithStorageLeakedCallBack};
+
+struct T {}
+impl Tests for T {
+    type Dna<'a> = dna::Dna<'a>;
+    type Rna<'a> = dna::Rna;
+}
+
+fn with_storage_leaked(
+    rna: &mut dna::Rna,
+    with_storage_leaked_call_back: WithStorageLeakedCallBack<bool>,
+) -> bool {
+    let mut bytes_iter = (0..dna::Rna::CAPACITY).map(|i| match super::code(&rna.packed, i) {
+        0 => 0,
+        _ => rna.get(i) as u8,
+    });
+    with_storage_leaked_call_back(&mut bytes_iter)
+}
+type _TWithStorageLeaked<'a> = WithStorageLeaked<'a, dna::Rna, bool>;
+const _CHECK_WITH_STORAGE_LEAKED_FUNCTION_SIGNATURE: _TWithStorageLeaked = &with_storage_leaked;
+
+test_harness::api_tests!(wipe_on_mut: T, with_storage_leaked);
+
+/// Unused slots are zero bits, so they don't leak even the (2-bit) nucleotides.
+#[test]
+fn test_unused_storage_is_zeroed() {
+    use utils::{RnaTrait, RnaTraitMut};
+
+    let mut rna = dna::Rna::new("CGAUUAGC").unwrap();
+    assert_eq!(rna.packed[..2], [0b11_10_01_00, 0b00_01_10_11]);
+    rna.set_from_iter(&mut "UA".chars()).unwrap();
+    assert_eq!(rna.packed[..2], [0b10_11, 0]);
+}
+
+/// Complementing in place (which flips bits) touches the used slots only.
+#[test]
+fn test_complement_keeps_unused_storage_zeroed() {
+    use utils::{ComplementMutTrait, RnaTrait};
+
+    let mut rna = dna::Rna::new("CGAUU").unwrap();
+    rna.reverse_complement_in_place();
+    assert_eq!(rna, dna::Rna::new("AAUCG").unwrap());
+    assert_eq!(rna.packed[..2], [0b00_11_10_10, 0b01]);
+    assert!(rna.packed[2..].iter().all(|&byte| byte == 0));
+}
@@ -0,0 +1,317 @@
+//! no_std heapless (bare metal/embedded-friendly). Each RNA nucleotide takes 2 bits only.
+#![no_std]
+
+use core::fmt::{self, Debug, Display, Formatter};
+use utils::api_tests_mut::RnaTraitMutLeakStorage;
+use utils::checks::Alphabet;
+use utils::{
+    checks, ComplementMutTrait, ComplementTrait, DnaTrait, MaterializedNucleotides,
+    NucleotideError, NucleotidesView, OurResult, RnaToDnaTrait, RnaTrait, RnaTraitMut,
+};
+
+#[cfg(test)]
+mod api_tests_mut_wipe_on_mut;
+
+const BITS_PER_NUCLEOTIDE: usize = 2;
+const NUCLEOTIDES_PER_BYTE: usize = 8 / BITS_PER_NUCLEOTIDE;
+const NUCLEOTIDE_MASK: u8 = 0b11;
+/// Both [`checks::RNA_NUCLEOTIDES`] and [`checks::DNA_NUCLEOTIDES`] list complementary nucleotides
+/// next to each other (at an even index and the following odd index). So `code ^ COMPLEMENT_BIT` is
+/// the code of the complementary nucleotide.
+const COMPLEMENT_BIT: u8 = 0b01;
+
+/// 10 bytes store 40 nucleotides - the same as `DEFAULT_MAX_NUCLEOTIDES` in
+/// [03-ret_tok-rnd-modify_any-store-own-arr-const_overall-limit](../../03-ret_tok-rnd-modify_any-store-own-arr-const_overall-limit/src/lib.rs),
+/// but in a quarter of its space.
+const DEFAULT_PACKED_BYTES: usize = 10;
+
+/// 2-bit code in slot `idx` of `packed`.
+fn code(packed: &[u8], idx: usize) -> u8 {
+    let shift = (idx % NUCLEOTIDES_PER_BYTE) * BITS_PER_NUCLEOTIDE;
+    (packed[idx / NUCLEOTIDES_PER_BYTE] >> shift) & NUCLEOTIDE_MASK
+}
+
+/// Store 2-bit `code` in slot `idx` of `packed`.
+fn set_code(packed: &mut [u8], idx: usize, code: u8) {
+    let shift = (idx % NUCLEOTIDES_PER_BYTE) * BITS_PER_NUCLEOTIDE;
+    let byte = &mut packed[idx / NUCLEOTIDES_PER_BYTE];
+    *byte = (*byte & !(NUCLEOTIDE_MASK << shift)) | (code << shift);
+}
+
+/// Nucleotide in slot `idx` of `packed`, as a character of `alphabet` (either
+/// [`checks::RNA_NUCLEOTIDES`] or [`checks::DNA_NUCLEOTIDES`]).
+fn unpack(packed: &[u8], idx: usize, alphabet: &str) -> char {
+    alphabet.as_bytes()[code(packed, idx) as usize] as char
+}
+
+/// Complement the first `len` slots of `packed` in place (without unpacking them), and reverse
+/// them if `reverse`. This works for either alphabet - see [`COMPLEMENT_BIT`]. Unused slots stay
+/// untouched (zeroed).
+fn complement_in_place(packed: &mut [u8], len: usize, reverse: bool) {
+    for i in 0..len {
+        set_code(packed, i, code(packed, i) ^ COMPLEMENT_BIT);
+    }
+    if reverse {
+        for i in 0..len / 2 {
+            let (front, back) = (code(packed, i), code(packed, len - 1 - i));
+            set_code(packed, i, back);
+            set_code(packed, len - 1 - i, front);
+        }
+    }
+}
+
+/// DNA (DNA nucleotide sequence).
+///
+/// `const B` parameter does not affect storage of [`DnaImpl::GivenNucleotides`]. It's used to infer
+/// respective [`RnaImpl`] size when calling [`DnaImpl::into_rna`], and as the storage size of
+/// [`DnaImpl::Materialized`].
+#[derive(Clone, Copy)]
+pub enum DnaImpl<'a, const B: usize = DEFAULT_PACKED_BYTES> {
+    /// Represented by given DNA nucleotides. Returned by [`DnaImpl::new`].
+    GivenNucleotides(&'a str),
+    /// DNA nucleotides materialized (reverse transcribed) from [`RnaImpl`] by
+    /// [`RnaImpl::into_dna`]. Packed the same way as [`RnaImpl`], but as indexes in
+    /// [`checks::DNA_NUCLEOTIDES`].
+    Materialized(PackedDna<B>),
+    /// Complement, reverse complement, or reverse of given nucleotides, *not* transformed. Returned
+    /// by [`ComplementTrait`] methods of [`DnaImpl::GivenNucleotides`], so that they don't need to
+    /// fit in any storage.
+    View(NucleotidesView<'a>),
+}
+
+/// Payload of [`DnaImpl::Materialized`]: `len` packed nucleotides. Its fields are private, so only
+/// [`RnaImpl::into_dna`] creates it, and `len` always fits in `B` bytes.
+#[derive(Clone, Copy)]
+pub struct PackedDna<const B: usize> {
+    packed: [u8; B],
+    len: usize,
+}
+
+pub type Dna<'a> = DnaImpl<'a, DEFAULT_PACKED_BYTES>;
+
+/// RNA (RNA nucleotide sequence), packed four nucleotides per byte. `B` is the number of bytes of
+/// storage, hence the capacity is `4 * B` nucleotides.
+///
+/// Each nucleotide is stored as its 2-bit index in [`checks::RNA_NUCLEOTIDES`]. Nucleotide at index
+/// `i` occupies bits `2 * (i % 4)` and `2 * (i % 4) + 1` of byte `i / 4`. All unused slots are kept
+/// zeroed, so that no previous data leaks (see [`RnaImpl::set_from_iter_impl`]).
+///
+/// Like in
+/// [06-ret_tok-rnd-modify_any-store-own-arr-const_generic-limit](../../06-ret_tok-rnd-modify_any-store-own-arr-const_generic-limit/src/lib.rs),
+/// we don't derive [`PartialEq`], so that we can compare instances regardless of `B`.
+#[derive(Clone)]
+pub struct RnaImpl<const B: usize = DEFAULT_PACKED_BYTES> {
+    packed: [u8; B],
+    len: usize,
+}
+
+pub type Rna = RnaImpl<DEFAULT_PACKED_BYTES>;
+
+impl<'a, const B: usize> DnaTrait<'a, RnaImpl<B>> for DnaImpl<'a, B> {
+    fn new(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna(dna)?;
+        Ok(Self::GivenNucleotides(dna))
+    }
+
+    /// Like [`RnaImpl::new_iupac`]: [`DnaImpl::into_rna`] couldn't pack ambiguity codes, so this
+    /// rejects them, too.
+    fn new_iupac(dna: &'a str) -> OurResult<Self> {
+        checks::check_dna_with(dna, Alphabet::Iupac)?;
+        Self::new(dna)
+    }
+
+    fn into_rna(&self) -> RnaImpl<B> {
+        self.try_into_rna().expect("RNA sequence")
+    }
+
+    /// Return [`NucleotideError::CapacityExceeded`] if the transcribed nucleotides don't fit in
+    /// `4 * B` slots.
+    fn try_into_rna(&self) -> OurResult<RnaImpl<B>> {
+        self.with_rna_chars(|rna_chars| RnaImpl::new_from_iter(rna_chars))
+    }
+}
+
+impl<'a, const B: usize> RnaTrait<'a> for RnaImpl<B> {
+    fn new(rna: &'a str) -> OurResult<Self> {
+        Self::new_from_iter(rna.chars())
+    }
+
+    /// Two bits per nucleotide have no room for ambiguity codes. So this accepts the same
+    /// nucleotides as [`RnaTrait::new`] - but it reports any invalid IUPAC input against the IUPAC
+    /// alphabet first, and only then it rejects any ambiguity code with
+    /// [`NucleotideError::InvalidNucleotide`].
+    fn new_iupac(rna: &'a str) -> OurResult<Self> {
+        checks::check_rna_str_with(rna, Alphabet::Iupac)?;
+        Self::new_from_iter(rna.chars())
+    }
+}
+
+impl<const B: usize> RnaImpl<B> {
+    /// Maximum number of nucleotides.
+    const CAPACITY: usize = NUCLEOTIDES_PER_BYTE * B;
+
+    pub fn new_from_iter(rna_iter: impl Iterator<Item = char>) -> OurResult<Self> {
+        let mut result = Self {
+            packed: [0; B],
+            len: 0,
+        };
+        result.set_from_iter_impl(rna_iter)?;
+        Ok(result)
+    }
+
+    /// We purge any extra leftover data. On error we purge all data and leave `self` empty. (Unlike
+    /// byte-based implementations, we can't store an invalid nucleotide even temporarily.)
+    fn set_from_iter_impl(&mut self, rna_iter: impl Iterator<Item = char>) -> OurResult<()> {
+        let previous_len = self.len;
+        match self.pack(rna_iter) {
+            Ok(len) => {
+                self.len = len;
+                for i in len..previous_len {
+                    self.set(i, 0);
+                }
+                Ok(())
+            }
+            Err(err) => {
+                self.packed = [0; B];
+                self.len = 0;
+                Err(err)
+            }
+        }
+    }
+
+    /// Pack all nucleotides from `rna_iter`, starting at slot `0`. Return their number. Like other
+    /// implementations with a limited storage, report [`NucleotideError::CapacityExceeded`] in
+    /// preference to [`NucleotideError::InvalidNucleotide`].
+    fn pack(&mut self, rna_iter: impl Iterator<Item = char>) -> OurResult<usize> {
+        let mut first_invalid = None;
+        let mut byte_offset = 0usize;
+        let mut len = 0usize;
+        for c in rna_iter {
+            if len < Self::CAPACITY {
+                // RNA_NUCLEOTIDES are ASCII, so a byte position is also a character index.
+                match checks::RNA_NUCLEOTIDES.find(c) {
+                    Some(code) => self.set(len, code as u8),
+                    None => {
+                        first_invalid.get_or_insert(NucleotideError::InvalidNucleotide {
+                            char_index: len,
+                            byte_offset,
+                            found: c,
+                            expected_alphabet: checks::RNA_NUCLEOTIDES,
+                        });
+                    }
+                }
+            }
+            byte_offset += c.len_utf8();
+            len += 1;
+        }
+        if len > Self::CAPACITY {
+            return Err(NucleotideError::CapacityExceeded {
+                capacity: Self::CAPACITY,
+                required: len,
+            });
+        }
+        match first_invalid {
+            Some(err) => Err(err),
+            None => Ok(len),
+        }
+    }
+
+    /// Store 2-bit `code` in slot `idx`.
+    fn set(&mut self, idx: usize, code: u8) {
+        set_code(&mut self.packed, idx, code);
+    }
+
+    /// Nucleotide in slot `idx`. This doesn't check `idx` against `self.len`, so that
+    /// `api_tests_mut_wipe_on_mut` can inspect the unused slots, too.
+    fn get(&self, idx: usize) -> char {
+        unpack(&self.packed, idx, checks::RNA_NUCLEOTIDES)
+    }
+
+    /// Iterate over the stored (unpacked) nucleotides.
+    fn iter(&self) -> impl Iterator<Item = char> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+}
+
+impl<'a, const B: usize> RnaTraitMut<'a> for RnaImpl<B> {
+    fn set_from_iter(&mut self, iter: &mut dyn Iterator<Item = char>) -> OurResult<()> {
+        self.set_from_iter_impl(iter)
+    }
+}
+
+impl<'a, const B: usize> RnaTraitMutLeakStorage<'a> for RnaImpl<B> {}
+
+impl<'a, const B: usize> RnaToDnaTrait<'a, DnaImpl<'a, B>> for RnaImpl<B> {
+    /// Create a [`DnaImpl::Materialized`] instance. [`utils::rna_to_dna`] maps `i`-th nucleotide of
+    /// [`checks::RNA_NUCLEOTIDES`] to `i`-th nucleotide of [`checks::DNA_NUCLEOTIDES`]. So the
+    /// packed bits stay the same - we only copy them.
+    fn into_dna(&self) -> DnaImpl<'a, B> {
+        DnaImpl::Materialized(PackedDna {
+            packed: self.packed,
+            len: self.len,
+        })
+    }
+}
+
+utils::lazy_dna_impls!(DnaImpl<'a, B> [const B: usize]);
+
+impl<const B: usize> MaterializedNucleotides for PackedDna<B> {
+    fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        (0..self.len).map(|i| unpack(&self.packed, i, checks::DNA_NUCLEOTIDES))
+    }
+
+    fn complement_in_place(&mut self, reverse: bool) {
+        complement_in_place(&mut self.packed, self.len, reverse);
+    }
+}
+
+impl<'a, const L: usize, const R: usize> PartialEq<DnaImpl<'_, R>> for DnaImpl<'a, L> {
+    fn eq(&self, other: &DnaImpl<'_, R>) -> bool {
+        self.with_chars(|self_chars| other.with_chars(|other_chars| self_chars.eq(other_chars)))
+    }
+}
+impl<'a, const B: usize> Eq for DnaImpl<'a, B> {}
+
+impl<const L: usize, const R: usize> PartialEq<RnaImpl<R>> for RnaImpl<L> {
+    fn eq(&self, other: &RnaImpl<R>) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+impl<const B: usize> Eq for RnaImpl<B> {}
+
+impl<const B: usize> Display for RnaImpl<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        self.iter().try_for_each(|c| write!(f, "{c}"))?;
+        Ok(())
+    }
+}
+
+impl<const B: usize> Debug for RnaImpl<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Rna(\"{self}\")")
+    }
+}
+
+impl<const B: usize> ComplementTrait for RnaImpl<B> {
+    fn complement(&self) -> Self {
+        let mut result = self.clone();
+        result.complement_in_place();
+        result
+    }
+
+    fn reverse_complement(&self) -> Self {
+        let mut result = self.clone();
+        result.reverse_complement_in_place();
+        result
+    }
+}
+
+impl<const B: usize> ComplementMutTrait for RnaImpl<B> {
+    fn complement_in_place(&mut self) {
+        complement_in_place(&mut self.packed, self.len, false);
+    }
+
+    fn reverse_complement_in_place(&mut self) {
+        complement_in_place(&mut self.packed, self.len, true);
+    }
+}
@@ -0,0 +1,58 @@
+#![no_std]
+
+use ret_tok_rnd_modify_any_store_own_arr_const_generic_limit_packed as dna;
+use test_harness::api_tests_read_only::Tests;
+use test_harness::differential::Tests as DifferentialTests;
+use utils::{checks, DnaTrait, NucleotideError, RnaTrait};
+
+struct T {}
+impl Tests for T {
+    type Dna<'a> = dna::Dna<'a>;
+    type Rna<'a> = dna::Rna;
+
+    const AMBIGUITY_CODES: bool = false;
+}
+
+impl DifferentialTests for T {
+    const MAX_LEN: usize = 40;
+}
+
+test_harness::api_tests!(read_only: T);
+test_harness::api_tests!(capacity: T);
+
+test_harness::api_tests!(differential: T);
+
+/// Two bits per nucleotide can't store ambiguity codes. Invalid IUPAC input is reported against
+/// the IUPAC alphabet first.
+#[test]
+fn test_iupac_rejects_ambiguity_codes() {
+    assert_eq!(
+        dna::Rna::new_iupac("ACGN"),
+        Err(NucleotideError::InvalidNucleotide {
+            char_index: 3,
+            byte_offset: 3,
+            found: 'N',
+            expected_alphabet: checks::RNA_NUCLEOTIDES,
+        })
+    );
+    assert_eq!(dna::Rna::new_iupac("ACGX").unwrap_err().index(), 3);
+    assert!(dna::Rna::new_iupac("ACGU").is_ok());
+    assert_eq!(
+        dna::Dna::new_iupac("ACGX").unwrap_err(),
+        NucleotideError::InvalidNucleotide {
+            char_index: 3,
+            byte_offset: 3,
+            found: 'X',
+            expected_alphabet: checks::DNA_IUPAC_NUCLEOTIDES,
+        }
+    );
+}
+
+/// Sequences that don't fill their last byte compare equal across capacities.
+#[test]
+fn test_partially_used_bytes() {
+    let rna = <dna::RnaImpl<2>>::new("CGAUUAG").unwrap();
+    assert_eq!(rna, <dna::RnaImpl<10>>::new("CGAUUAG").unwrap());
+    assert_ne!(rna, <dna::RnaImpl<10>>::new("CGAUUAGC").unwrap());
+    assert_ne!(rna, <dna::RnaImpl<10>>::new("CGAUUA").unwrap());
+}